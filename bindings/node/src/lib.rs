@@ -0,0 +1,112 @@
+//! Node.js bindings for `window-enumerator`, built with napi-rs.
+//!
+//! Filters are query strings as accepted by
+//! [`parse_query`](window_enumerator::parse_query), so JS callers share the
+//! syntax of the CLI and of `WindowRuleSet`:
+//!
+//! ```js
+//! const { enumerateWindows } = require('window-enumerator');
+//!
+//! for (const window of enumerateWindows('process~chrome sort:title select:1-3')) {
+//!   console.log(window.index, window.title);
+//! }
+//! ```
+
+use napi::{Error, Result};
+use napi_derive::napi;
+use window_enumerator::{parse_query, WindowEnumerator, WindowQuery, WindowState};
+
+/// A window as returned to JavaScript.
+///
+/// Field names are converted to camelCase, e.g. `className` and
+/// `processName`.
+#[napi(object)]
+pub struct WindowInfo {
+    /// The window handle (HWND).
+    pub hwnd: i64,
+    /// The handle of the owner window, if any.
+    pub owner_hwnd: Option<i64>,
+    /// The process ID (PID) that owns the window.
+    pub pid: u32,
+    /// The ID of the thread that created the window.
+    pub thread_id: u32,
+    /// The 1-based index from the enumeration.
+    pub index: u32,
+    /// The window title.
+    pub title: String,
+    /// The window class name.
+    pub class_name: String,
+    /// The name of the process executable.
+    pub process_name: String,
+    /// The full path of the process executable.
+    pub process_file: String,
+    /// The x-coordinate of the top-left corner in screen coordinates.
+    pub x: i32,
+    /// The y-coordinate of the top-left corner in screen coordinates.
+    pub y: i32,
+    /// The width of the window in pixels.
+    pub width: i32,
+    /// The height of the window in pixels.
+    pub height: i32,
+    /// Whether the window is visible.
+    pub is_visible: bool,
+    /// Whether the window is minimized.
+    pub is_minimized: bool,
+    /// Whether the window is maximized.
+    pub is_maximized: bool,
+    /// Whether the window is cloaked by DWM.
+    pub is_cloaked: bool,
+    /// Whether the window did not respond to messages.
+    pub is_hung: bool,
+}
+
+impl From<&window_enumerator::WindowInfo> for WindowInfo {
+    fn from(window: &window_enumerator::WindowInfo) -> Self {
+        Self {
+            hwnd: window.hwnd.as_raw() as i64,
+            owner_hwnd: window.owner_hwnd.map(|owner| owner.as_raw() as i64),
+            pid: window.pid,
+            thread_id: window.thread_id,
+            index: window.index as u32,
+            title: window.title.clone(),
+            class_name: window.class_name.clone(),
+            process_name: window.process_name.clone(),
+            process_file: window.process_file.to_string_lossy().into_owned(),
+            x: window.position.x,
+            y: window.position.y,
+            width: window.position.width,
+            height: window.position.height,
+            is_visible: window.is_visible,
+            is_minimized: window.is_minimized,
+            is_maximized: window.state() == WindowState::Maximized,
+            is_cloaked: window.is_cloaked,
+            is_hung: window.is_hung,
+        }
+    }
+}
+
+/// Enumerates the visible top-level windows, optionally filtered, sorted,
+/// and selected with a query string such as
+/// `"process~chrome sort:title select:1-3"`.
+///
+/// Throws if the query is invalid or the enumeration fails.
+#[napi]
+pub fn enumerate_windows(filter: Option<String>) -> Result<Vec<WindowInfo>> {
+    let (criteria, sort_keys, selection) =
+        parse_query(filter.as_deref().unwrap_or_default()).map_err(to_js_error)?;
+
+    let mut enumerator = WindowEnumerator::new();
+    enumerator.enumerate_all_windows().map_err(to_js_error)?;
+
+    Ok(WindowQuery::new(enumerator.get_windows())
+        .filter(&criteria)
+        .sort(&sort_keys)
+        .select(&selection)
+        .into_iter()
+        .map(WindowInfo::from)
+        .collect())
+}
+
+fn to_js_error(error: window_enumerator::WindowError) -> Error {
+    Error::from_reason(error.to_string())
+}
@@ -0,0 +1,59 @@
+//! Generates the C header of the `ffi` feature.
+
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+/// Writes `window_enumerator.h` into `OUT_DIR` from the `extern "C"`
+/// functions in `src/ffi.rs`, warning if the checked-in
+/// `include/window_enumerator.h` is out of date.
+///
+/// A header that cannot be generated only produces a warning, since the
+/// library itself builds fine without it.
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    use std::path::Path;
+
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=include/window_enumerator.h");
+
+    let (Some(crate_dir), Some(out_dir)) = (
+        std::env::var_os("CARGO_MANIFEST_DIR"),
+        std::env::var_os("OUT_DIR"),
+    ) else {
+        return;
+    };
+    let crate_dir = Path::new(&crate_dir);
+    let header = Path::new(&out_dir).join("window_enumerator.h");
+
+    let config = match cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")) {
+        Ok(config) => config,
+        Err(error) => {
+            println!("cargo:warning=cannot read cbindgen.toml: {}", error);
+            return;
+        }
+    };
+    match cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+    {
+        Ok(bindings) => {
+            bindings.write_to_file(&header);
+        }
+        Err(error) => {
+            println!("cargo:warning=cannot generate the C header: {}", error);
+            return;
+        }
+    }
+
+    let checked_in = crate_dir.join("include/window_enumerator.h");
+    if std::fs::read(&checked_in).ok() != std::fs::read(&header).ok() {
+        println!(
+            "cargo:warning=include/window_enumerator.h is out of date, copy it from {}",
+            header.display()
+        );
+    }
+}
@@ -0,0 +1,166 @@
+#ifndef WINDOW_ENUMERATOR_H
+#define WINDOW_ENUMERATOR_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * An opaque list of windows returned by [`we_enumerate`] and
+ * [`we_filter`].
+ */
+typedef struct WindowListC WindowListC;
+
+/**
+ * A window as seen from C.
+ *
+ * The strings are owned by the [`WindowListC`] the window belongs to.
+ */
+typedef struct WindowInfoC {
+  /**
+   * The window handle (HWND).
+   */
+  ptrdiff_t hwnd;
+  /**
+   * The handle of the owner window, or `0` if the window has no owner.
+   */
+  ptrdiff_t owner_hwnd;
+  /**
+   * The process ID (PID) that owns the window.
+   */
+  uint32_t pid;
+  /**
+   * The ID of the thread that created the window.
+   */
+  uint32_t thread_id;
+  /**
+   * The 1-based index from the enumeration.
+   */
+  size_t index;
+  /**
+   * The window title.
+   */
+  const char *title;
+  /**
+   * The window class name.
+   */
+  const char *class_name;
+  /**
+   * The name of the process executable.
+   */
+  const char *process_name;
+  /**
+   * The full path of the process executable.
+   */
+  const char *process_file;
+  /**
+   * The x-coordinate of the top-left corner in screen coordinates.
+   */
+  int32_t x;
+  /**
+   * The y-coordinate of the top-left corner in screen coordinates.
+   */
+  int32_t y;
+  /**
+   * The width of the window in pixels.
+   */
+  int32_t width;
+  /**
+   * The height of the window in pixels.
+   */
+  int32_t height;
+  /**
+   * Whether the window is visible.
+   */
+  bool is_visible;
+  /**
+   * Whether the window is minimized.
+   */
+  bool is_minimized;
+  /**
+   * Whether the window is maximized.
+   */
+  bool is_maximized;
+  /**
+   * Whether the window is cloaked by DWM.
+   */
+  bool is_cloaked;
+  /**
+   * Whether the window did not respond to messages.
+   */
+  bool is_hung;
+} WindowInfoC;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Enumerates the visible top-level windows with the default options.
+ *
+ * Returns `NULL` on failure. The list must be released with
+ * [`we_free_list`].
+ */
+struct WindowListC *we_enumerate(void);
+
+/**
+ * Filters, sorts, and selects the windows of a list with a query string,
+ * e.g. `"process~chrome sort:title select:1-3"`.
+ *
+ * See [`parse_query`](crate::parse_query) for the syntax. Returns a new
+ * list, or `NULL` if an argument is `NULL` or the query is invalid. The
+ * given list stays valid.
+ *
+ * # Safety
+ *
+ * `list` must come from this library and not have been freed, and
+ * `query` must be a NUL-terminated string.
+ */
+struct WindowListC *we_filter(const struct WindowListC *list, const char *query);
+
+/**
+ * Returns the number of windows in a list, or `0` for `NULL`.
+ *
+ * # Safety
+ *
+ * `list` must be `NULL` or come from this library and not have been freed.
+ */
+size_t we_list_len(const struct WindowListC *list);
+
+/**
+ * Returns the window at a 0-based position of a list, or `NULL` if the
+ * position is out of range.
+ *
+ * The window is valid until the list is freed.
+ *
+ * # Safety
+ *
+ * `list` must be `NULL` or come from this library and not have been freed.
+ */
+const struct WindowInfoC *we_list_get(const struct WindowListC *list, size_t position);
+
+/**
+ * Releases a list and all of its windows and strings. `NULL` is ignored.
+ *
+ * # Safety
+ *
+ * `list` must be `NULL` or come from this library and not have been freed
+ * already.
+ */
+void we_free_list(struct WindowListC *list);
+
+/**
+ * Returns the error message of the last failed call on this thread, or
+ * `NULL` if the last call succeeded.
+ *
+ * The message is valid until the next call on the same thread.
+ */
+const char *we_last_error(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* WINDOW_ENUMERATOR_H */
@@ -0,0 +1,450 @@
+use std::time::{Duration, Instant};
+
+use windows::core::{s, w};
+use windows::Win32::Foundation::*;
+use windows::Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress};
+use windows::Win32::System::Threading::{
+    GetCurrentProcessId, OpenProcess, TerminateProcess, PROCESS_TERMINATE,
+};
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::{Result, WindowError};
+use crate::models::{WS_EX_TOPMOST, WS_MAXIMIZE};
+use crate::types::{CloseMethod, WindowInfo, WindowPlacement, WindowPosition, WindowState};
+
+/// How long [`WindowInfo::request_close`] waits for the window to handle
+/// `WM_CLOSE`.
+const CLOSE_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Actions that change the state of a window.
+impl WindowInfo {
+    /// Makes the window always-on-top, or removes that state.
+    ///
+    /// Updates [`ex_style`](Self::ex_style) on success, so
+    /// [`is_topmost`](Self::is_topmost) reflects the new state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the window cannot be
+    /// repositioned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let criteria = FilterCriteria::builder().topmost(true).build();
+    /// for mut window in enumerator.filter_windows(&criteria) {
+    ///     window.set_topmost(false).unwrap();
+    /// }
+    /// ```
+    pub fn set_topmost(&mut self, topmost: bool) -> Result<()> {
+        let insert_after = if topmost {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+
+        unsafe {
+            SetWindowPos(
+                HWND::from(self.hwnd),
+                insert_after,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            )
+            .map_err(|e| WindowError::api("SetWindowPos", e).for_window(self.hwnd))?;
+        }
+
+        if topmost {
+            self.ex_style |= WS_EX_TOPMOST;
+        } else {
+            self.ex_style &= !WS_EX_TOPMOST;
+        }
+        Ok(())
+    }
+
+    /// Reads the show state and restored bounds of the window
+    /// (`GetWindowPlacement`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the window no longer exists.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let window = enumerator.get_window_by_index(1).unwrap();
+    /// let placement = window.get_placement().unwrap();
+    /// println!("{:?} at {:?}", placement.state, placement.normal_position);
+    /// ```
+    pub fn get_placement(&self) -> Result<WindowPlacement> {
+        let mut placement = WINDOWPLACEMENT {
+            length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+            ..Default::default()
+        };
+        unsafe {
+            GetWindowPlacement(HWND::from(self.hwnd), &mut placement)
+                .map_err(|e| WindowError::api("GetWindowPlacement", e).for_window(self.hwnd))?;
+        }
+
+        let state = match SHOW_WINDOW_CMD(placement.showCmd as i32) {
+            SW_SHOWMINIMIZED => WindowState::Minimized,
+            SW_SHOWMAXIMIZED => WindowState::Maximized,
+            _ => WindowState::Normal,
+        };
+        let rect = placement.rcNormalPosition;
+        Ok(WindowPlacement {
+            state,
+            normal_position: WindowPosition {
+                x: rect.left,
+                y: rect.top,
+                width: rect.right - rect.left,
+                height: rect.bottom - rect.top,
+            },
+            restore_to_maximized: placement.flags.0 & WPF_RESTORETOMAXIMIZED.0 != 0,
+        })
+    }
+
+    /// Restores a placement saved by [`get_placement`](Self::get_placement)
+    /// (`SetWindowPlacement`).
+    ///
+    /// Updates [`is_minimized`](Self::is_minimized) and the maximized bit of
+    /// [`style`](Self::style) on success; call
+    /// [`refresh`](Self::refresh) to update the position as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the window cannot be
+    /// placed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let mut window = enumerator.get_window_by_index(1).unwrap().clone();
+    /// let placement = window.get_placement().unwrap();
+    /// // ... the user rearranges the window ...
+    /// window.apply_placement(&placement).unwrap();
+    /// ```
+    pub fn apply_placement(&mut self, placement: &WindowPlacement) -> Result<()> {
+        let show = match placement.state {
+            WindowState::Normal => SW_SHOWNORMAL,
+            WindowState::Minimized => SW_SHOWMINIMIZED,
+            WindowState::Maximized => SW_SHOWMAXIMIZED,
+        };
+        let flags = if placement.restore_to_maximized {
+            WPF_RESTORETOMAXIMIZED
+        } else {
+            WINDOWPLACEMENT_FLAGS(0)
+        };
+        let position = placement.normal_position;
+        let raw = WINDOWPLACEMENT {
+            length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+            flags,
+            showCmd: show.0 as u32,
+            ptMinPosition: POINT { x: -1, y: -1 },
+            ptMaxPosition: POINT { x: -1, y: -1 },
+            rcNormalPosition: RECT {
+                left: position.x,
+                top: position.y,
+                right: position.x + position.width,
+                bottom: position.y + position.height,
+            },
+        };
+
+        unsafe {
+            SetWindowPlacement(HWND::from(self.hwnd), &raw)
+                .map_err(|e| WindowError::api("SetWindowPlacement", e).for_window(self.hwnd))?;
+        }
+
+        self.is_minimized = placement.state == WindowState::Minimized;
+        if placement.state == WindowState::Maximized {
+            self.style |= WS_MAXIMIZE;
+        } else {
+            self.style &= !WS_MAXIMIZE;
+        }
+        Ok(())
+    }
+
+    /// Flashes the window's caption and taskbar button `count` times
+    /// (`FlashWindowEx`).
+    ///
+    /// A zero `rate` flashes at the cursor blink rate.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// if let Some(window) = enumerator.find_by_title("Build").first() {
+    ///     window.flash(3, Duration::from_millis(500));
+    /// }
+    /// ```
+    pub fn flash(&self, count: u32, rate: Duration) {
+        self.flash_with(FLASHW_ALL, count, rate);
+    }
+
+    /// Flashes the window's taskbar button until the user brings the window
+    /// to the foreground, as applications do when they need attention.
+    pub fn demand_attention(&self) {
+        self.flash_with(FLASHW_TRAY | FLASHW_TIMERNOFG, 0, Duration::ZERO);
+    }
+
+    /// Stops flashing started by [`flash`](Self::flash) or
+    /// [`demand_attention`](Self::demand_attention).
+    pub fn stop_flashing(&self) {
+        self.flash_with(FLASHW_STOP, 0, Duration::ZERO);
+    }
+
+    /// Calls `FlashWindowEx`, whose result is the previous caption state
+    /// rather than an error.
+    fn flash_with(&self, flags: FLASHWINFO_FLAGS, count: u32, rate: Duration) {
+        let info = FLASHWINFO {
+            cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+            hwnd: HWND::from(self.hwnd),
+            dwFlags: flags,
+            uCount: count,
+            dwTimeout: rate.as_millis().min(u32::MAX as u128) as u32,
+        };
+        unsafe {
+            FlashWindowEx(&info);
+        }
+    }
+
+    /// Asks the window to close (`WM_CLOSE`), as clicking its close button
+    /// does.
+    ///
+    /// The application may still refuse, e.g. by asking to save changes.
+    /// Use [`WindowEnumerator::wait_for_close`] to find out whether the
+    /// window actually closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::TimedOut`] if the window is hung or did not
+    /// handle the message within five seconds, and
+    /// [`WindowError::ApiCallFailed`] if the message cannot be sent.
+    pub fn request_close(&self) -> Result<()> {
+        self.send_close(CLOSE_REQUEST_TIMEOUT)
+    }
+
+    /// Makes the window go away, escalating until it does.
+    ///
+    /// The window is first asked to close (`WM_CLOSE`), then ended like
+    /// "End task" in the taskbar does (`EndTask`), and finally its process
+    /// is terminated. Each step waits up to `timeout` for the window to
+    /// disappear.
+    ///
+    /// # Returns
+    ///
+    /// Which step made the window go away.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::WindowNotFound`] if the handle no longer
+    /// names this window, e.g. because it was closed and reused by another
+    /// process, [`WindowError::ApiCallFailed`] if the process cannot be
+    /// terminated, e.g. because it is elevated, [`WindowError::TimedOut`]
+    /// if the window survives its termination, and [`WindowError::Other`]
+    /// if the window belongs to the calling process, which is never
+    /// terminated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let criteria = FilterCriteria::builder().process_name_contains("crashreporter").build();
+    /// for window in enumerator.filter_windows(&criteria) {
+    ///     let method = window.force_close(Duration::from_secs(3)).unwrap();
+    ///     println!("{} closed by {:?}", window.hwnd, method);
+    /// }
+    /// ```
+    pub fn force_close(&self, timeout: Duration) -> Result<CloseMethod> {
+        let hwnd = HWND::from(self.hwnd);
+        let closed = |timeout| {
+            WindowEnumerator::wait_for_thread_window_close(self.hwnd, self.thread_id, timeout)
+        };
+
+        // Hung windows and unanswered save prompts fall through to EndTask
+        self.ensure_same_window()?;
+        let started = Instant::now();
+        let _ = self.send_close(timeout);
+        let remaining = timeout.saturating_sub(started.elapsed());
+        if closed(remaining) {
+            return Ok(CloseMethod::Requested);
+        }
+
+        self.ensure_same_window()?;
+        if unsafe { end_task(hwnd) } && closed(timeout) {
+            return Ok(CloseMethod::EndTask);
+        }
+
+        self.ensure_same_window()?;
+        if self.pid == unsafe { GetCurrentProcessId() } {
+            return Err(WindowError::Other(
+                "Refusing to terminate the current process".to_string(),
+            ));
+        }
+        unsafe {
+            let process = OpenProcess(PROCESS_TERMINATE, false, self.pid)
+                .map_err(|e| WindowError::api("OpenProcess", e).for_window(self.hwnd))?;
+            let terminated = TerminateProcess(process, 1);
+            CloseHandle(process).ok();
+            terminated
+                .map_err(|e| WindowError::api("TerminateProcess", e).for_window(self.hwnd))?;
+        }
+        if closed(timeout) {
+            Ok(CloseMethod::Terminated)
+        } else {
+            Err(WindowError::TimedOut)
+        }
+    }
+
+    /// Checks that the handle still names this window, as handles and PIDs
+    /// are recycled once a window or process is gone.
+    fn ensure_same_window(&self) -> Result<()> {
+        let (thread_id, pid) =
+            unsafe { WindowEnumerator::get_thread_process_id(HWND::from(self.hwnd)) };
+        if thread_id == 0 || thread_id != self.thread_id || pid != self.pid {
+            return Err(WindowError::WindowNotFound);
+        }
+        Ok(())
+    }
+
+    /// Sends `WM_CLOSE`, waiting up to `timeout` for it to be handled.
+    fn send_close(&self, timeout: Duration) -> Result<()> {
+        self.send_message_timeout(WM_CLOSE, 0, 0, timeout)
+            .map(|_| ())
+    }
+
+    /// Places a message in the message queue of the window's thread and
+    /// returns without waiting (`PostMessageW`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the message cannot be
+    /// posted, e.g. because the window was closed or the queue is full.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// const WM_APP_RELOAD: u32 = 0x8000 + 1;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// let criteria = FilterCriteria::builder().class_name_contains("MyAppWindow").build();
+    /// for window in enumerator.filter_windows(&criteria) {
+    ///     window.post_message(WM_APP_RELOAD, 0, 0).unwrap();
+    /// }
+    /// ```
+    pub fn post_message(&self, msg: u32, wparam: usize, lparam: isize) -> Result<()> {
+        unsafe { PostMessageW(HWND::from(self.hwnd), msg, WPARAM(wparam), LPARAM(lparam)) }
+            .map_err(|e| WindowError::api("PostMessageW", e).for_window(self.hwnd))
+    }
+
+    /// Sends a message to the window and waits up to `timeout` for the
+    /// result (`SendMessageTimeoutW`), returning early if the window is hung.
+    ///
+    /// Pointers in `wparam` or `lparam` are only marshalled across
+    /// processes for system messages such as `WM_GETTEXT`.
+    ///
+    /// # Returns
+    ///
+    /// The result of processing the message, which depends on the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::TimedOut`] if the window is hung or did not
+    /// handle the message in time, and [`WindowError::ApiCallFailed`] if
+    /// the message cannot be sent.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// const WM_GETTEXTLENGTH: u32 = 0x000E;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// let window = enumerator.get_window_by_index(1).unwrap();
+    /// let length = window
+    ///     .send_message_timeout(WM_GETTEXTLENGTH, 0, 0, Duration::from_millis(200))
+    ///     .unwrap();
+    /// ```
+    pub fn send_message_timeout(
+        &self,
+        msg: u32,
+        wparam: usize,
+        lparam: isize,
+        timeout: Duration,
+    ) -> Result<isize> {
+        let millis = timeout.as_millis().min(u32::MAX as u128) as u32;
+        let mut result: usize = 0;
+        let sent = unsafe {
+            SendMessageTimeoutW(
+                HWND::from(self.hwnd),
+                msg,
+                WPARAM(wparam),
+                LPARAM(lparam),
+                SMTO_ABORTIFHUNG,
+                millis,
+                Some(&mut result),
+            )
+        };
+        if sent.0 != 0 {
+            return Ok(result as isize);
+        }
+
+        let error = windows::core::Error::from_win32();
+        if error.code() == ERROR_TIMEOUT.to_hresult() {
+            Err(WindowError::TimedOut)
+        } else {
+            Err(WindowError::api("SendMessageTimeoutW", error).for_window(self.hwnd))
+        }
+    }
+}
+
+/// Ends a window's application like "End task" in the taskbar, returning
+/// whether the call succeeded.
+///
+/// `EndTask` is exported by user32 but not declared by the `windows`
+/// crate, so it is resolved at runtime.
+unsafe fn end_task(hwnd: HWND) -> bool {
+    type EndTask = unsafe extern "system" fn(HWND, BOOL, BOOL) -> BOOL;
+
+    let Ok(user32) = GetModuleHandleW(w!("user32.dll")) else {
+        return false;
+    };
+    let Some(function) = GetProcAddress(user32, s!("EndTask")) else {
+        return false;
+    };
+    let function = std::mem::transmute::<unsafe extern "system" fn() -> isize, EndTask>(function);
+    function(hwnd, false.into(), true.into()).as_bool()
+}
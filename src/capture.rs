@@ -0,0 +1,153 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::*;
+use windows::Win32::Storage::Xps::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::errors::{Result, WindowError};
+use crate::types::{WindowCapture, WindowInfo};
+
+/// Window capture functionality for [`WindowInfo`].
+///
+/// Requires the `capture` feature.
+impl WindowInfo {
+    /// Captures an image of the whole window, including its frame.
+    ///
+    /// Uses `PrintWindow`, which also works for windows that are covered by
+    /// other windows, and falls back to copying from the screen if the
+    /// window does not support it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if a GDI call fails. A window
+    /// without a visible area, e.g. of zero size, gives an empty capture.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let enumerator = WindowEnumerator::new();
+    /// let window = enumerator.foreground_window().unwrap();
+    /// let image = window.capture().unwrap();
+    /// println!("{}x{}, {} bytes", image.width, image.height, image.data.len());
+    /// ```
+    pub fn capture(&self) -> Result<WindowCapture> {
+        unsafe { capture_window(HWND::from(self.hwnd), false) }
+    }
+
+    /// Captures an image of the window's client area only, without its frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if a GDI call fails. A window
+    /// without a client area gives an empty capture.
+    pub fn capture_client_area(&self) -> Result<WindowCapture> {
+        unsafe { capture_window(HWND::from(self.hwnd), true) }
+    }
+}
+
+/// Captures a window into an RGBA buffer.
+unsafe fn capture_window(hwnd: HWND, client_area_only: bool) -> Result<WindowCapture> {
+    let mut rect = RECT::default();
+    if client_area_only {
+        GetClientRect(hwnd, &mut rect)
+            .map_err(|e| WindowError::api("GetClientRect", e).for_window(hwnd.into()))?;
+    } else {
+        GetWindowRect(hwnd, &mut rect)
+            .map_err(|e| WindowError::api("GetWindowRect", e).for_window(hwnd.into()))?;
+    }
+    let width = rect.right - rect.left;
+    let height = rect.bottom - rect.top;
+    if width <= 0 || height <= 0 {
+        return Ok(WindowCapture {
+            width: 0,
+            height: 0,
+            data: Vec::new(),
+        });
+    }
+
+    let (window_dc, get_dc) = if client_area_only {
+        (GetDC(hwnd), "GetDC")
+    } else {
+        (GetWindowDC(hwnd), "GetWindowDC")
+    };
+    if window_dc.is_invalid() {
+        return Err(WindowError::last_api(get_dc).for_window(hwnd.into()));
+    }
+    let memory_dc = CreateCompatibleDC(window_dc);
+    if memory_dc.is_invalid() {
+        let error = WindowError::last_api("CreateCompatibleDC").for_window(hwnd.into());
+        ReleaseDC(hwnd, window_dc);
+        return Err(error);
+    }
+    let bitmap = CreateCompatibleBitmap(window_dc, width, height);
+    if bitmap.is_invalid() {
+        let error = WindowError::last_api("CreateCompatibleBitmap").for_window(hwnd.into());
+        DeleteDC(memory_dc);
+        ReleaseDC(hwnd, window_dc);
+        return Err(error);
+    }
+    let previous = SelectObject(memory_dc, bitmap);
+
+    let mut flags = PW_RENDERFULLCONTENT;
+    if client_area_only {
+        flags |= PW_CLIENTONLY.0;
+    }
+    let mut result = if PrintWindow(hwnd, memory_dc, PRINT_WINDOW_FLAGS(flags)).as_bool() {
+        Ok(())
+    } else {
+        // Fall back to copying whatever is currently on screen
+        BitBlt(memory_dc, 0, 0, width, height, window_dc, 0, 0, SRCCOPY)
+            .map_err(|e| WindowError::api("BitBlt", e).for_window(hwnd.into()))
+    };
+
+    // Read the bitmap as top-down 32-bit BGRA
+    let mut data = vec![0u8; width as usize * height as usize * 4];
+    if result.is_ok() {
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // Deselect the bitmap first; GetDIBits requires it not to be selected
+        SelectObject(memory_dc, previous);
+        let lines = GetDIBits(
+            memory_dc,
+            bitmap,
+            0,
+            height as u32,
+            Some(data.as_mut_ptr() as *mut std::ffi::c_void),
+            &mut info,
+            DIB_RGB_COLORS,
+        );
+        if lines == 0 {
+            result = Err(WindowError::last_api("GetDIBits").for_window(hwnd.into()));
+        }
+    } else {
+        SelectObject(memory_dc, previous);
+    }
+
+    DeleteObject(bitmap);
+    DeleteDC(memory_dc);
+    ReleaseDC(hwnd, window_dc);
+    result?;
+
+    // Convert BGRA to RGBA with an opaque alpha channel
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        pixel[3] = 255;
+    }
+
+    Ok(WindowCapture {
+        width: width as u32,
+        height: height as u32,
+        data,
+    })
+}
@@ -0,0 +1,171 @@
+use std::os::windows::ffi::OsStringExt;
+use std::time::Duration;
+
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::enumerator::WindowEnumerator;
+use crate::types::{ControlKind, ControlText, WindowInfo};
+
+// List box and combo box messages and styles, from WinUser.h
+const LB_GETTEXT: u32 = 0x0189;
+const LB_GETTEXTLEN: u32 = 0x018A;
+const LB_GETCOUNT: u32 = 0x018B;
+const CB_GETLBTEXT: u32 = 0x0148;
+const CB_GETLBTEXTLEN: u32 = 0x0149;
+const CB_GETCOUNT: u32 = 0x0146;
+const LBS_OWNERDRAW: u32 = 0x0030;
+const LBS_HASSTRINGS: u32 = 0x0040;
+const CBS_OWNERDRAW: u32 = 0x0030;
+const CBS_HASSTRINGS: u32 = 0x0200;
+
+/// The messages for reading the entries of a list box or combo box.
+struct ListMessages {
+    count: u32,
+    text_len: u32,
+    text: u32,
+}
+
+/// Reading the contents of child controls.
+impl WindowInfo {
+    /// Reads the text of the standard child controls of the window: text
+    /// boxes, labels, buttons, list boxes, and combo boxes, including
+    /// controls nested in group boxes or panels.
+    ///
+    /// Each control gets at most `timeout` per message to answer, so a hung
+    /// dialog yields empty texts instead of blocking. Controls of other
+    /// kinds, e.g. list views or custom-drawn controls, are skipped;
+    /// password boxes report no text.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::{ControlKind, FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let criteria = FilterCriteria::builder().class_name_contains("#32770").build();
+    /// for dialog in enumerator.filter_windows(&criteria) {
+    ///     for control in dialog.control_texts(Duration::from_millis(200)) {
+    ///         if control.kind == ControlKind::Static && !control.text.is_empty() {
+    ///             println!("{}: {}", dialog.title, control.text);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn control_texts(&self, timeout: Duration) -> Vec<ControlText> {
+        let mut children: Vec<HWND> = Vec::new();
+        unsafe {
+            let _ = EnumChildWindows(
+                HWND::from(self.hwnd),
+                Some(collect_child_proc),
+                LPARAM(&mut children as *mut _ as isize),
+            );
+        }
+
+        children
+            .into_iter()
+            .filter_map(|hwnd| unsafe { read_control(hwnd, timeout) })
+            .collect()
+    }
+}
+
+/// Collects every descendant window into the `Vec<HWND>` behind `lparam`.
+unsafe extern "system" fn collect_child_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let children = &mut *(lparam.0 as *mut Vec<HWND>);
+    children.push(hwnd);
+    TRUE
+}
+
+/// Reads the text of a child control, or returns `None` if it is not a
+/// standard control.
+unsafe fn read_control(hwnd: HWND, timeout: Duration) -> Option<ControlText> {
+    let class_name = WindowEnumerator::get_class_name(hwnd);
+    let kind = ControlKind::from_class_name(&class_name)?;
+    let style = GetWindowLongW(hwnd, GWL_STYLE) as u32;
+
+    let items = match kind {
+        ControlKind::ListBox if style & LBS_OWNERDRAW == 0 || style & LBS_HASSTRINGS != 0 => {
+            let messages = ListMessages {
+                count: LB_GETCOUNT,
+                text_len: LB_GETTEXTLEN,
+                text: LB_GETTEXT,
+            };
+            read_items(hwnd, &messages, timeout)
+        }
+        ControlKind::ComboBox if style & CBS_OWNERDRAW == 0 || style & CBS_HASSTRINGS != 0 => {
+            let messages = ListMessages {
+                count: CB_GETCOUNT,
+                text_len: CB_GETLBTEXTLEN,
+                text: CB_GETLBTEXT,
+            };
+            read_items(hwnd, &messages, timeout)
+        }
+        _ => Vec::new(),
+    };
+
+    Some(ControlText {
+        hwnd: hwnd.into(),
+        class_name,
+        kind,
+        control_id: GetDlgCtrlID(hwnd),
+        is_visible: IsWindowVisible(hwnd).as_bool(),
+        text: WindowEnumerator::get_window_text_timeout(hwnd, None, timeout).unwrap_or_default(),
+        items,
+    })
+}
+
+/// Reads the entries of a list box or combo box, stopping at the first
+/// entry that cannot be read in time.
+unsafe fn read_items(hwnd: HWND, messages: &ListMessages, timeout: Duration) -> Vec<String> {
+    let Some(count) = send(hwnd, messages.count, 0, 0, timeout) else {
+        return Vec::new();
+    };
+
+    let mut items = Vec::new();
+    for index in 0..count.max(0) as usize {
+        // Both length messages return LB_ERR / CB_ERR (-1) for bad indices
+        let Some(len) = send(hwnd, messages.text_len, index, 0, timeout).filter(|&len| len >= 0)
+        else {
+            break;
+        };
+        let mut buffer = vec![0u16; len as usize + 1];
+        let lparam = buffer.as_mut_ptr() as isize;
+        let Some(copied) = send(hwnd, messages.text, index, lparam, timeout).filter(|&n| n >= 0)
+        else {
+            break;
+        };
+        let copied = (copied as usize).min(len as usize);
+        items.push(
+            std::ffi::OsString::from_wide(&buffer[..copied])
+                .to_string_lossy()
+                .into_owned(),
+        );
+    }
+    items
+}
+
+/// Sends a message with a timeout, returning its result or `None` if the
+/// control did not answer.
+unsafe fn send(
+    hwnd: HWND,
+    msg: u32,
+    wparam: usize,
+    lparam: isize,
+    timeout: Duration,
+) -> Option<isize> {
+    let millis = timeout.as_millis().min(u32::MAX as u128) as u32;
+    let mut result: usize = 0;
+    let sent = SendMessageTimeoutW(
+        hwnd,
+        msg,
+        WPARAM(wparam),
+        LPARAM(lparam),
+        SMTO_ABORTIFHUNG | SMTO_ERRORONEXIT,
+        millis,
+        Some(&mut result),
+    );
+    (sent.0 != 0).then_some(result as isize)
+}
@@ -1,154 +1,1875 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::os::windows::ffi::OsStringExt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use windows::core::*;
 use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Dwm::*;
+use windows::Win32::Graphics::Gdi::*;
+use windows::Win32::Security::{
+    GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, LookupAccountSidW,
+    TokenElevation, TokenIntegrityLevel, TokenUser, SID_NAME_USE, TOKEN_ELEVATION,
+    TOKEN_MANDATORY_LABEL, TOKEN_QUERY, TOKEN_USER,
+};
+use windows::Win32::Storage::Packaging::Appx::{GetApplicationUserModelId, GetPackageFullName};
+use windows::Win32::System::Diagnostics::ToolHelp::*;
+use windows::Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress};
 use windows::Win32::System::ProcessStatus::*;
+use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+use windows::Win32::System::StationsAndDesktops::{
+    CloseDesktop, EnumDesktopWindows, OpenDesktopW, DESKTOP_CONTROL_FLAGS, DESKTOP_ENUMERATE,
+    DESKTOP_READOBJECTS,
+};
+use windows::Win32::System::SystemInformation::{
+    IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64,
+    IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_I386, IMAGE_FILE_MACHINE_UNKNOWN,
+};
 use windows::Win32::System::Threading::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
 
+#[cfg(feature = "command-line")]
+use windows::Wdk::System::Threading::{NtQueryInformationProcess, ProcessCommandLineInformation};
+
 use crate::errors::{Result, WindowError};
-use crate::types::{FilterCriteria, WindowInfo, WindowPosition};
+use crate::types::{
+    EnumerationOptions, Filter, FilterCriteria, IntegrityLevel, KnownWindowClass, MonitorInfo,
+    ProcessArch, ProcessCacheStats, ProcessMetrics, ProcessWindows, RefreshSummary, ScoreCriteria,
+    TableStyle, UwpAppInfo, WindowGeometry, WindowHandle, WindowInfo, WindowList, WindowPosition,
+    WindowQuery,
+};
 use crate::utils;
 
 #[cfg(feature = "selection")]
 use crate::types::Selection;
 
+#[cfg(feature = "signing")]
+use crate::types::SignatureStatus;
+
 #[cfg(feature = "sorting")]
-use crate::types::SortCriteria;
+use crate::types::{SortKey, SortKeys};
 
 #[cfg(feature = "sorting")]
 use crate::models::WindowSorter;
 
+/// A process listed by [`WindowEnumerator::snapshot_processes`].
+pub(crate) struct ProcessEntry {
+    pub(crate) pid: u32,
+    pub(crate) parent_pid: u32,
+    pub(crate) thread_count: u32,
+    /// The executable file name, e.g. `notepad.exe`.
+    pub(crate) exe_name: String,
+}
+
 /// The main window enumeration and inspection interface.
 ///
 /// This struct provides methods to discover, filter, and sort Windows windows
 /// with various criteria. It serves as the primary entry point for the library.
 pub struct WindowEnumerator {
     windows: Vec<WindowInfo>,
+    /// The options of the shorthand methods such as
+    /// [`enumerate_all_windows`](Self::enumerate_all_windows).
+    profile: EnumerationOptions,
+    /// Positions in `windows`, keyed by window handle.
+    hwnd_lookup: HashMap<WindowHandle, usize>,
+    /// Positions in `windows`, keyed by window index.
+    index_lookup: HashMap<usize, usize>,
+    pub(crate) options: EnumerationOptions,
+    /// The kind of the last enumeration, repeated by
+    /// [`refresh`](Self::refresh).
+    scope: EnumerationScope,
+    next_index: usize,
+    process_cache: Mutex<ProcessCache>,
+    persistent_process_cache: bool,
+}
+
+/// Which windows an enumeration collected.
+#[derive(Debug, Clone, Copy)]
+enum EnumerationScope {
+    All,
+    AppWindows,
+    Process(u32),
+}
+
+/// State shared with the window enumeration callbacks.
+struct EnumContext<'a> {
+    options: &'a EnumerationOptions,
+    handles: Vec<WindowHandle>,
+}
+
+impl<'a> EnumContext<'a> {
+    fn new(options: &'a EnumerationOptions) -> Self {
+        Self {
+            options,
+            handles: Vec::new(),
+        }
+    }
+}
+
+/// Process information cached per PID, so that many windows of the same
+/// process only open it once.
+#[derive(Default)]
+struct ProcessCache {
+    entries: HashMap<u32, CachedProcess>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Clone)]
+struct CachedProcess {
+    process_name: String,
+    process_file: std::path::PathBuf,
+    process_device_path: std::path::PathBuf,
+    process_info_error: Option<WindowError>,
+    aumid: Option<String>,
+    is_elevated: Option<bool>,
+    integrity_level: Option<IntegrityLevel>,
+    process_arch: Option<ProcessArch>,
+    user_name: Option<String>,
+    version: Option<ExeVersion>,
+    #[cfg(feature = "signing")]
+    signature: Option<(SignatureStatus, Option<String>)>,
+    session_id: Option<u32>,
+    #[cfg(feature = "command-line")]
+    process_command_line: Option<String>,
+}
+
+/// The strings read from the version resource of an executable.
+#[derive(Clone, Default)]
+pub(crate) struct ExeVersion {
+    pub(crate) product_name: Option<String>,
+    pub(crate) company_name: Option<String>,
+    pub(crate) file_version: Option<String>,
 }
 
-impl WindowEnumerator {
-    /// Creates a new window enumerator.
+impl WindowEnumerator {
+    /// Creates a new window enumerator.
+    ///
+    /// The enumerator starts with no windows loaded. Call [`enumerate_all_windows`]
+    /// to populate it with the current system windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let enumerator = WindowEnumerator::new();
+    /// ```
+    ///
+    /// [`enumerate_all_windows`]: WindowEnumerator::enumerate_all_windows
+    pub fn new() -> Self {
+        Self {
+            windows: Vec::new(),
+            profile: EnumerationOptions::default(),
+            hwnd_lookup: HashMap::new(),
+            index_lookup: HashMap::new(),
+            options: EnumerationOptions::default(),
+            scope: EnumerationScope::All,
+            next_index: 1,
+            process_cache: Mutex::new(ProcessCache::default()),
+            persistent_process_cache: false,
+        }
+    }
+
+    /// Creates an enumerator whose shorthand methods use `options` instead
+    /// of the default profile.
+    ///
+    /// [`enumerate_all_windows`](Self::enumerate_all_windows),
+    /// [`enumerate_all_windows_strict`](Self::enumerate_all_windows_strict),
+    /// [`enumerate_app_windows`](Self::enumerate_app_windows), and
+    /// [`enumerate_windows_of_process`](Self::enumerate_windows_of_process)
+    /// then gather what `options` asks for, and [`refresh`](Self::refresh)
+    /// and the waits use them until another enumeration passes its own.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{EnumerationOptions, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::with_options(EnumerationOptions {
+    ///     include_cloaked: false,
+    ///     include_owned_windows: false,
+    ///     ..EnumerationOptions::minimal()
+    /// });
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// ```
+    pub fn with_options(options: EnumerationOptions) -> Self {
+        Self {
+            profile: options.clone(),
+            options,
+            ..Self::new()
+        }
+    }
+
+    /// Returns the options of the last enumeration, or those given to
+    /// [`with_options`](Self::with_options) before the first one.
+    pub fn options(&self) -> &EnumerationOptions {
+        &self.options
+    }
+
+    /// Enumerates all visible windows on the system.
+    ///
+    /// This method populates the internal window list with all currently
+    /// visible, non-child windows. Each window is assigned a 1-based index.
+    /// The default profile of [`EnumerationOptions`] is used unless the
+    /// enumerator was created [`with_options`](Self::with_options).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the Windows API call fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// ```
+    pub fn enumerate_all_windows(&mut self) -> Result<()> {
+        let options = self.profile.clone();
+        self.enumerate_all_windows_with(&options)
+    }
+
+    /// Enumerates windows, gathering only the requested properties.
+    ///
+    /// Properties excluded by `options` keep their default values, which
+    /// avoids e.g. opening a handle to every owning process when only titles
+    /// are needed. The options also decide whether hidden, minimized, and
+    /// owned windows are included.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the Windows API call fails,
+    /// and [`WindowError::TimedOut`] or [`WindowError::Cancelled`] if the
+    /// enumeration was stopped early by `options`. The windows gathered
+    /// until then are kept in either of the latter cases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{EnumerationOptions, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator
+    ///     .enumerate_all_windows_with(&EnumerationOptions::minimal())
+    ///     .unwrap();
+    ///
+    /// // Find windows of applications minimized to the notification area
+    /// let options = EnumerationOptions {
+    ///     include_hidden: true,
+    ///     ..Default::default()
+    /// };
+    /// enumerator.enumerate_all_windows_with(&options).unwrap();
+    ///
+    /// // Never block the caller for more than 100 ms
+    /// let options = EnumerationOptions {
+    ///     timeout: Some(std::time::Duration::from_millis(100)),
+    ///     ..Default::default()
+    /// };
+    /// match enumerator.enumerate_all_windows_with(&options) {
+    ///     Err(error) if error.is_interrupted() => {
+    ///         println!("partial: {} windows", enumerator.get_windows().len())
+    ///     }
+    ///     result => result.unwrap(),
+    /// }
+    /// ```
+    pub fn enumerate_all_windows_with(&mut self, options: &EnumerationOptions) -> Result<()> {
+        self.enumerate_all_windows_strict_with(options)?;
+        Ok(())
+    }
+
+    /// Enumerates windows with `options` and returns them.
+    ///
+    /// Shorthand for [`enumerate_all_windows_with`](Self::enumerate_all_windows_with)
+    /// followed by [`get_windows`](Self::get_windows).
+    ///
+    /// # Errors
+    ///
+    /// See [`enumerate_all_windows_with`](Self::enumerate_all_windows_with).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{EnumerationOptions, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// let options = EnumerationOptions {
+    ///     include_hidden: true,
+    ///     include_cloaked: false,
+    ///     ..Default::default()
+    /// };
+    /// for window in enumerator.enumerate_with(&options).unwrap() {
+    ///     println!("{}", window.title);
+    /// }
+    /// ```
+    pub fn enumerate_with(&mut self, options: &EnumerationOptions) -> Result<&[WindowInfo]> {
+        self.enumerate_all_windows_with(options)?;
+        Ok(&self.windows)
+    }
+
+    /// Enumerates all visible windows and reports the windows that had to be
+    /// skipped.
+    ///
+    /// [`enumerate_all_windows`](Self::enumerate_all_windows) silently drops
+    /// windows whose information could not be gathered, e.g. because they
+    /// were destroyed during the enumeration. This variant returns those
+    /// windows together with the reason, so callers can verify that every
+    /// window found by `EnumWindows` is accounted for. The successfully
+    /// enumerated windows are available through
+    /// [`get_windows`](Self::get_windows) as usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the enumeration itself fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// let failures = enumerator.enumerate_all_windows_strict().unwrap();
+    /// for (hwnd, error) in &failures {
+    ///     eprintln!("skipped {}: {}", hwnd, error);
+    /// }
+    /// ```
+    pub fn enumerate_all_windows_strict(&mut self) -> Result<Vec<(WindowHandle, WindowError)>> {
+        let options = self.profile.clone();
+        self.enumerate_all_windows_strict_with(&options)
+    }
+
+    /// Like [`enumerate_all_windows_strict`](Self::enumerate_all_windows_strict),
+    /// gathering only the properties requested by `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the enumeration itself
+    /// fails, and [`WindowError::TimedOut`] or [`WindowError::Cancelled`]
+    /// if it was stopped early, keeping the windows gathered until then.
+    pub fn enumerate_all_windows_strict_with(
+        &mut self,
+        options: &EnumerationOptions,
+    ) -> Result<Vec<(WindowHandle, WindowError)>> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("enumerate_all_windows").entered();
+
+        // A timeout too large to represent means no deadline
+        let deadline = options
+            .timeout
+            .and_then(|timeout| Instant::now().checked_add(timeout));
+        self.options = options.clone();
+        self.scope = EnumerationScope::All;
+        self.prepare_process_cache();
+
+        let mut context = EnumContext::new(&self.options);
+        match self.options.desktop {
+            Some(ref desktop) => unsafe { Self::enum_desktop_windows(desktop, &mut context)? },
+            None => unsafe {
+                EnumWindows(
+                    Some(Self::enum_windows_proc),
+                    LPARAM(&mut context as *mut _ as isize),
+                )
+                .map_err(|e| {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(error = %e, "EnumWindows failed");
+                    WindowError::api("EnumWindows", e)
+                })?;
+            },
+        }
+
+        let handles = context.handles;
+        let (windows, failures, interrupted) = self.collect_window_info(&handles, deadline);
+        self.windows = windows;
+        self.finish_enumeration()?;
+        match interrupted {
+            Some(error) => Err(error),
+            None => Ok(failures),
+        }
+    }
+
+    /// Re-enumerates all visible windows while keeping indices stable.
+    ///
+    /// Windows that still exist keep the index they had before, so indices
+    /// stored from a previous run stay meaningful. New windows receive fresh
+    /// indices that were never used before, and indices of closed windows
+    /// are not reused. The previous run is repeated with its options, e.g.
+    /// only application windows are collected again after
+    /// [`enumerate_app_windows`](Self::enumerate_app_windows).
+    ///
+    /// # Returns
+    ///
+    /// The handles of the windows that were added and removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the Windows API call fails.
+    /// If the enumeration is stopped early by a timeout or cancellation, the
+    /// partial results still keep their indices but the error is returned
+    /// instead of the summary.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let changes = enumerator.refresh().unwrap();
+    /// println!("{} opened, {} closed", changes.added.len(), changes.removed.len());
+    /// ```
+    pub fn refresh(&mut self) -> Result<RefreshSummary> {
+        let previous: HashMap<WindowHandle, usize> = self
+            .windows
+            .iter()
+            .map(|window| (window.hwnd, window.index))
+            .collect();
+        let mut next_index = self.next_index;
+
+        // Keep indices stable for partial results too, then report the interruption
+        let options = self.options.clone();
+        let result = match self.scope {
+            EnumerationScope::All => self.enumerate_all_windows_with(&options),
+            EnumerationScope::AppWindows => self.enumerate_app_windows_with(&options),
+            EnumerationScope::Process(pid) => self.enumerate_windows_of_process_with(pid, &options),
+        };
+        let interrupted = match result {
+            Err(error) if error.is_interrupted() => Some(error),
+            result => result.map(|()| None)?,
+        };
+
+        let mut summary = RefreshSummary::default();
+        for window in &mut self.windows {
+            match previous.get(&window.hwnd) {
+                Some(&index) => window.index = index,
+                None => {
+                    window.index = next_index;
+                    next_index += 1;
+                    summary.added.push(window.hwnd);
+                }
+            }
+        }
+        self.next_index = next_index;
+        self.rebuild_lookup();
+
+        summary.removed = previous
+            .into_iter()
+            .filter(|(hwnd, _)| !self.hwnd_lookup.contains_key(hwnd))
+            .map(|(hwnd, _)| hwnd)
+            .collect();
+        summary.removed.sort();
+
+        match interrupted {
+            Some(error) => Err(error),
+            None => Ok(summary),
+        }
+    }
+
+    /// Enumerates the visible top-level windows of a single process.
+    ///
+    /// Instead of scanning the whole desktop, this walks the threads of the
+    /// given process and enumerates only their windows, which is much cheaper
+    /// when targeting one application repeatedly.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the thread snapshot cannot be taken.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_windows_of_process(1234).unwrap();
+    /// ```
+    pub fn enumerate_windows_of_process(&mut self, pid: u32) -> Result<()> {
+        let options = self.profile.clone();
+        self.enumerate_windows_of_process_with(pid, &options)
+    }
+
+    /// Enumerates the windows of a single process, gathering only the
+    /// requested properties.
+    ///
+    /// See [`enumerate_windows_of_process`](Self::enumerate_windows_of_process)
+    /// and [`enumerate_all_windows_with`](Self::enumerate_all_windows_with).
+    /// When `options` name a desktop, that desktop is scanned for the
+    /// windows of the process instead of walking its threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the thread snapshot cannot
+    /// be taken or the desktop cannot be enumerated, and
+    /// [`WindowError::TimedOut`] or [`WindowError::Cancelled`] if the
+    /// enumeration was stopped early, keeping the windows gathered until then.
+    pub fn enumerate_windows_of_process_with(
+        &mut self,
+        pid: u32,
+        options: &EnumerationOptions,
+    ) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("enumerate_windows_of_process", pid).entered();
+
+        // A timeout too large to represent means no deadline
+        let deadline = options
+            .timeout
+            .and_then(|timeout| Instant::now().checked_add(timeout));
+        self.options = options.clone();
+        self.scope = EnumerationScope::Process(pid);
+        self.prepare_process_cache();
+
+        let mut context = EnumContext::new(&self.options);
+        match self.options.desktop {
+            Some(ref desktop) => unsafe {
+                Self::enum_desktop_windows(desktop, &mut context)?;
+                context
+                    .handles
+                    .retain(|&hwnd| Self::get_thread_process_id(hwnd.into()).1 == pid);
+            },
+            None => {
+                for thread_id in Self::get_process_thread_ids(pid)? {
+                    unsafe {
+                        // Fails for threads without windows, which is expected
+                        let _ = EnumThreadWindows(
+                            thread_id,
+                            Some(Self::enum_windows_proc),
+                            LPARAM(&mut context as *mut _ as isize),
+                        );
+                    }
+                }
+            }
+        }
+
+        let handles = context.handles;
+        let (windows, _, interrupted) = self.collect_window_info(&handles, deadline);
+        self.windows = windows;
+        self.finish_enumeration()?;
+        match interrupted {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Collects the top-level windows of a named desktop.
+    unsafe fn enum_desktop_windows(desktop: &str, context: &mut EnumContext) -> Result<()> {
+        let name = HSTRING::from(desktop);
+        let handle = OpenDesktopW(
+            &name,
+            DESKTOP_CONTROL_FLAGS(0),
+            false,
+            DESKTOP_READOBJECTS.0 | DESKTOP_ENUMERATE.0,
+        )
+        .map_err(|e| WindowError::api("OpenDesktopW", e))?;
+
+        let result = EnumDesktopWindows(
+            handle,
+            Some(Self::enum_windows_proc),
+            LPARAM(context as *mut _ as isize),
+        )
+        .map_err(|e| WindowError::api("EnumDesktopWindows", e));
+        CloseDesktop(handle).ok();
+        result
+    }
+
+    /// Retrieves the IDs of all threads belonging to a process.
+    fn get_process_thread_ids(pid: u32) -> Result<Vec<u32>> {
+        let mut thread_ids = Vec::new();
+
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)
+                .map_err(|e| WindowError::api("CreateToolhelp32Snapshot", e))?;
+
+            let mut entry = THREADENTRY32 {
+                dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+                ..Default::default()
+            };
+            let mut found = Thread32First(snapshot, &mut entry).is_ok();
+            while found {
+                if entry.th32OwnerProcessID == pid {
+                    thread_ids.push(entry.th32ThreadID);
+                }
+                found = Thread32Next(snapshot, &mut entry).is_ok();
+            }
+
+            CloseHandle(snapshot).ok();
+        }
+
+        Ok(thread_ids)
+    }
+
+    /// Assigns indices and resolves monitor indices after windows were collected.
+    fn finish_enumeration(&mut self) -> Result<()> {
+        // A window can be reported twice when the Z-order changes mid-enumeration
+        let mut seen = HashSet::new();
+        self.windows.retain(|window| seen.insert(window.hwnd));
+        self.assign_indices();
+
+        #[cfg(feature = "tracing")]
+        {
+            let stats = self.process_cache_stats();
+            tracing::debug!(
+                windows = self.windows.len(),
+                cache_hits = stats.hits,
+                cache_misses = stats.misses,
+                "enumeration finished"
+            );
+        }
+
+        // Drop cached processes that no longer own windows, since their PIDs
+        // may be reused by new processes
+        if self.persistent_process_cache {
+            let pids: HashSet<u32> = self.windows.iter().map(|window| window.pid).collect();
+            self.process_cache_mut()
+                .entries
+                .retain(|pid, _| pids.contains(pid));
+        }
+
+        let processes = if self.options.include_process_metrics || self.options.include_parent_chain
+        {
+            unsafe { Self::snapshot_processes() }
+        } else {
+            Vec::new()
+        };
+
+        // Query each process once, since its windows share the figures
+        if self.options.include_process_metrics {
+            let mut metrics: HashMap<u32, Option<ProcessMetrics>> = HashMap::new();
+            for window in &mut self.windows {
+                window.process_metrics = *metrics.entry(window.pid).or_insert_with(|| {
+                    let process = processes.iter().find(|process| process.pid == window.pid)?;
+                    unsafe { Self::get_process_metrics(window.pid, process.thread_count) }
+                });
+            }
+        }
+
+        // One automation client serves the whole enumeration
+        #[cfg(feature = "uia")]
+        if self.options.include_uia {
+            if let Some(client) = crate::uia::UiaClient::new() {
+                for window in self.windows.iter_mut().filter(|window| !window.is_hung) {
+                    client.load_into(window);
+                }
+            }
+        }
+
+        if self.options.include_parent_chain {
+            let mut chains: HashMap<u32, (Option<u32>, Option<Vec<String>>)> = HashMap::new();
+            for window in &mut self.windows {
+                (window.parent_pid, window.parent_chain) = chains
+                    .entry(window.pid)
+                    .or_insert_with(|| unsafe { Self::get_parent_chain(&processes, window.pid) })
+                    .clone();
+            }
+        }
+
+        // Resolve monitor indices from the monitor handles
+        if self.options.include_position {
+            let monitors = Self::enumerate_monitors_for_metadata();
+            for window in &mut self.windows {
+                window.monitor_index = monitors
+                    .iter()
+                    .find(|monitor| monitor.handle == window.monitor_handle)
+                    .map(|monitor| monitor.index);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enumerates only "real" application windows, as shown by Alt-Tab.
+    ///
+    /// This applies the Alt-Tab heuristic on top of [`enumerate_all_windows`]:
+    /// the window must be visible, not cloaked by DWM, not a tool window
+    /// (unless it is explicitly marked as an app window), unowned or owned by
+    /// a hidden window, and not a UWP `Windows.UI.Core.CoreWindow` hosted
+    /// inside an `ApplicationFrameWindow`. Indices are reassigned afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the Windows API call fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_app_windows().unwrap();
+    /// println!("{}", enumerator);
+    /// ```
+    ///
+    /// [`enumerate_all_windows`]: WindowEnumerator::enumerate_all_windows
+    pub fn enumerate_app_windows(&mut self) -> Result<()> {
+        let options = self.profile.clone();
+        self.enumerate_app_windows_with(&options)
+    }
+
+    /// Enumerates application windows, gathering only the requested properties.
+    ///
+    /// See [`enumerate_app_windows`](Self::enumerate_app_windows) and
+    /// [`enumerate_all_windows_with`](Self::enumerate_all_windows_with).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the Windows API call fails,
+    /// and [`WindowError::TimedOut`] or [`WindowError::Cancelled`] if the
+    /// enumeration was stopped early, keeping the application windows
+    /// gathered until then.
+    pub fn enumerate_app_windows_with(&mut self, options: &EnumerationOptions) -> Result<()> {
+        // Partial results of an interrupted enumeration are filtered as well
+        let result = match self.enumerate_all_windows_with(options) {
+            Err(error) if !error.is_interrupted() => return Err(error),
+            result => result,
+        };
+        self.windows
+            .retain(|window| unsafe { Self::is_alt_tab_window(window) });
+        self.assign_indices();
+        self.scope = EnumerationScope::AppWindows;
+        result
+    }
+
+    /// Assigns 1-based indices to each window in the current list.
+    fn assign_indices(&mut self) {
+        for (index, window) in self.windows.iter_mut().enumerate() {
+            window.index = index + 1;
+        }
+        self.next_index = self.windows.len() + 1;
+        self.rebuild_lookup();
+    }
+
+    /// Indexes the current list by window handle and index, so single
+    /// windows can be looked up without scanning it.
+    fn rebuild_lookup(&mut self) {
+        self.hwnd_lookup.clear();
+        self.index_lookup.clear();
+        for (position, window) in self.windows.iter().enumerate() {
+            self.hwnd_lookup.insert(window.hwnd, position);
+            self.index_lookup.insert(window.index, position);
+        }
+    }
+
+    /// Checks whether a window would be shown in the Alt-Tab switcher.
+    unsafe fn is_alt_tab_window(window: &WindowInfo) -> bool {
+        let hwnd = HWND::from(window.hwnd);
+
+        if !IsWindowVisible(hwnd).as_bool() || window.is_cloaked {
+            return false;
+        }
+
+        if window.is_tool_window() && !window.is_app_window() {
+            return false;
+        }
+
+        if KnownWindowClass::CoreWindow.matches(&window.class_name) {
+            return false;
+        }
+
+        let owner = GetWindow(hwnd, GW_OWNER);
+        owner.0 == 0 || !IsWindowVisible(owner).as_bool()
+    }
+
+    /// Returns the hung window a `Ghost` window stands in for.
+    ///
+    /// `HungWindowFromGhostWindow` is exported by user32 but not declared by
+    /// the `windows` crate, so it is resolved at runtime.
+    unsafe fn get_hung_window_of_ghost(hwnd: HWND) -> Option<WindowHandle> {
+        type HungWindowFromGhostWindow = unsafe extern "system" fn(HWND) -> HWND;
+
+        let user32 = GetModuleHandleW(w!("user32.dll")).ok()?;
+        let function = GetProcAddress(user32, s!("HungWindowFromGhostWindow"))?;
+        let function = std::mem::transmute::<
+            unsafe extern "system" fn() -> isize,
+            HungWindowFromGhostWindow,
+        >(function);
+        let hung = function(hwnd);
+        (hung.0 != 0).then(|| hung.into())
+    }
+
+    /// Checks whether DWM has cloaked a window (e.g. suspended UWP apps or
+    /// windows on another virtual desktop).
+    pub(crate) unsafe fn is_window_cloaked(hwnd: HWND) -> bool {
+        let mut cloaked: u32 = 0;
+        DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_CLOAKED,
+            &mut cloaked as *mut _ as *mut std::ffi::c_void,
+            std::mem::size_of::<u32>() as u32,
+        )
+        .is_ok()
+            && cloaked != 0
+    }
+
+    /// Retrieves the top-level window at the given screen coordinates.
+    ///
+    /// Does not require a prior enumeration. If the window was enumerated
+    /// before, its index is preserved; otherwise the index is `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::WindowNotFound`] if there is no window at the point.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let enumerator = WindowEnumerator::new();
+    /// let window = enumerator.window_at_point(100, 100).unwrap();
+    /// println!("{} ({})", window.title, window.process_name);
+    /// ```
+    pub fn window_at_point(&self, x: i32, y: i32) -> Result<WindowInfo> {
+        unsafe {
+            let hwnd = WindowFromPoint(POINT { x, y });
+            if hwnd.0 == 0 {
+                return Err(WindowError::WindowNotFound);
+            }
+            self.get_single_window_info(GetAncestor(hwnd, GA_ROOT))
+        }
+    }
+
+    /// Retrieves the top-level window currently under the mouse cursor.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the cursor position cannot be
+    /// read, or [`WindowError::WindowNotFound`] if there is no window under it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let enumerator = WindowEnumerator::new();
+    /// let window = enumerator.window_under_cursor().unwrap();
+    /// println!("{:#}", window);
+    /// ```
+    pub fn window_under_cursor(&self) -> Result<WindowInfo> {
+        let mut point = POINT::default();
+        unsafe { GetCursorPos(&mut point).map_err(|e| WindowError::api("GetCursorPos", e))? };
+        self.window_at_point(point.x, point.y)
+    }
+
+    /// Retrieves the current foreground (active) window.
+    ///
+    /// Does not require a prior enumeration, which makes it cheap enough to
+    /// poll from time-tracking tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::WindowNotFound`] if no window is in the foreground,
+    /// e.g. while the focus is switching between windows.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let enumerator = WindowEnumerator::new();
+    /// let active = enumerator.foreground_window().unwrap();
+    /// println!("{} - {}", active.process_name, active.title);
+    /// ```
+    pub fn foreground_window(&self) -> Result<WindowInfo> {
+        unsafe { self.get_single_window_info(GetForegroundWindow()) }
+    }
+
+    /// Ranks the enumerated windows by how well they match fuzzy criteria,
+    /// best candidate first.
+    ///
+    /// See [`ScoreCriteria`]; the current foreground window receives the
+    /// foreground bonus.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{ScoreCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let criteria = ScoreCriteria {
+    ///     process_name: Some("excel.exe".to_string()),
+    ///     title: Some("Budget".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// if let Some((window, score)) = enumerator.score_windows(&criteria).first() {
+    ///     println!("best match: {} ({:.1})", window.title, score);
+    /// }
+    /// ```
+    pub fn score_windows(&self, criteria: &ScoreCriteria) -> Vec<(WindowInfo, f32)> {
+        let foreground = unsafe { GetForegroundWindow() };
+        criteria.rank(&self.windows, Some(foreground.into()))
+    }
+
+    /// Enumerates all display monitors attached to the desktop.
+    ///
+    /// Each monitor is assigned a 1-based index in the order reported by
+    /// the system. These indices match [`WindowInfo::monitor_index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the Windows API call fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// for monitor in WindowEnumerator::enumerate_monitors().unwrap() {
+    ///     println!("{} {:?}", monitor.device_name, monitor.bounds);
+    /// }
+    /// ```
+    pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>> {
+        let mut monitors: Vec<MonitorInfo> = Vec::new();
+
+        unsafe {
+            if !EnumDisplayMonitors(
+                HDC::default(),
+                None,
+                Some(Self::enum_monitors_proc),
+                LPARAM(&mut monitors as *mut _ as isize),
+            )
+            .as_bool()
+            {
+                return Err(WindowError::last_api("EnumDisplayMonitors"));
+            }
+        }
+
+        // Assign 1-based indices to each monitor
+        for (index, monitor) in monitors.iter_mut().enumerate() {
+            monitor.index = index + 1;
+        }
+
+        Ok(monitors)
+    }
+
+    /// Enumerates the monitors to resolve [`WindowInfo::monitor_index`].
+    ///
+    /// Monitor indices are optional metadata, so a failure only leaves them
+    /// unset instead of failing the enumeration.
+    pub(crate) fn enumerate_monitors_for_metadata() -> Vec<MonitorInfo> {
+        Self::enumerate_monitors().unwrap_or_else(|_error| {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %_error, "monitor enumeration failed, monitor indices are unset");
+            Vec::new()
+        })
+    }
+
+    /// Retrieves the full bounds of a monitor, or an empty rectangle if the
+    /// monitor is gone.
+    pub(crate) unsafe fn get_monitor_bounds(monitor_handle: isize) -> WindowPosition {
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        if GetMonitorInfoW(HMONITOR(monitor_handle), &mut info).as_bool() {
+            Self::rect_to_position(&info.rcMonitor)
+        } else {
+            WindowPosition::default()
+        }
+    }
+
+    /// Monitor enumeration callback function.
+    unsafe extern "system" fn enum_monitors_proc(
+        hmonitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        lparam: LPARAM,
+    ) -> BOOL {
+        let monitors = &mut *(lparam.0 as *mut Vec<MonitorInfo>);
+
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(hmonitor, &mut info as *mut _ as *mut MONITORINFO).as_bool() {
+            let name_len = info
+                .szDevice
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(info.szDevice.len());
+            monitors.push(MonitorInfo {
+                handle: hmonitor.0,
+                index: 0, // Temporary value, will be set later
+                device_name: std::ffi::OsString::from_wide(&info.szDevice[..name_len])
+                    .to_string_lossy()
+                    .into_owned(),
+                bounds: Self::rect_to_position(&info.monitorInfo.rcMonitor),
+                work_area: Self::rect_to_position(&info.monitorInfo.rcWork),
+                is_primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            });
+        }
+
+        BOOL::from(true) // Continue enumeration
+    }
+
+    /// Windows enumeration callback function.
+    unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let context = &mut *(lparam.0 as *mut EnumContext);
+
+        // Skip child windows. GetParent would also report the owner of
+        // popups, so ask for the real parent instead.
+        let options = context.options;
+        let is_top_level = GetAncestor(hwnd, GA_PARENT) == GetDesktopWindow();
+        let is_visible = IsWindowVisible(hwnd).as_bool();
+        let is_minimized = IsIconic(hwnd).as_bool();
+        let is_owned = GetWindow(hwnd, GW_OWNER).0 != 0;
+
+        if is_top_level
+            && (options.include_hidden || is_visible)
+            && (options.include_minimized || !is_minimized)
+            && (options.include_owned_windows || !is_owned)
+            && (options.include_cloaked || !Self::is_window_cloaked(hwnd))
+        {
+            context.handles.push(hwnd.into());
+        } else {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                hwnd = %WindowHandle::from(hwnd),
+                is_top_level,
+                is_visible,
+                is_minimized,
+                is_owned,
+                "window skipped by enumeration options"
+            );
+        }
+
+        BOOL::from(true) // Continue enumeration
+    }
+
+    /// Gathers information about the collected windows, in order.
+    ///
+    /// Returns the windows that could be queried and, separately, the
+    /// handles of the windows that were skipped along with the reason.
+    ///
+    /// With the `parallel` feature the per-window queries (titles, process
+    /// info, rectangles) run on the rayon thread pool.
+    ///
+    /// Windows left once `deadline` passes or the enumeration is cancelled
+    /// are skipped, and the reason is returned alongside the results.
+    fn collect_window_info(
+        &self,
+        handles: &[WindowHandle],
+        deadline: Option<Instant>,
+    ) -> (
+        Vec<WindowInfo>,
+        Vec<(WindowHandle, WindowError)>,
+        Option<WindowError>,
+    ) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(candidates = handles.len(), "collecting window information");
+
+        let gather = |hwnd: WindowHandle| {
+            self.check_interrupted(deadline)?;
+            self.get_window_info(hwnd.into())
+        };
+
+        #[cfg(feature = "parallel")]
+        let results: Vec<Result<WindowInfo>> = {
+            use rayon::prelude::*;
+
+            handles.par_iter().map(|&hwnd| gather(hwnd)).collect()
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let results: Vec<Result<WindowInfo>> = handles.iter().map(|&hwnd| gather(hwnd)).collect();
+
+        let mut windows = Vec::with_capacity(results.len());
+        let mut failures = Vec::new();
+        let mut interrupted = None;
+        for (&hwnd, result) in handles.iter().zip(results) {
+            match result {
+                Ok(window) => windows.push(window),
+                Err(error) if error.is_interrupted() => interrupted = Some(error),
+                Err(error) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(hwnd = %hwnd, %error, "window skipped, failed to query its information");
+                    failures.push((hwnd, error));
+                }
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        if let Some(ref error) = interrupted {
+            tracing::warn!(%error, gathered = windows.len(), "enumeration stopped early");
+        }
+
+        (windows, failures, interrupted)
+    }
+
+    /// Fails once the enumeration deadline has passed or it was cancelled.
+    fn check_interrupted(&self, deadline: Option<Instant>) -> Result<()> {
+        if self
+            .options
+            .cancellation
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+        {
+            return Err(WindowError::Cancelled);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(WindowError::TimedOut);
+        }
+        Ok(())
+    }
+
+    /// Gathers information about a specific window.
+    fn get_window_info(&self, hwnd: HWND) -> Result<WindowInfo> {
+        unsafe {
+            // Get window title
+            let max_title_length = self.options.max_title_length;
+            let (title, is_hung) = match self.options.title_timeout {
+                Some(timeout) => {
+                    match Self::get_window_text_timeout(hwnd, max_title_length, timeout) {
+                        Some(title) => (title, false),
+                        None => (String::new(), true),
+                    }
+                }
+                None => (Self::get_window_text_limited(hwnd, max_title_length), false),
+            };
+            let is_hung = is_hung || IsHungAppWindow(hwnd).as_bool();
+
+            // Get owner and root ancestor
+            let owner = GetWindow(hwnd, GW_OWNER);
+            let owner_hwnd = (owner.0 != 0).then(|| owner.into());
+            let root_ancestor = GetAncestor(hwnd, GA_ROOTOWNER).into();
+
+            // Get window class name
+            let class_name = Self::get_class_name(hwnd);
+
+            // Get owning thread and process IDs. A zero thread ID means the
+            // window no longer exists.
+            let (thread_id, pid) = Self::get_thread_process_id(hwnd);
+            if thread_id == 0 {
+                return Err(
+                    WindowError::last_api("GetWindowThreadProcessId").for_window(hwnd.into())
+                );
+            }
+
+            let mut window_info = WindowInfo {
+                hwnd: hwnd.into(),
+                pid,
+                thread_id,
+                title,
+                class_name,
+                owner_hwnd,
+                root_ancestor,
+                is_visible: IsWindowVisible(hwnd).as_bool(),
+                is_minimized: IsIconic(hwnd).as_bool(),
+                is_hung,
+                index: 0, // Temporary value, will be set later
+                ..Default::default()
+            };
+            if KnownWindowClass::Ghost.matches(&window_info.class_name) {
+                window_info.ghost_of = Self::get_hung_window_of_ghost(hwnd);
+            }
+
+            // Get process information
+            if self.options.include_process_info && pid > 0 {
+                self.load_process_info_cached(&mut window_info);
+                if self.options.include_user_name {
+                    window_info.user_name = self.get_user_name_cached(pid);
+                }
+                if self.options.include_version_info {
+                    let version = self.get_exe_version_cached(pid, &window_info.process_file);
+                    window_info.product_name = version.product_name;
+                    window_info.company_name = version.company_name;
+                    window_info.file_version = version.file_version;
+                }
+                #[cfg(feature = "signing")]
+                if self.options.verify_signatures {
+                    if let Some((status, publisher)) =
+                        self.get_signature_cached(pid, &window_info.process_file)
+                    {
+                        window_info.signature_status = Some(status);
+                        window_info.publisher = publisher;
+                    }
+                }
+
+                if KnownWindowClass::ApplicationFrame.matches(&window_info.class_name) {
+                    window_info.uwp_app = Self::get_uwp_app_info(hwnd, pid);
+                }
+                if window_info.is_console() {
+                    window_info.console_client_pid =
+                        Self::get_console_client_pid(&window_info.class_name, pid);
+                }
+
+                // Prefer the window's own AUMID, then the process's, then the hosted app's
+                #[cfg(feature = "aumid")]
+                if let Some(aumid) = Self::get_window_aumid(hwnd) {
+                    window_info.aumid = Some(aumid);
+                }
+                if window_info.aumid.is_none() {
+                    window_info.aumid = window_info
+                        .uwp_app
+                        .as_ref()
+                        .and_then(|app| app.aumid.clone());
+                }
+            }
+
+            // Get window position, size, geometry, and monitor
+            if self.options.include_position {
+                window_info.position = Self::get_window_position(hwnd);
+                window_info.geometry = Self::get_window_geometry(hwnd, &window_info.position);
+                window_info.monitor_handle = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST).0;
+                window_info.monitor_bounds = Self::get_monitor_bounds(window_info.monitor_handle);
+            }
+
+            // Get window style flags and DWM cloaking state
+            if self.options.include_styles {
+                window_info.style = GetWindowLongW(hwnd, GWL_STYLE) as u32;
+                window_info.ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+                window_info.is_cloaked = Self::is_window_cloaked(hwnd);
+            }
+            if self.options.include_class_details {
+                window_info.class_details = Some(crate::inspect::class_details(hwnd));
+            }
+            window_info.kind = window_info.classify();
+
+            // Don't report data gathered from a window destroyed halfway through
+            if !IsWindow(hwnd).as_bool() {
+                return Err(WindowError::WindowNotFound);
+            }
+
+            Ok(window_info)
+        }
+    }
+
+    /// Gathers information about a single window outside of a full enumeration.
+    ///
+    /// The index is taken from the current window list if the window was
+    /// enumerated before, and is `0` otherwise.
+    pub(crate) fn get_single_window_info(&self, hwnd: HWND) -> Result<WindowInfo> {
+        if hwnd.0 == 0 {
+            return Err(WindowError::WindowNotFound);
+        }
+
+        let mut window_info = self.get_window_info(hwnd)?;
+        window_info.index = self
+            .get_window_by_hwnd(window_info.hwnd)
+            .map_or(0, |window| window.index);
+
+        if self.options.include_position {
+            window_info.monitor_index = Self::enumerate_monitors_for_metadata()
+                .iter()
+                .find(|monitor| monitor.handle == window_info.monitor_handle)
+                .map(|monitor| monitor.index);
+        }
+
+        Ok(window_info)
+    }
+
+    /// Fills in the process information of a window, consulting the per-PID
+    /// cache first.
+    unsafe fn load_process_info_cached(&self, window: &mut WindowInfo) {
+        let cached = {
+            let mut cache = self.process_cache();
+            let cached = cache.entries.get(&window.pid).cloned();
+            if cached.is_some() {
+                cache.hits += 1;
+            } else {
+                cache.misses += 1;
+            }
+            cached
+        };
+
+        // Query the process outside the lock so parallel lookups don't serialize
+        let cached = cached.unwrap_or_else(|| {
+            Self::load_process_info(window);
+            let entry = CachedProcess {
+                process_name: window.process_name.clone(),
+                process_file: window.process_file.clone(),
+                process_device_path: window.process_device_path.clone(),
+                process_info_error: window.process_info_error.clone(),
+                aumid: window.aumid.clone(),
+                is_elevated: window.is_elevated,
+                integrity_level: window.integrity_level,
+                process_arch: window.process_arch,
+                user_name: None,
+                version: None,
+                #[cfg(feature = "signing")]
+                signature: None,
+                session_id: window.session_id,
+                #[cfg(feature = "command-line")]
+                process_command_line: window.process_command_line.clone(),
+            };
+            self.process_cache()
+                .entries
+                .insert(window.pid, entry.clone());
+            entry
+        });
+
+        window.process_name = cached.process_name;
+        window.process_file = cached.process_file;
+        window.process_device_path = cached.process_device_path;
+        window.process_info_error = cached.process_info_error;
+        window.aumid = cached.aumid;
+        window.is_elevated = cached.is_elevated;
+        window.integrity_level = cached.integrity_level;
+        window.process_arch = cached.process_arch;
+        window.session_id = cached.session_id;
+        #[cfg(feature = "command-line")]
+        {
+            window.process_command_line = cached.process_command_line;
+        }
+    }
+
+    /// Resolves the account owning a process, reusing a cached name.
+    unsafe fn get_user_name_cached(&self, pid: u32) -> Option<String> {
+        let cached = self
+            .process_cache()
+            .entries
+            .get(&pid)
+            .and_then(|entry| entry.user_name.clone());
+        if cached.is_some() {
+            return cached;
+        }
+
+        let user_name = Self::get_process_user_name(pid);
+        if let Some(entry) = self.process_cache().entries.get_mut(&pid) {
+            entry.user_name.clone_from(&user_name);
+        }
+        user_name
+    }
+
+    /// Reads the version resource of a process executable, reusing a cached
+    /// result.
+    unsafe fn get_exe_version_cached(&self, pid: u32, exe: &std::path::Path) -> ExeVersion {
+        let cached = self
+            .process_cache()
+            .entries
+            .get(&pid)
+            .and_then(|entry| entry.version.clone());
+        if let Some(version) = cached {
+            return version;
+        }
+
+        let version = Self::get_exe_version(exe);
+        if let Some(entry) = self.process_cache().entries.get_mut(&pid) {
+            entry.version = Some(version.clone());
+        }
+        version
+    }
+
+    /// Verifies the signature of a process executable, reusing a cached
+    /// result.
+    #[cfg(feature = "signing")]
+    unsafe fn get_signature_cached(
+        &self,
+        pid: u32,
+        exe: &std::path::Path,
+    ) -> Option<(SignatureStatus, Option<String>)> {
+        let cached = self
+            .process_cache()
+            .entries
+            .get(&pid)
+            .and_then(|entry| entry.signature.clone());
+        if cached.is_some() {
+            return cached;
+        }
+
+        let signature = crate::signing::verify_file(exe);
+        if let Some(entry) = self.process_cache().entries.get_mut(&pid) {
+            entry.signature.clone_from(&signature);
+        }
+        signature
+    }
+
+    /// Reads the product name, company, and file version from the version
+    /// resource of an executable, in its first listed language.
+    pub(crate) unsafe fn get_exe_version(exe: &std::path::Path) -> ExeVersion {
+        use std::os::windows::ffi::OsStrExt;
+        use windows::Win32::Storage::FileSystem::{
+            GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
+        };
+
+        let path: Vec<u16> = exe.as_os_str().encode_wide().chain(Some(0)).collect();
+        let size = GetFileVersionInfoSizeW(PCWSTR(path.as_ptr()), None);
+        if size == 0 {
+            return ExeVersion::default();
+        }
+        let mut data = vec![0u8; size as usize];
+        if GetFileVersionInfoW(PCWSTR(path.as_ptr()), 0, size, data.as_mut_ptr().cast()).is_err() {
+            return ExeVersion::default();
+        }
+
+        // `Var` lengths are in bytes, `String` lengths in characters
+        let query = |name: &str, len_in_bytes: bool| -> Option<&[u16]> {
+            let key: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+            let mut value = std::ptr::null_mut();
+            let mut len = 0u32;
+            let found = VerQueryValueW(
+                data.as_ptr().cast(),
+                PCWSTR(key.as_ptr()),
+                &mut value,
+                &mut len,
+            );
+            if !found.as_bool() || value.is_null() {
+                return None;
+            }
+            let count = if len_in_bytes { len / 2 } else { len } as usize;
+
+            // The value points into `data`; never read past its end
+            let offset = (value as usize).checked_sub(data.as_ptr() as usize)?;
+            let end = offset.checked_add(count.checked_mul(2)?)?;
+            (count > 0 && end <= data.len())
+                .then(|| std::slice::from_raw_parts(value as *const u16, count))
+        };
+
+        // Strings are keyed by language and code page, defaulting to US English Unicode
+        let translation = match query("\\VarFileInfo\\Translation", true) {
+            Some(&[language, code_page, ..]) => format!("{:04x}{:04x}", language, code_page),
+            _ => "040904b0".to_string(),
+        };
+        let string = |name: &str| {
+            // Values include the terminator
+            query(
+                &format!("\\StringFileInfo\\{}\\{}", translation, name),
+                false,
+            )
+            .map(|value| {
+                let end = value.iter().position(|&c| c == 0).unwrap_or(value.len());
+                String::from_utf16_lossy(&value[..end]).trim().to_string()
+            })
+            .filter(|value| !value.is_empty())
+        };
+
+        ExeVersion {
+            product_name: string("ProductName"),
+            company_name: string("CompanyName"),
+            file_version: string("FileVersion"),
+        }
+    }
+
+    fn process_cache(&self) -> std::sync::MutexGuard<'_, ProcessCache> {
+        self.process_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn process_cache_mut(&mut self) -> &mut ProcessCache {
+        self.process_cache
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Empties the process cache before an enumeration unless it persists
+    /// across enumerations.
+    fn prepare_process_cache(&mut self) {
+        if !self.persistent_process_cache {
+            self.process_cache_mut().entries.clear();
+        }
+    }
+
+    /// Returns hit/miss statistics of the per-PID process information cache.
     ///
-    /// The enumerator starts with no windows loaded. Call [`enumerate_all_windows`]
-    /// to populate it with the current system windows.
+    /// During an enumeration the name, path, and command line of each
+    /// process are queried once and reused for all of its windows. The
+    /// statistics accumulate until [`clear_cache`](Self::clear_cache) is
+    /// called.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use window_enumerator::WindowEnumerator;
     ///
-    /// let enumerator = WindowEnumerator::new();
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let stats = enumerator.process_cache_stats();
+    /// println!("{} hits, {} misses", stats.hits, stats.misses);
     /// ```
+    pub fn process_cache_stats(&self) -> ProcessCacheStats {
+        let cache = self.process_cache();
+        ProcessCacheStats {
+            hits: cache.hits,
+            misses: cache.misses,
+            entries: cache.entries.len(),
+        }
+    }
+
+    /// Empties the process information cache and resets its statistics.
+    pub fn clear_cache(&mut self) {
+        *self.process_cache_mut() = ProcessCache::default();
+    }
+
+    /// Drops the cached information of a single process.
+    pub(crate) fn evict_process(&mut self, pid: u32) {
+        self.process_cache_mut().entries.remove(&pid);
+    }
+
+    /// Keeps cached process information across enumerations and refreshes.
     ///
-    /// [`enumerate_all_windows`]: WindowEnumerator::enumerate_all_windows
-    pub fn new() -> Self {
-        Self {
-            windows: Vec::new(),
+    /// By default the cache is emptied at the start of every enumeration.
+    /// When persistent, entries are only dropped once their process no longer
+    /// owns any enumerated window, since its PID may then be reused.
+    pub fn set_persistent_process_cache(&mut self, persistent: bool) {
+        self.persistent_process_cache = persistent;
+    }
+
+    /// Fills in the process name, executable path, and command line of a window.
+    pub(crate) unsafe fn load_process_info(window: &mut WindowInfo) {
+        match Self::get_process_info(window.pid) {
+            Ok((process_name, process_file, process_device_path)) => {
+                window.process_name = process_name;
+                window.process_file = process_file;
+                window.process_device_path = process_device_path;
+                window.process_info_error = None;
+            }
+            Err(error) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    pid = window.pid,
+                    %error,
+                    access_denied = error.is_access_denied(),
+                    "failed to query process information"
+                );
+                window.process_name.clear();
+                window.process_file.clear();
+                window.process_device_path.clear();
+                window.process_info_error = Some(error);
+            }
+        }
+
+        window.aumid = Self::get_process_aumid(window.pid);
+        (window.is_elevated, window.integrity_level) = Self::get_process_token_info(window.pid);
+        window.process_arch = Self::get_process_arch(window.pid);
+        window.session_id = Self::get_process_session_id(window.pid);
+
+        #[cfg(feature = "command-line")]
+        {
+            window.process_command_line = Self::get_process_command_line(window.pid);
         }
     }
 
-    /// Enumerates all visible windows on the system.
+    /// Determines the architecture a process runs as.
     ///
-    /// This method populates the internal window list with all currently
-    /// visible, non-child windows. Each window is assigned a 1-based index.
+    /// `GetProcessInformation` also recognizes x64 processes emulated on
+    /// ARM64 but needs Windows 11. Older systems fall back to
+    /// `IsWow64Process2`, which reports native processes as the machine
+    /// architecture.
+    unsafe fn get_process_arch(pid: u32) -> Option<ProcessArch> {
+        let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+        let mut info = PROCESS_MACHINE_INFORMATION::default();
+        let machine = if GetProcessInformation(
+            process_handle,
+            ProcessMachineTypeInfo,
+            &mut info as *mut _ as *mut _,
+            std::mem::size_of::<PROCESS_MACHINE_INFORMATION>() as u32,
+        )
+        .is_ok()
+        {
+            Some(info.ProcessMachine)
+        } else {
+            let mut process_machine = IMAGE_FILE_MACHINE::default();
+            let mut native_machine = IMAGE_FILE_MACHINE::default();
+            IsWow64Process2(
+                process_handle,
+                &mut process_machine,
+                Some(&mut native_machine),
+            )
+            .ok()
+            .map(|()| {
+                // Native processes report no WOW64 machine
+                if process_machine == IMAGE_FILE_MACHINE_UNKNOWN {
+                    native_machine
+                } else {
+                    process_machine
+                }
+            })
+        };
+        CloseHandle(process_handle).ok();
+
+        match machine? {
+            IMAGE_FILE_MACHINE_I386 => Some(ProcessArch::X86),
+            IMAGE_FILE_MACHINE_AMD64 => Some(ProcessArch::X64),
+            IMAGE_FILE_MACHINE_ARMNT => Some(ProcessArch::Arm),
+            IMAGE_FILE_MACHINE_ARM64 => Some(ProcessArch::Arm64),
+            _ => None,
+        }
+    }
+
+    /// Returns the integrity level of the current process.
     ///
-    /// # Errors
+    /// Compare it with [`WindowInfo::integrity_level`] to find windows that
+    /// User Interface Privilege Isolation prevents this process from sending
+    /// input or messages to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
     ///
-    /// Returns [`WindowError::WindowsApiError`] if the Windows API call fails.
+    /// let level = WindowEnumerator::current_integrity_level().unwrap();
+    /// let criteria = FilterCriteria::builder().max_integrity_level(level).build();
+    /// let reachable = enumerator.filter_windows(&criteria);
+    /// ```
+    pub fn current_integrity_level() -> Option<IntegrityLevel> {
+        unsafe { Self::get_token_info(GetCurrentProcess()).1 }
+    }
+
+    /// Returns the Terminal Services session of the current process.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use window_enumerator::WindowEnumerator;
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
     ///
     /// let mut enumerator = WindowEnumerator::new();
     /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let session = WindowEnumerator::current_session_id().unwrap();
+    /// let criteria = FilterCriteria::builder().session_id(session).build();
+    /// let mine = enumerator.filter_windows(&criteria);
     /// ```
-    pub fn enumerate_all_windows(&mut self) -> Result<()> {
-        self.windows.clear();
+    pub fn current_session_id() -> Option<u32> {
+        unsafe { Self::get_process_session_id(GetCurrentProcessId()) }
+    }
 
-        unsafe {
-            EnumWindows(
-                Some(Self::enum_windows_proc),
-                LPARAM(self as *mut _ as isize),
-            )
-            .map_err(|e| Error::new(e.code(), "Failed to enumerate windows".into()))?;
+    /// Retrieves the session a process runs in.
+    unsafe fn get_process_session_id(pid: u32) -> Option<u32> {
+        let mut session_id = 0u32;
+        ProcessIdToSessionId(pid, &mut session_id).ok()?;
+        Some(session_id)
+    }
+
+    /// Retrieves the elevation state and integrity level of a process.
+    pub(crate) unsafe fn get_process_token_info(
+        pid: u32,
+    ) -> (Option<bool>, Option<IntegrityLevel>) {
+        match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+            Ok(process_handle) => {
+                let info = Self::get_token_info(process_handle);
+                CloseHandle(process_handle).ok();
+                info
+            }
+            Err(_) => (None, None),
         }
+    }
 
-        // Assign 1-based indices to each window
-        for (index, window) in self.windows.iter_mut().enumerate() {
-            window.index = index + 1;
+    /// Reads the elevation state and integrity level from a process token.
+    unsafe fn get_token_info(process_handle: HANDLE) -> (Option<bool>, Option<IntegrityLevel>) {
+        let mut token = HANDLE::default();
+        if OpenProcessToken(process_handle, TOKEN_QUERY, &mut token).is_err() {
+            return (None, None);
         }
 
-        Ok(())
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut len = 0u32;
+        let is_elevated = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut _ as *mut std::ffi::c_void),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut len,
+        )
+        .ok()
+        .map(|_| elevation.TokenIsElevated != 0);
+
+        // The label is variable-sized, so ask for its size first
+        let _ = GetTokenInformation(token, TokenIntegrityLevel, None, 0, &mut len);
+        let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+        let integrity_level = GetTokenInformation(
+            token,
+            TokenIntegrityLevel,
+            Some(buffer.as_mut_ptr() as *mut std::ffi::c_void),
+            len,
+            &mut len,
+        )
+        .ok()
+        .and_then(|_| {
+            let label = &*(buffer.as_ptr() as *const TOKEN_MANDATORY_LABEL);
+            let count = *GetSidSubAuthorityCount(label.Label.Sid);
+            // The level is the last sub-authority of the label's SID
+            let rid = *GetSidSubAuthority(label.Label.Sid, u32::from(count.checked_sub(1)?));
+            Some(IntegrityLevel::from_rid(rid))
+        });
+
+        CloseHandle(token).ok();
+        (is_elevated, integrity_level)
     }
 
-    /// Windows enumeration callback function.
-    unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
-        let enumerator = &mut *(lparam.0 as *mut WindowEnumerator);
-
-        // Skip invisible windows and child windows
-        if IsWindowVisible(hwnd).as_bool() && GetParent(hwnd).0 == 0 {
-            if let Ok(mut window_info) = enumerator.get_window_info(hwnd) {
-                // Temporary index, will be reassigned later
-                window_info.index = enumerator.windows.len() + 1;
-                enumerator.windows.push(window_info);
-            }
-        }
+    /// Resolves the account owning a process as `DOMAIN\\user`.
+    pub(crate) unsafe fn get_process_user_name(pid: u32) -> Option<String> {
+        let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut token = HANDLE::default();
+        let opened = OpenProcessToken(process_handle, TOKEN_QUERY, &mut token);
+        CloseHandle(process_handle).ok();
+        opened.ok()?;
 
-        BOOL::from(true) // Continue enumeration
+        // The SID is variable-sized, so ask for its size first
+        let mut len = 0u32;
+        let _ = GetTokenInformation(token, TokenUser, None, 0, &mut len);
+        let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+        let queried = GetTokenInformation(
+            token,
+            TokenUser,
+            Some(buffer.as_mut_ptr() as *mut std::ffi::c_void),
+            len,
+            &mut len,
+        );
+        CloseHandle(token).ok();
+        queried.ok()?;
+
+        let sid = (*(buffer.as_ptr() as *const TOKEN_USER)).User.Sid;
+        let mut name = [0u16; 256];
+        let mut domain = [0u16; 256];
+        let mut name_len = name.len() as u32;
+        let mut domain_len = domain.len() as u32;
+        let mut sid_type = SID_NAME_USE::default();
+        LookupAccountSidW(
+            PCWSTR::null(),
+            sid,
+            PWSTR(name.as_mut_ptr()),
+            &mut name_len,
+            PWSTR(domain.as_mut_ptr()),
+            &mut domain_len,
+            &mut sid_type,
+        )
+        .ok()?;
+
+        let name = String::from_utf16_lossy(&name[..name_len as usize]);
+        let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+        Some(if domain.is_empty() {
+            name
+        } else {
+            format!("{}\\{}", domain, name)
+        })
     }
 
-    /// Gathers information about a specific window.
-    fn get_window_info(&self, hwnd: HWND) -> Result<WindowInfo> {
-        unsafe {
-            // Get window title
-            let title = Self::get_window_text(hwnd);
+    /// Retrieves the AUMID of a packaged process.
+    unsafe fn get_process_aumid(pid: u32) -> Option<String> {
+        let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let aumid = Self::query_process_string(|len, buffer| {
+            GetApplicationUserModelId(process_handle, len, buffer)
+        });
+        CloseHandle(process_handle).ok();
+        aumid
+    }
 
-            // Get window class name
-            let class_name = Self::get_class_name(hwnd);
+    /// Retrieves the AUMID a window declares in its shell property store.
+    #[cfg(feature = "aumid")]
+    unsafe fn get_window_aumid(hwnd: HWND) -> Option<String> {
+        use windows::Win32::System::Com::CoTaskMemFree;
+        use windows::Win32::System::Com::StructuredStorage::{
+            PropVariantClear, PropVariantToStringAlloc,
+        };
+        use windows::Win32::UI::Shell::PropertiesSystem::{
+            IPropertyStore, SHGetPropertyStoreForWindow, PROPERTYKEY,
+        };
+
+        // PKEY_AppUserModel_ID
+        const PKEY_APP_USER_MODEL_ID: PROPERTYKEY = PROPERTYKEY {
+            fmtid: GUID::from_u128(0x9f4c2855_9f79_4b39_a8d0_e1d42de1d5f3),
+            pid: 5,
+        };
 
-            // Get process ID
-            let pid = Self::get_process_id(hwnd);
+        let store: IPropertyStore = SHGetPropertyStoreForWindow(hwnd).ok()?;
+        let mut value = store.GetValue(&PKEY_APP_USER_MODEL_ID).ok()?;
+        let text = PropVariantToStringAlloc(&value);
+        let _ = PropVariantClear(&mut value);
 
-            // Get process information
-            let (process_name, process_file) = if pid > 0 {
-                Self::get_process_info(pid).unwrap_or_default()
-            } else {
-                (String::new(), std::path::PathBuf::new())
-            };
+        let text = text.ok()?;
+        let aumid = text.to_string().ok();
+        CoTaskMemFree(Some(text.0 as *const _));
+        aumid.filter(|aumid| !aumid.is_empty())
+    }
 
-            // Get window position and size
-            let position = Self::get_window_position(hwnd);
+    /// Retrieves the text of a window.
+    pub(crate) unsafe fn get_window_text(hwnd: HWND) -> String {
+        Self::get_window_text_limited(hwnd, None)
+    }
 
-            Ok(WindowInfo {
-                hwnd: hwnd.0,
-                pid,
-                title,
-                class_name,
-                process_name,
-                process_file,
-                position,
-                index: 0, // Temporary value, will be set later
-            })
+    /// Retrieves the text of a window, truncated to `max_len` UTF-16 code
+    /// units if given.
+    ///
+    /// The buffer is sized from `GetWindowTextLengthW` and only grown if the
+    /// title grew in the meantime, so long titles are not cut off.
+    pub(crate) unsafe fn get_window_text_limited(hwnd: HWND, max_len: Option<usize>) -> String {
+        let limit = max_len.unwrap_or(usize::MAX);
+        let mut len = GetWindowTextLengthW(hwnd).max(0) as usize;
+        if len == 0 || limit == 0 {
+            return String::new();
+        }
+
+        loop {
+            let capacity = len.min(limit);
+            let mut buffer = vec![0u16; capacity + 1];
+            let copied = GetWindowTextW(hwnd, &mut buffer).max(0) as usize;
+            let text = || {
+                std::ffi::OsString::from_wide(&buffer[..copied])
+                    .to_string_lossy()
+                    .into_owned()
+            };
+
+            // Only a full buffer can hide a title that grew since its length was queried
+            if copied < capacity || capacity == limit {
+                return text();
+            }
+            let current = GetWindowTextLengthW(hwnd).max(0) as usize;
+            if current <= copied {
+                return text();
+            }
+            len = current;
         }
     }
 
-    /// Retrieves the text of a window.
-    unsafe fn get_window_text(hwnd: HWND) -> String {
-        let mut buffer = [0u16; 256];
-        let len = GetWindowTextW(hwnd, &mut buffer);
-        if len > 0 {
-            std::ffi::OsString::from_wide(&buffer[..len as usize])
-                .to_string_lossy()
-                .into_owned()
-        } else {
-            String::new()
+    /// Retrieves the text of a window with `WM_GETTEXT`, waiting at most
+    /// `timeout` for the owning thread to respond.
+    ///
+    /// Returns `None` if the window is hung or did not answer in time.
+    pub(crate) unsafe fn get_window_text_timeout(
+        hwnd: HWND,
+        max_len: Option<usize>,
+        timeout: Duration,
+    ) -> Option<String> {
+        let millis = timeout.as_millis().min(u32::MAX as u128) as u32;
+        let flags = SMTO_ABORTIFHUNG | SMTO_ERRORONEXIT;
+
+        let mut len: usize = 0;
+        let sent = SendMessageTimeoutW(
+            hwnd,
+            WM_GETTEXTLENGTH,
+            WPARAM(0),
+            LPARAM(0),
+            flags,
+            millis,
+            Some(&mut len),
+        );
+        if sent.0 == 0 {
+            return None;
+        }
+
+        let len = len.min(max_len.unwrap_or(usize::MAX));
+        if len == 0 {
+            return Some(String::new());
+        }
+
+        let mut buffer = vec![0u16; len + 1];
+        let mut copied: usize = 0;
+        let sent = SendMessageTimeoutW(
+            hwnd,
+            WM_GETTEXT,
+            WPARAM(buffer.len()),
+            LPARAM(buffer.as_mut_ptr() as isize),
+            flags,
+            millis,
+            Some(&mut copied),
+        );
+        if sent.0 == 0 {
+            return None;
         }
+
+        Some(
+            std::ffi::OsString::from_wide(&buffer[..copied.min(len)])
+                .to_string_lossy()
+                .into_owned(),
+        )
     }
 
     /// Retrieves the class name of a window.
-    unsafe fn get_class_name(hwnd: HWND) -> String {
+    pub(crate) unsafe fn get_class_name(hwnd: HWND) -> String {
         let mut buffer = [0u16; 256];
         let len = GetClassNameW(hwnd, &mut buffer);
         if len > 0 {
@@ -160,38 +1881,114 @@ impl WindowEnumerator {
         }
     }
 
-    /// Retrieves the process ID of a window.
-    unsafe fn get_process_id(hwnd: HWND) -> u32 {
+    /// Retrieves the owning thread ID and process ID of a window.
+    pub(crate) unsafe fn get_thread_process_id(hwnd: HWND) -> (u32, u32) {
         let mut pid: u32 = 0;
-        GetWindowThreadProcessId(hwnd, Some(&mut pid));
-        pid
+        let thread_id = GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        (thread_id, pid)
     }
 
     /// Retrieves the position and dimensions of a window.
-    unsafe fn get_window_position(hwnd: HWND) -> WindowPosition {
+    pub(crate) unsafe fn get_window_position(hwnd: HWND) -> WindowPosition {
         let mut rect = RECT::default();
         if GetWindowRect(hwnd, &mut rect).is_ok() {
-            WindowPosition {
-                x: rect.left,
-                y: rect.top,
-                width: rect.right - rect.left,
-                height: rect.bottom - rect.top,
-            }
+            Self::rect_to_position(&rect)
         } else {
             WindowPosition::default()
         }
     }
 
+    /// Retrieves the client area and DWM frame bounds of a window.
+    ///
+    /// `position` is the window rectangle, used when DWM does not report
+    /// extended frame bounds.
+    pub(crate) unsafe fn get_window_geometry(
+        hwnd: HWND,
+        position: &WindowPosition,
+    ) -> WindowGeometry {
+        let mut geometry = WindowGeometry {
+            frame_bounds: *position,
+            ..Default::default()
+        };
+
+        let mut client_rect = RECT::default();
+        if GetClientRect(hwnd, &mut client_rect).is_ok() {
+            let mut origin = POINT::default();
+            if ClientToScreen(hwnd, &mut origin).as_bool() {
+                geometry.client = WindowPosition {
+                    x: origin.x,
+                    y: origin.y,
+                    width: client_rect.right - client_rect.left,
+                    height: client_rect.bottom - client_rect.top,
+                };
+            }
+        }
+
+        let mut frame_rect = RECT::default();
+        if DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut frame_rect as *mut _ as *mut std::ffi::c_void,
+            std::mem::size_of::<RECT>() as u32,
+        )
+        .is_ok()
+        {
+            geometry.frame_bounds = Self::rect_to_position(&frame_rect);
+        }
+
+        geometry
+    }
+
+    /// Converts a Win32 [`RECT`] into a [`WindowPosition`].
+    fn rect_to_position(rect: &RECT) -> WindowPosition {
+        WindowPosition {
+            x: rect.left,
+            y: rect.top,
+            width: rect.right - rect.left,
+            height: rect.bottom - rect.top,
+        }
+    }
+
     /// Retrieves process information for a given process ID.
-    unsafe fn get_process_info(pid: u32) -> Result<(String, std::path::PathBuf)> {
-        let process_handle = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid)?;
+    ///
+    /// Returns the executable name, its Win32 path (`C:\...`), and its NT
+    /// device path (`\Device\HarddiskVolume3\...`).
+    pub(crate) unsafe fn get_process_info(
+        pid: u32,
+    ) -> Result<(String, std::path::PathBuf, std::path::PathBuf)> {
+        // Elevated and system processes refuse full query rights but usually
+        // still grant limited ones, which suffice for the image path
+        let process_handle = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid)
+            .or_else(|_error| {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    pid,
+                    error = %_error,
+                    "OpenProcess with full query rights failed, retrying with limited rights"
+                );
+                OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+            })
+            .map_err(|error| {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(pid, %error, "OpenProcess failed");
+                if error.code() == E_ACCESSDENIED {
+                    WindowError::ProcessAccessDenied { pid }
+                } else {
+                    WindowError::api("OpenProcess", error).for_process(pid)
+                }
+            })?;
 
         let mut file_buffer = [0u16; MAX_PATH as usize];
         let len = GetProcessImageFileNameW(process_handle, &mut file_buffer);
 
         if len > 0 {
-            let full_path = std::ffi::OsString::from_wide(&file_buffer[..len as usize]);
-            let path_buf = std::path::PathBuf::from(&full_path);
+            let device_path = std::path::PathBuf::from(std::ffi::OsString::from_wide(
+                &file_buffer[..len as usize],
+            ));
+
+            // Prefer the drive-letter path, falling back to the device path
+            let path_buf = Self::query_image_path(process_handle, PROCESS_NAME_WIN32)
+                .unwrap_or_else(|| device_path.clone());
 
             // Extract just the filename
             let process_name = path_buf
@@ -200,15 +1997,311 @@ impl WindowEnumerator {
                 .unwrap_or_default();
 
             CloseHandle(process_handle).ok();
-            Ok((process_name, path_buf))
+            Ok((process_name, path_buf, device_path))
         } else {
+            // Read the error before CloseHandle can overwrite it
+            let error = WindowError::last_api("GetProcessImageFileNameW").for_process(pid);
+            CloseHandle(process_handle).ok();
+            #[cfg(feature = "tracing")]
+            tracing::debug!(pid, %error, "GetProcessImageFileNameW failed");
+            Err(error)
+        }
+    }
+
+    /// Retrieves the executable path of a process in the given format.
+    unsafe fn query_image_path(
+        process_handle: HANDLE,
+        format: PROCESS_NAME_FORMAT,
+    ) -> Option<std::path::PathBuf> {
+        let mut buffer = vec![0u16; 1024];
+        let mut len = buffer.len() as u32;
+
+        QueryFullProcessImageNameW(process_handle, format, PWSTR(buffer.as_mut_ptr()), &mut len)
+            .ok()?;
+
+        Some(std::path::PathBuf::from(std::ffi::OsString::from_wide(
+            &buffer[..len as usize],
+        )))
+    }
+
+    /// Resolves the UWP app hosted inside an `ApplicationFrameWindow`.
+    unsafe fn get_uwp_app_info(frame: HWND, frame_pid: u32) -> Option<UwpAppInfo> {
+        let mut context = (frame_pid, None::<(HWND, u32)>);
+        let _ = EnumChildWindows(
+            frame,
+            Some(Self::find_core_window_proc),
+            LPARAM(&mut context as *mut _ as isize),
+        );
+        let (core_window, pid) = context.1?;
+
+        let (process_name, process_file, _) = Self::get_process_info(pid).unwrap_or_default();
+        let mut app = UwpAppInfo {
+            core_window: core_window.into(),
+            pid,
+            process_name,
+            process_file,
+            ..Default::default()
+        };
+
+        if let Ok(process_handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+            app.aumid = Self::query_process_string(|len, buffer| {
+                GetApplicationUserModelId(process_handle, len, buffer)
+            });
+            app.package_full_name = Self::query_process_string(|len, buffer| {
+                GetPackageFullName(process_handle, len, buffer)
+            });
             CloseHandle(process_handle).ok();
-            // 使用标准库的方法获取错误代码
-            let last_error = std::io::Error::last_os_error();
-            Err(WindowError::WindowsApiError(
-                last_error.raw_os_error().unwrap_or(0) as u32,
-            ))
         }
+
+        Some(app)
+    }
+
+    /// Lists the running processes (`CreateToolhelp32Snapshot`), or nothing
+    /// if the snapshot cannot be taken.
+    pub(crate) unsafe fn snapshot_processes() -> Vec<ProcessEntry> {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return Vec::new();
+        };
+        let mut processes = Vec::new();
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut found = Process32FirstW(snapshot, &mut entry).is_ok();
+        while found {
+            let len = entry
+                .szExeFile
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(entry.szExeFile.len());
+            processes.push(ProcessEntry {
+                pid: entry.th32ProcessID,
+                parent_pid: entry.th32ParentProcessID,
+                thread_count: entry.cntThreads,
+                exe_name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+            });
+            found = Process32NextW(snapshot, &mut entry).is_ok();
+        }
+        CloseHandle(snapshot).ok();
+        processes
+    }
+
+    /// Queries the memory, CPU time, handle count, and start time of a
+    /// process. The thread count comes from a process snapshot.
+    pub(crate) unsafe fn get_process_metrics(
+        pid: u32,
+        thread_count: u32,
+    ) -> Option<ProcessMetrics> {
+        let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+        let mut memory = PROCESS_MEMORY_COUNTERS {
+            cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+            ..Default::default()
+        };
+        let has_memory = GetProcessMemoryInfo(process_handle, &mut memory, memory.cb).is_ok();
+
+        let (mut created, mut exited, mut kernel, mut user) = Default::default();
+        let has_times = GetProcessTimes(
+            process_handle,
+            &mut created,
+            &mut exited,
+            &mut kernel,
+            &mut user,
+        )
+        .is_ok();
+
+        let mut handle_count = 0;
+        let _ = GetProcessHandleCount(process_handle, &mut handle_count);
+        CloseHandle(process_handle).ok();
+
+        if !has_memory && !has_times {
+            return None;
+        }
+
+        let ticks =
+            |time: FILETIME| (u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime);
+        // FILETIMEs count 100 ns intervals, timestamps since 1601-01-01
+        const UNIX_EPOCH_TICKS: u64 = 116_444_736_000_000_000;
+        Some(ProcessMetrics {
+            working_set: memory.WorkingSetSize as u64,
+            private_bytes: memory.PagefileUsage as u64,
+            cpu_time: Duration::from_nanos((ticks(kernel) + ticks(user)) * 100),
+            handle_count,
+            thread_count,
+            start_time: (has_times && ticks(created) >= UNIX_EPOCH_TICKS).then(|| {
+                std::time::UNIX_EPOCH
+                    + Duration::from_nanos((ticks(created) - UNIX_EPOCH_TICKS) * 100)
+            }),
+        })
+    }
+
+    /// Resolves the parent of a process and the executable names of its
+    /// ancestors, oldest first.
+    ///
+    /// The walk stops at an ancestor missing from the snapshot, and at one
+    /// that started after its child: its PID then belongs to an unrelated
+    /// process that reused the ID of the exited ancestor.
+    pub(crate) unsafe fn get_parent_chain(
+        processes: &[ProcessEntry],
+        pid: u32,
+    ) -> (Option<u32>, Option<Vec<String>>) {
+        let Some(process) = processes.iter().find(|process| process.pid == pid) else {
+            return (None, None);
+        };
+
+        let mut chain = Vec::new();
+        let mut visited = HashSet::from([pid]);
+        let mut child_created = Self::get_process_creation_time(pid);
+        let mut current = process.parent_pid;
+        while current != 0 && visited.insert(current) {
+            let Some(parent) = processes.iter().find(|process| process.pid == current) else {
+                break;
+            };
+            let created = Self::get_process_creation_time(current);
+            if matches!((created, child_created), (Some(parent), Some(child)) if parent > child) {
+                break;
+            }
+            chain.push(parent.exe_name.clone());
+            child_created = created;
+            current = parent.parent_pid;
+        }
+        chain.reverse();
+
+        let parent_pid = (process.parent_pid != 0).then_some(process.parent_pid);
+        (parent_pid, Some(chain))
+    }
+
+    /// Reads when a process was started, in `FILETIME` ticks.
+    unsafe fn get_process_creation_time(pid: u32) -> Option<u64> {
+        let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let (mut created, mut exited, mut kernel, mut user) = Default::default();
+        let result = GetProcessTimes(
+            process_handle,
+            &mut created,
+            &mut exited,
+            &mut kernel,
+            &mut user,
+        );
+        CloseHandle(process_handle).ok();
+        result.ok()?;
+        Some((u64::from(created.dwHighDateTime) << 32) | u64::from(created.dwLowDateTime))
+    }
+
+    /// Resolves the client process attached to a console window.
+    ///
+    /// Console hosts are started on behalf of their client, so a classic
+    /// console window resolves to the parent of `conhost.exe`. Windows
+    /// Terminal starts its shells itself, so a terminal window resolves to
+    /// its newest child that is not a pseudo console host.
+    unsafe fn get_console_client_pid(class_name: &str, pid: u32) -> Option<u32> {
+        const CONSOLE_HOSTS: [&str; 2] = ["conhost.exe", "openconsole.exe"];
+
+        let processes: Vec<(u32, u32, bool)> = Self::snapshot_processes()
+            .into_iter()
+            .map(|process| {
+                let name = process.exe_name.to_lowercase();
+                let is_host = CONSOLE_HOSTS.contains(&name.as_str());
+                (process.pid, process.parent_pid, is_host)
+            })
+            .collect();
+
+        if KnownWindowClass::WindowsTerminal.matches(class_name) {
+            return processes
+                .iter()
+                .rev()
+                .find(|&&(_, parent, is_host)| parent == pid && !is_host)
+                .map(|&(child, _, _)| child);
+        }
+
+        // Without a host process, the window already reports its client
+        match processes.iter().find(|&&(process, _, _)| process == pid) {
+            Some(&(_, parent, true)) => processes
+                .iter()
+                .any(|&(process, _, _)| process == parent)
+                .then_some(parent),
+            _ => Some(pid),
+        }
+    }
+
+    /// Child enumeration callback that finds the `Windows.UI.Core.CoreWindow`
+    /// belonging to a process other than the frame host.
+    unsafe extern "system" fn find_core_window_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let context = &mut *(lparam.0 as *mut (u32, Option<(HWND, u32)>));
+
+        if KnownWindowClass::CoreWindow.matches(&Self::get_class_name(hwnd)) {
+            let (_, pid) = Self::get_thread_process_id(hwnd);
+            if pid != context.0 {
+                context.1 = Some((hwnd, pid));
+                return BOOL::from(false); // Stop enumeration
+            }
+        }
+
+        BOOL::from(true) // Continue enumeration
+    }
+
+    /// Calls a "query length, then fill buffer" string API such as
+    /// `GetApplicationUserModelId`.
+    unsafe fn query_process_string(
+        query: impl Fn(*mut u32, PWSTR) -> windows::core::Result<()>,
+    ) -> Option<String> {
+        let mut len: u32 = 0;
+        let _ = query(&mut len, PWSTR::null());
+        if len == 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; len as usize];
+        query(&mut len, PWSTR(buffer.as_mut_ptr())).ok()?;
+
+        // The reported length includes the terminating null
+        let len = (len as usize).saturating_sub(1).min(buffer.len());
+        Some(
+            std::ffi::OsString::from_wide(&buffer[..len])
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    /// Retrieves the command line of a process via `NtQueryInformationProcess`.
+    #[cfg(feature = "command-line")]
+    unsafe fn get_process_command_line(pid: u32) -> Option<String> {
+        let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+        // First call reports the required buffer size
+        let mut len: u32 = 0;
+        let _ = NtQueryInformationProcess(
+            process_handle,
+            ProcessCommandLineInformation,
+            std::ptr::null_mut(),
+            0,
+            &mut len,
+        );
+
+        // u64 elements keep the UNICODE_STRING header properly aligned
+        let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+        let status = if len > 0 {
+            NtQueryInformationProcess(
+                process_handle,
+                ProcessCommandLineInformation,
+                buffer.as_mut_ptr() as *mut std::ffi::c_void,
+                len,
+                &mut len,
+            )
+        } else {
+            STATUS_UNSUCCESSFUL
+        };
+        CloseHandle(process_handle).ok();
+
+        if status.is_err() {
+            return None;
+        }
+
+        let unicode = &*(buffer.as_ptr() as *const UNICODE_STRING);
+        if unicode.Buffer.is_null() {
+            return Some(String::new());
+        }
+        let chars = std::slice::from_raw_parts(unicode.Buffer.0, unicode.Length as usize / 2);
+        Some(String::from_utf16_lossy(chars))
     }
 
     /// Finds windows by title containing the specified string (case-insensitive).
@@ -233,7 +2326,7 @@ impl WindowEnumerator {
     ///
     /// let chrome_windows = enumerator.find_by_title("Chrome");
     /// for window in chrome_windows {
-    ///     window.print_compact();
+    ///     println!("{}", window);
     /// }
     /// ```
     pub fn find_by_title(&self, title_substring: &str) -> Vec<WindowInfo> {
@@ -244,34 +2337,97 @@ impl WindowEnumerator {
         self.filter_windows(&criteria)
     }
 
-    /// Filters windows based on the specified criteria.
+    /// Filters windows based on the specified criteria.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - The filter criteria to apply
+    ///
+    /// # Returns
+    ///
+    /// A vector containing only the windows that match all criteria.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{WindowEnumerator, FilterCriteria};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let criteria = FilterCriteria {
+    ///     title_contains: Some("Chrome".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let chrome_windows = enumerator.filter_windows(&criteria);
+    /// ```
+    pub fn filter_windows(&self, criteria: &FilterCriteria) -> Vec<WindowInfo> {
+        self.filter_windows_iter(criteria).cloned().collect()
+    }
+
+    /// Filters windows based on the specified criteria without cloning them.
+    ///
+    /// This is the borrowing counterpart of [`filter_windows`]; prefer it in
+    /// hot loops where the matched windows are only inspected.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - The filter criteria to apply
+    ///
+    /// # Returns
+    ///
+    /// An iterator over references to the windows that match all criteria.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{WindowEnumerator, FilterCriteria};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let criteria = FilterCriteria::builder().title_contains("Chrome").build();
+    /// let count = enumerator.filter_windows_iter(&criteria).count();
+    /// ```
+    ///
+    /// [`filter_windows`]: WindowEnumerator::filter_windows
+    pub fn filter_windows_iter<'a>(
+        &'a self,
+        criteria: &'a FilterCriteria,
+    ) -> impl Iterator<Item = &'a WindowInfo> + 'a {
+        self.windows
+            .iter()
+            .filter(move |window| utils::matches_criteria(window, criteria))
+    }
+
+    /// Filters windows based on a composable filter expression.
     ///
     /// # Arguments
     ///
-    /// * `criteria` - The filter criteria to apply
+    /// * `filter` - The filter expression to evaluate
     ///
     /// # Returns
     ///
-    /// A vector containing only the windows that match all criteria.
+    /// A vector containing only the windows that satisfy the expression.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use window_enumerator::{WindowEnumerator, FilterCriteria};
+    /// use window_enumerator::{Filter, FilterCriteria, WindowEnumerator};
     ///
     /// let mut enumerator = WindowEnumerator::new();
     /// enumerator.enumerate_all_windows().unwrap();
     ///
-    /// let criteria = FilterCriteria {
-    ///     title_contains: Some("Chrome".to_string()),
-    ///     ..Default::default()
-    /// };
-    /// let chrome_windows = enumerator.filter_windows(&criteria);
+    /// let browsers = Filter::Or(vec![
+    ///     FilterCriteria::builder().title_contains("Chrome").build().into(),
+    ///     FilterCriteria::builder().title_contains("Firefox").build().into(),
+    /// ]);
+    /// let windows = enumerator.filter_windows_expr(&browsers);
     /// ```
-    pub fn filter_windows(&self, criteria: &FilterCriteria) -> Vec<WindowInfo> {
+    pub fn filter_windows_expr(&self, filter: &Filter) -> Vec<WindowInfo> {
         self.windows
             .iter()
-            .filter(|window| utils::matches_criteria(window, criteria))
+            .filter(|window| utils::matches_filter(window, filter))
             .cloned()
             .collect()
     }
@@ -283,18 +2439,77 @@ impl WindowEnumerator {
     /// # Arguments
     ///
     /// * `criteria` - The filter criteria to apply
-    /// * `sort_criteria` - The sort criteria to apply
+    /// * `sort_keys` - The keys to sort by, in order of precedence; a
+    ///   deprecated [`SortCriteria`](crate::SortCriteria) is accepted as well
     ///
     /// # Returns
     ///
     /// A vector containing the filtered and sorted windows.
     #[cfg(feature = "sorting")]
-    pub fn filter_and_sort_windows(
+    pub fn filter_and_sort_windows<'k>(
         &self,
         criteria: &FilterCriteria,
-        sort_criteria: &SortCriteria,
+        sort_keys: impl Into<SortKeys<'k>>,
     ) -> Vec<WindowInfo> {
-        WindowSorter::filter_and_sort_windows(&self.windows, criteria, sort_criteria)
+        WindowSorter::filter_and_sort_windows(&self.windows, criteria, sort_keys)
+    }
+
+    /// Filters and sorts windows without cloning them.
+    ///
+    /// Requires the `sorting` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - The filter criteria to apply
+    /// * `sort_keys` - The keys to sort by, in order of precedence
+    ///
+    /// # Returns
+    ///
+    /// A vector of references to the filtered and sorted windows.
+    #[cfg(feature = "sorting")]
+    pub fn filter_and_sort_windows_refs(
+        &self,
+        criteria: &FilterCriteria,
+        sort_keys: &[SortKey],
+    ) -> Vec<&WindowInfo> {
+        WindowSorter::filter_and_sort_window_refs(&self.windows, criteria, sort_keys)
+    }
+
+    /// Filters windows and sorts them with a caller-supplied comparator.
+    ///
+    /// Requires the `sorting` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - The filter criteria to apply
+    /// * `compare` - The comparator deciding the order of matching windows
+    ///
+    /// # Returns
+    ///
+    /// A vector containing the filtered and sorted windows.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// // Order by distance from the top-left corner of the screen.
+    /// let distance = |x: i32, y: i32| (x as i64).pow(2) + (y as i64).pow(2);
+    /// let windows = enumerator.filter_and_sort_by(&FilterCriteria::default(), |a, b| {
+    ///     distance(a.position.x, a.position.y).cmp(&distance(b.position.x, b.position.y))
+    /// });
+    /// ```
+    #[cfg(feature = "sorting")]
+    pub fn filter_and_sort_by<F>(&self, criteria: &FilterCriteria, compare: F) -> Vec<WindowInfo>
+    where
+        F: FnMut(&WindowInfo, &WindowInfo) -> std::cmp::Ordering,
+    {
+        let mut filtered: Vec<WindowInfo> = self.filter_windows_iter(criteria).cloned().collect();
+        WindowSorter::sort_windows_by(&mut filtered, compare);
+        filtered
     }
 
     /// Filters windows with selection criteria.
@@ -304,7 +2519,8 @@ impl WindowEnumerator {
     /// # Arguments
     ///
     /// * `criteria` - The filter criteria to apply
-    /// * `selection` - The selection criteria to apply
+    /// * `selection` - The selection criteria to apply; use
+    ///   [`Selection::ByPosition`] to select by position in the results
     ///
     /// # Returns
     ///
@@ -315,15 +2531,47 @@ impl WindowEnumerator {
         criteria: &FilterCriteria,
         selection: &Selection,
     ) -> Vec<WindowInfo> {
-        let filtered = self.filter_windows(criteria);
+        self.filter_windows_with_selection_iter(criteria, selection)
+            .cloned()
+            .collect()
+    }
 
-        match selection {
-            Selection::All => filtered,
-            Selection::Indices(indices) => filtered
-                .into_iter()
-                .filter(|window| indices.contains(&window.index))
-                .collect(),
-        }
+    /// Filters windows with selection criteria without cloning them.
+    ///
+    /// Requires the `selection` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - The filter criteria to apply
+    /// * `selection` - The selection criteria to apply; use
+    ///   [`Selection::ByPosition`] to select by position in the results
+    ///
+    /// # Returns
+    ///
+    /// An iterator over references to the selected windows.
+    #[cfg(feature = "selection")]
+    pub fn filter_windows_with_selection_iter<'a>(
+        &'a self,
+        criteria: &'a FilterCriteria,
+        selection: &'a Selection,
+    ) -> impl Iterator<Item = &'a WindowInfo> + 'a {
+        // Position-based selections need the length of the filtered list
+        let len = if selection.is_by_position() {
+            self.filter_windows_iter(criteria).count()
+        } else {
+            utils::max_index(&self.windows)
+        };
+
+        self.filter_windows_iter(criteria)
+            .enumerate()
+            .filter(move |(position, window)| {
+                if selection.is_by_position() {
+                    selection.matches_in(position + 1, len)
+                } else {
+                    selection.matches_in(window.index, len)
+                }
+            })
+            .map(|(_, window)| window)
     }
 
     /// Filters, sorts, and selects windows based on the specified criteria.
@@ -333,29 +2581,260 @@ impl WindowEnumerator {
     /// # Arguments
     ///
     /// * `criteria` - The filter criteria to apply
-    /// * `sort_criteria` - The sort criteria to apply
-    /// * `selection` - The selection criteria to apply
+    /// * `sort_keys` - The keys to sort by, in order of precedence; a
+    ///   deprecated [`SortCriteria`](crate::SortCriteria) is accepted as well
+    /// * `selection` - The selection criteria to apply; use
+    ///   [`Selection::ByPosition`] to select by position in the results
     ///
     /// # Returns
     ///
     /// A vector containing the filtered, sorted, and selected windows.
     #[cfg(all(feature = "sorting", feature = "selection"))]
-    pub fn filter_sort_windows_with_selection(
+    pub fn filter_sort_windows_with_selection<'k>(
         &self,
         criteria: &FilterCriteria,
-        sort_criteria: &SortCriteria,
+        sort_keys: impl Into<SortKeys<'k>>,
         selection: &Selection,
     ) -> Vec<WindowInfo> {
-        let filtered =
-            WindowSorter::filter_and_sort_windows(&self.windows, criteria, sort_criteria);
+        self.filter_sort_windows_with_selection_refs(criteria, &sort_keys.into(), selection)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Filters, sorts, and selects windows without cloning them.
+    ///
+    /// Requires both `sorting` and `selection` features.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - The filter criteria to apply
+    /// * `sort_keys` - The keys to sort by, in order of precedence
+    /// * `selection` - The selection criteria to apply; use
+    ///   [`Selection::ByPosition`] to select by position in the results
+    ///
+    /// # Returns
+    ///
+    /// A vector of references to the filtered, sorted, and selected windows.
+    #[cfg(all(feature = "sorting", feature = "selection"))]
+    pub fn filter_sort_windows_with_selection_refs(
+        &self,
+        criteria: &FilterCriteria,
+        sort_keys: &[SortKey],
+        selection: &Selection,
+    ) -> Vec<&WindowInfo> {
+        let filtered = self.filter_and_sort_windows_refs(criteria, sort_keys);
+        selection.select(filtered, utils::max_index(&self.windows))
+    }
+
+    /// Filters, sorts with a caller-supplied comparator, and selects windows.
+    ///
+    /// Requires both `sorting` and `selection` features.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` - The filter criteria to apply
+    /// * `compare` - The comparator deciding the order of matching windows
+    /// * `selection` - The selection criteria to apply; use
+    ///   [`Selection::ByPosition`] to select by position in the results
+    ///
+    /// # Returns
+    ///
+    /// A vector containing the filtered, sorted, and selected windows.
+    #[cfg(all(feature = "sorting", feature = "selection"))]
+    pub fn filter_sort_by_with_selection<F>(
+        &self,
+        criteria: &FilterCriteria,
+        compare: F,
+        selection: &Selection,
+    ) -> Vec<WindowInfo>
+    where
+        F: FnMut(&WindowInfo, &WindowInfo) -> std::cmp::Ordering,
+    {
+        let mut filtered: Vec<&WindowInfo> = self.filter_windows_iter(criteria).collect();
+        WindowSorter::sort_window_refs_by(&mut filtered, compare);
+        selection
+            .select(filtered, utils::max_index(&self.windows))
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Filters, sorts, and selects windows using a single query string.
+    ///
+    /// Requires both `sorting` and `selection` features. See
+    /// [`parse_query`](crate::utils::parse_query) for the query syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let windows = enumerator
+    ///     .query("process~chrome exclude:tool sort:title select:1-3")
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the query string is invalid.
+    #[cfg(all(feature = "sorting", feature = "selection"))]
+    pub fn query(&self, query: &str) -> Result<Vec<WindowInfo>> {
+        let (criteria, sort_keys, selection) = utils::parse_query(query)?;
+        Ok(self.filter_sort_windows_with_selection(&criteria, &sort_keys, &selection))
+    }
+
+    /// Starts a lazily evaluated [`WindowQuery`] over the enumerated windows.
+    ///
+    /// Chaining filters, sort keys, a selection, and limits replaces the
+    /// combinations of the `filter_*` methods.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{parse_selection, FilterCriteria, SortKey, SortOrder, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let criteria = FilterCriteria::builder().process_name_contains("chrome").build();
+    /// let windows = enumerator
+    ///     .windows()
+    ///     .filter(&criteria)
+    ///     .sort(&[SortKey::Title(SortOrder::Asc)])
+    ///     .select(&parse_selection("all,!1").unwrap())
+    ///     .take(3)
+    ///     .collect();
+    /// ```
+    pub fn windows(&self) -> WindowQuery<'_> {
+        WindowQuery::new(&self.windows)
+    }
+
+    /// Returns the main window of a process among the enumerated windows.
+    ///
+    /// Uses the same heuristic as Task Manager and .NET's
+    /// `Process.MainWindowHandle`: the first window in Z-order that is
+    /// visible, not cloaked, has no owner, and is not a tool window. The
+    /// `ApplicationFrameWindow` hosting a UWP app counts as that app's window.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let pid = 1234;
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_windows_of_process(pid).unwrap();
+    ///
+    /// if let Some(window) = enumerator.main_window_of(pid) {
+    ///     println!("main window: {}", window.title);
+    /// }
+    /// ```
+    pub fn main_window_of(&self, pid: u32) -> Option<WindowInfo> {
+        self.windows
+            .iter()
+            .filter(|window| {
+                window.pid == pid || window.uwp_app.as_ref().is_some_and(|app| app.pid == pid)
+            })
+            .find(|window| {
+                window.is_visible
+                    && !window.is_cloaked
+                    && window.owner_hwnd.is_none()
+                    && !window.is_tool_window()
+            })
+            .cloned()
+    }
+
+    /// Groups the enumerated windows by the thread that created them.
+    ///
+    /// # Returns
+    ///
+    /// A map from thread ID to that thread's windows, in enumeration order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// for (thread_id, windows) in enumerator.group_by_thread() {
+    ///     println!("thread {}: {} windows", thread_id, windows.len());
+    /// }
+    /// ```
+    pub fn group_by_thread(&self) -> BTreeMap<u32, Vec<WindowInfo>> {
+        let mut groups: BTreeMap<u32, Vec<WindowInfo>> = BTreeMap::new();
+        for window in &self.windows {
+            groups
+                .entry(window.thread_id)
+                .or_default()
+                .push(window.clone());
+        }
+        groups
+    }
+
+    /// Groups the enumerated windows by their owning process.
+    ///
+    /// # Returns
+    ///
+    /// One entry per process, ordered by PID, with windows in enumeration order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// for process in enumerator.group_by_process() {
+    ///     println!("{} ({}): {} windows", process.process_name, process.pid, process.windows.len());
+    /// }
+    /// ```
+    pub fn group_by_process(&self) -> Vec<ProcessWindows> {
+        let mut groups: BTreeMap<u32, ProcessWindows> = BTreeMap::new();
+        for window in &self.windows {
+            groups
+                .entry(window.pid)
+                .or_insert_with(|| ProcessWindows {
+                    pid: window.pid,
+                    process_name: window.process_name.clone(),
+                    process_file: window.process_file.clone(),
+                    windows: Vec::new(),
+                })
+                .windows
+                .push(window.clone());
+        }
+        groups.into_values().collect()
+    }
 
-        match selection {
-            Selection::All => filtered,
-            Selection::Indices(indices) => filtered
-                .into_iter()
-                .filter(|window| indices.contains(&window.index))
-                .collect(),
+    /// Groups the enumerated windows by process, sorting the windows of each process.
+    ///
+    /// Requires the `sorting` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `sort_keys` - The keys to sort by within each process, in order of
+    ///   precedence; a deprecated [`SortCriteria`](crate::SortCriteria) is
+    ///   accepted as well
+    ///
+    /// # Returns
+    ///
+    /// One entry per process, ordered by PID, with windows sorted by `sort_keys`.
+    #[cfg(feature = "sorting")]
+    pub fn group_by_process_sorted<'k>(
+        &self,
+        sort_keys: impl Into<SortKeys<'k>>,
+    ) -> Vec<ProcessWindows> {
+        let sort_keys = sort_keys.into();
+        let mut groups = self.group_by_process();
+        for group in &mut groups {
+            WindowSorter::sort_windows(&mut group.windows, &*sort_keys);
         }
+        groups
     }
 
     /// Returns a reference to all enumerated windows.
@@ -367,6 +2846,38 @@ impl WindowEnumerator {
         &self.windows
     }
 
+    /// Takes an immutable snapshot of the enumerated windows.
+    ///
+    /// The snapshot can be shared across threads and is unaffected by later
+    /// enumerations.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::thread;
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// let snapshot = enumerator.snapshot();
+    /// let workers: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let snapshot = snapshot.clone();
+    ///         thread::spawn(move || snapshot.len())
+    ///     })
+    ///     .collect();
+    /// ```
+    pub fn snapshot(&self) -> WindowList {
+        WindowList::new(self.windows.clone())
+    }
+
+    /// Consumes the enumerator and turns its windows into a snapshot
+    /// without cloning them.
+    pub fn into_list(self) -> WindowList {
+        WindowList::new(self.windows)
+    }
+
     /// Retrieves a window by its 1-based index.
     ///
     /// # Arguments
@@ -377,7 +2888,51 @@ impl WindowEnumerator {
     ///
     /// `Some(&WindowInfo)` if a window with the given index exists, `None` otherwise.
     pub fn get_window_by_index(&self, index: usize) -> Option<&WindowInfo> {
-        self.windows.iter().find(|w| w.index == index)
+        self.index_lookup
+            .get(&index)
+            .map(|&position| &self.windows[position])
+    }
+
+    /// Returns the enumerated windows directly owned by the given window,
+    /// such as its dialog boxes and popups.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    ///
+    /// if let Ok(main) = enumerator.foreground_window() {
+    ///     for dialog in enumerator.windows_owned_by(main.hwnd) {
+    ///         println!("{} owns {}", main.title, dialog.title);
+    ///     }
+    /// }
+    /// ```
+    pub fn windows_owned_by(&self, hwnd: WindowHandle) -> Vec<&WindowInfo> {
+        self.windows
+            .iter()
+            .filter(|w| w.owner_hwnd == Some(hwnd))
+            .collect()
+    }
+
+    /// Retrieves a window by its handle.
+    ///
+    /// # Arguments
+    ///
+    /// * `hwnd` - The handle of the window to retrieve
+    ///
+    /// # Returns
+    ///
+    /// `Some(&WindowInfo)` if a window with the given handle was enumerated, `None` otherwise.
+    ///
+    /// The lookup takes constant time, so it suits mapping the handles of
+    /// [`WindowEvent`](crate::WindowEvent)s back to enumerated windows.
+    pub fn get_window_by_hwnd(&self, hwnd: WindowHandle) -> Option<&WindowInfo> {
+        self.hwnd_lookup
+            .get(&hwnd)
+            .map(|&position| &self.windows[position])
     }
 
     /// Prints all enumerated windows with their indices in a formatted table.
@@ -388,26 +2943,37 @@ impl WindowEnumerator {
     /// # Examples
     ///
     /// ```no_run
+    /// # #![allow(deprecated)]
     /// use window_enumerator::WindowEnumerator;
     ///
     /// let mut enumerator = WindowEnumerator::new();
     /// enumerator.enumerate_all_windows().unwrap();
     /// enumerator.print_windows_with_indices();
     /// ```
+    ///
+    /// Requires the `console` feature. Format the enumerator with `{}` to
+    /// get the table as a string.
+    #[cfg(feature = "console")]
+    #[deprecated(note = "format the enumerator with `{}` or use `TablePrinter` instead")]
     pub fn print_windows_with_indices(&self) {
-        println!("Index | Handle      | PID    | Position    | Title");
-        println!("------|-------------|--------|-------------|-------------------");
-        for window in &self.windows {
-            println!(
-                "{:5} | 0x{:08x} | {:6} | {:4},{:4}     | {}",
-                window.index,
-                window.hwnd,
-                window.pid,
-                window.position.x,
-                window.position.y,
-                window.title
-            );
-        }
+        println!("{}", self.to_table(TableStyle::Plain));
+    }
+
+    /// Renders all enumerated windows as a table string.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{TableStyle, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// let table = enumerator.to_table(TableStyle::Markdown);
+    /// ```
+    pub fn to_table(&self, style: TableStyle) -> String {
+        crate::format::TablePrinter::new()
+            .style(style)
+            .render(&self.windows)
     }
 }
 
@@ -416,3 +2982,21 @@ impl Default for WindowEnumerator {
         Self::new()
     }
 }
+
+/// The enumerated windows as a plain table, see
+/// [`to_table`](WindowEnumerator::to_table).
+impl std::fmt::Display for WindowEnumerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_table(TableStyle::Plain))
+    }
+}
+
+impl std::fmt::Debug for WindowEnumerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WindowEnumerator")
+            .field("windows", &self.windows)
+            .field("options", &self.options)
+            .field("process_cache", &self.process_cache_stats())
+            .finish_non_exhaustive()
+    }
+}
@@ -1,7 +1,9 @@
 use std::fmt;
 
+use crate::types::WindowHandle;
+
 /// Errors that can occur during window enumeration and inspection operations.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WindowError {
     /// The selection string format is invalid.
     ///
@@ -13,6 +15,60 @@ pub enum WindowError {
     /// Valid formats are: "x1", "y-1", "x1|y1"
     InvalidPositionSortFormat,
 
+    /// An item of a selection string is invalid.
+    ///
+    /// Raised by [`parse_selection`](crate::utils::parse_selection) for the
+    /// first invalid comma-separated item.
+    InvalidSelectionToken {
+        /// The offending item, e.g. `"5-x"`.
+        token: String,
+        /// The byte offset of the item in the selection string.
+        position: usize,
+        /// Why the item is invalid, e.g. [`WindowError::InvalidIndex`].
+        reason: Box<WindowError>,
+    },
+
+    /// A part of a position sort string is invalid.
+    ///
+    /// Raised by [`parse_position_sort`](crate::utils::parse_position_sort)
+    /// for the first invalid `|`-separated part.
+    InvalidPositionSortToken {
+        /// The offending part, e.g. `"x2"`.
+        token: String,
+        /// The byte offset of the part in the sort string.
+        position: usize,
+        /// Why the part is invalid, e.g. [`WindowError::InvalidSortOrder`].
+        reason: Box<WindowError>,
+    },
+
+    /// A rule of a [`WindowRuleSet`](crate::WindowRuleSet) is invalid.
+    InvalidRule {
+        /// The name of the rule.
+        name: String,
+        /// Why the rule is invalid, e.g. [`WindowError::InvalidQueryFormat`].
+        reason: Box<WindowError>,
+    },
+
+    /// The sort string format is invalid.
+    ///
+    /// Valid format is a comma-separated list of "key:order" pairs, e.g. "pid:-1,title:1"
+    InvalidSortFormat,
+
+    /// A query string term is invalid.
+    ///
+    /// Contains the offending term.
+    InvalidQueryFormat(String),
+
+    /// An output template contains an unknown or unterminated placeholder.
+    ///
+    /// Contains the offending placeholder.
+    InvalidFormatTemplate(String),
+
+    /// A table column name is unknown.
+    ///
+    /// Contains the offending name.
+    InvalidTableColumn(String),
+
     /// The range format is invalid.
     ///
     /// Valid range format is: "start-end" where start <= end
@@ -26,11 +82,70 @@ pub enum WindowError {
     /// Valid orders are: 1 (ascending) or -1 (descending)
     InvalidSortOrder,
 
+    /// No window matched the request (e.g. no window at a screen point).
+    WindowNotFound,
+
     /// A Windows API call failed.
     ///
-    /// Contains the Windows error code.
+    /// Contains the Windows error code. Errors raised by the library itself
+    /// use [`WindowError::ApiCallFailed`], which also says what failed.
     WindowsApiError(u32),
 
+    /// A specific Windows API function failed.
+    ///
+    /// Calls acting on a window typically fail this way when the window was
+    /// closed in the meantime, or when it belongs to an elevated process,
+    /// which the system shields from less privileged callers.
+    ApiCallFailed {
+        /// The name of the failed function, e.g. `"OpenProcess"`.
+        function: &'static str,
+        /// The Windows error code (a Win32 error or an `HRESULT`).
+        code: u32,
+        /// The system message for the error code.
+        message: String,
+        /// The window the call concerned, if any.
+        hwnd: Option<WindowHandle>,
+        /// The process the call concerned, if any.
+        pid: Option<u32>,
+    },
+
+    /// Access to a process was denied, typically because it runs elevated
+    /// or is protected.
+    ProcessAccessDenied {
+        /// The ID of the process.
+        pid: u32,
+    },
+
+    /// An I/O operation failed.
+    Io {
+        /// The kind of I/O error.
+        kind: std::io::ErrorKind,
+        /// The error message.
+        message: String,
+    },
+
+    /// An enumeration ran past its
+    /// [`timeout`](crate::EnumerationOptions::timeout), a wait ended
+    /// before its condition was met, or a window did not close in time.
+    ///
+    /// The windows gathered before an enumeration timed out are still
+    /// available.
+    TimedOut,
+
+    /// An enumeration was stopped through its
+    /// [`cancellation`](crate::EnumerationOptions::cancellation) token.
+    ///
+    /// The windows gathered before the cancellation are still available.
+    Cancelled,
+
+    /// Synthesized input was blocked, typically by User Interface
+    /// Privilege Isolation (UIPI) because the window belongs to a process
+    /// with a higher integrity level, such as an elevated one.
+    InputBlocked {
+        /// The window the input was meant for.
+        hwnd: WindowHandle,
+    },
+
     /// Other unspecified errors.
     Other(String),
 }
@@ -39,7 +154,10 @@ impl fmt::Display for WindowError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             WindowError::InvalidSelectionFormat => {
-                write!(f, "Invalid selection format. Use 'all', '1,2,3', or '1-3'")
+                write!(
+                    f,
+                    "Invalid selection format. Use 'all', '1,2,3', '1-3', '3-', '1-9:2', '-1', 'last', or 'all,!3'"
+                )
             }
             WindowError::InvalidPositionSortFormat => {
                 write!(
@@ -47,17 +165,191 @@ impl fmt::Display for WindowError {
                     "Invalid position sort format. Use 'x1', 'y-1', or 'x1|y1'"
                 )
             }
+            WindowError::InvalidSelectionToken {
+                token,
+                position,
+                reason,
+            } => write!(
+                f,
+                "Invalid selection item '{}' at position {}: {}",
+                token, position, reason
+            ),
+            WindowError::InvalidPositionSortToken {
+                token,
+                position,
+                reason,
+            } => write!(
+                f,
+                "Invalid position sort '{}' at position {}: {}",
+                token, position, reason
+            ),
+            WindowError::InvalidRule { name, reason } => {
+                write!(f, "Invalid rule '{}': {}", name, reason)
+            }
+            WindowError::InvalidSortFormat => {
+                write!(
+                    f,
+                    "Invalid sort format. Use 'key:order' pairs like 'pid:-1,title:1'"
+                )
+            }
+            WindowError::InvalidQueryFormat(term) => write!(f, "Invalid query term '{}'", term),
+            WindowError::InvalidFormatTemplate(placeholder) => {
+                write!(f, "Invalid format placeholder '{}'", placeholder)
+            }
+            WindowError::InvalidTableColumn(name) => write!(f, "Invalid table column '{}'", name),
             WindowError::InvalidRange => write!(f, "Invalid range format"),
             WindowError::InvalidIndex => write!(f, "Invalid index"),
             WindowError::InvalidSortOrder => {
                 write!(f, "Sort order must be 1 (ascending) or -1 (descending)")
             }
+            WindowError::WindowNotFound => write!(f, "Window not found"),
             WindowError::WindowsApiError(code) => write!(f, "Windows API error: 0x{:08x}", code),
+            WindowError::ApiCallFailed {
+                function,
+                code,
+                message,
+                hwnd,
+                pid,
+            } => {
+                write!(f, "{} failed", function)?;
+                match (hwnd, pid) {
+                    (Some(hwnd), Some(pid)) => write!(f, " for window {} (PID {})", hwnd, pid)?,
+                    (Some(hwnd), None) => write!(f, " for window {}", hwnd)?,
+                    (None, Some(pid)) => write!(f, " for process {}", pid)?,
+                    (None, None) => {}
+                }
+                if message.is_empty() {
+                    write!(f, " (0x{:08x})", code)
+                } else {
+                    write!(f, ": {} (0x{:08x})", message, code)
+                }
+            }
+            WindowError::ProcessAccessDenied { pid } => {
+                write!(f, "Access to process {} was denied", pid)
+            }
+            WindowError::Io { message, .. } => write!(f, "I/O error: {}", message),
+            WindowError::TimedOut => write!(f, "Operation timed out"),
+            WindowError::Cancelled => write!(f, "Enumeration was cancelled, results are partial"),
+            WindowError::InputBlocked { hwnd } => write!(f, "Input to window {} was blocked", hwnd),
             WindowError::Other(msg) => write!(f, "{}", msg),
         }
     }
 }
 
+impl WindowError {
+    /// Returns `true` if this error reports that access was denied, e.g.
+    /// when querying an elevated or protected process.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::WindowError;
+    ///
+    /// assert!(WindowError::WindowsApiError(0x8007_0005).is_access_denied());
+    /// assert!(!WindowError::InvalidIndex.is_access_denied());
+    /// ```
+    pub fn is_access_denied(&self) -> bool {
+        // ERROR_ACCESS_DENIED, either raw or wrapped in an HRESULT
+        matches!(self, WindowError::ProcessAccessDenied { .. })
+            || matches!(self.code(), Some(5 | 0x8007_0005))
+    }
+
+    /// Returns `true` if an enumeration was stopped early by a timeout or
+    /// cancellation, leaving partial results.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::WindowError;
+    ///
+    /// assert!(WindowError::TimedOut.is_interrupted());
+    /// assert!(!WindowError::WindowNotFound.is_interrupted());
+    /// ```
+    pub fn is_interrupted(&self) -> bool {
+        matches!(self, WindowError::TimedOut | WindowError::Cancelled)
+    }
+
+    /// Returns the Windows error code, if this error came from a Windows API call.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::WindowError;
+    ///
+    /// assert_eq!(WindowError::WindowsApiError(5).code(), Some(5));
+    /// assert_eq!(WindowError::WindowNotFound.code(), None);
+    /// ```
+    pub fn code(&self) -> Option<u32> {
+        match self {
+            WindowError::WindowsApiError(code) | WindowError::ApiCallFailed { code, .. } => {
+                Some(*code)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the window the error concerned, if known.
+    pub fn hwnd(&self) -> Option<WindowHandle> {
+        match self {
+            WindowError::ApiCallFailed { hwnd, .. } => *hwnd,
+            WindowError::InputBlocked { hwnd } => Some(*hwnd),
+            _ => None,
+        }
+    }
+
+    /// Returns the ID of the process the error concerned, if known.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::WindowError;
+    ///
+    /// let error = WindowError::ProcessAccessDenied { pid: 4 };
+    /// assert_eq!(error.pid(), Some(4));
+    /// assert!(error.is_access_denied());
+    /// ```
+    pub fn pid(&self) -> Option<u32> {
+        match self {
+            WindowError::ApiCallFailed { pid, .. } => *pid,
+            WindowError::ProcessAccessDenied { pid } => Some(*pid),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "windows")]
+impl WindowError {
+    /// Creates an [`ApiCallFailed`](WindowError::ApiCallFailed) error for the
+    /// named function.
+    pub(crate) fn api(function: &'static str, error: windows::core::Error) -> Self {
+        WindowError::ApiCallFailed {
+            function,
+            code: error.code().0 as u32,
+            message: error.message().to_string(),
+            hwnd: None,
+            pid: None,
+        }
+    }
+
+    /// Creates an [`ApiCallFailed`](WindowError::ApiCallFailed) error for the
+    /// named function from the calling thread's last error.
+    pub(crate) fn last_api(function: &'static str) -> Self {
+        Self::api(function, windows::core::Error::from_win32())
+    }
+
+    /// Attaches the window a failed API call concerned.
+    pub(crate) fn for_window(mut self, window: WindowHandle) -> Self {
+        if let WindowError::ApiCallFailed { hwnd, .. } = &mut self {
+            *hwnd = Some(window);
+        }
+        self
+    }
+
+    /// Attaches the process a failed API call concerned.
+    pub(crate) fn for_process(mut self, process: u32) -> Self {
+        if let WindowError::ApiCallFailed { pid, .. } = &mut self {
+            *pid = Some(process);
+        }
+        self
+    }
+}
+
 impl std::error::Error for WindowError {}
 
 // 只在启用 windows 特性时提供 From 转换实现
@@ -68,5 +360,14 @@ impl From<windows::core::Error> for WindowError {
     }
 }
 
+impl From<std::io::Error> for WindowError {
+    fn from(error: std::io::Error) -> Self {
+        WindowError::Io {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+}
+
 /// A specialized [`Result`] type for window operations.
 pub type Result<T> = std::result::Result<T, WindowError>;
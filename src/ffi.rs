@@ -0,0 +1,268 @@
+//! A C interface for consumers outside of Rust, e.g. C, C++, or C# through
+//! P/Invoke.
+//!
+//! Requires the `ffi` feature. Build a dynamic library with
+//! `cargo rustc --release --features ffi --crate-type cdylib`; the matching
+//! C header is `include/window_enumerator.h`. The build regenerates it into
+//! `OUT_DIR` with cbindgen and warns if the checked-in copy is out of date.
+//!
+//! Window lists are opaque and owned by the library: enumerate with
+//! [`we_enumerate`], narrow down with [`we_filter`], read the windows with
+//! [`we_list_len`] and [`we_list_get`], and release each list with
+//! [`we_free_list`]. Strings are NUL-terminated UTF-8 and live as long as
+//! their list. Functions returning `NULL` on failure describe the error
+//! through [`we_last_error`].
+//!
+//! ```c
+//! WindowListC *all = we_enumerate();
+//! WindowListC *chrome = we_filter(all, "process~chrome sort:title");
+//! for (size_t i = 0; i < we_list_len(chrome); i++) {
+//!     const WindowInfoC *window = we_list_get(chrome, i);
+//!     printf("%zu %s\n", window->index, window->title);
+//! }
+//! we_free_list(chrome);
+//! we_free_list(all);
+//! ```
+//!
+//! The flags of [`WindowInfoC`] are 1-byte C `bool`s, while C# marshals
+//! `bool` as a 4-byte `BOOL` by default, so declare them as such:
+//!
+//! ```csharp
+//! [MarshalAs(UnmanagedType.U1)] public bool IsVisible;
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::{Result, WindowError};
+use crate::types::{WindowInfo, WindowQuery, WindowState};
+use crate::utils;
+
+thread_local! {
+    /// The message of the last failed call on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A window as seen from C.
+///
+/// The strings are owned by the [`WindowListC`] the window belongs to.
+#[repr(C)]
+#[derive(Debug)]
+pub struct WindowInfoC {
+    /// The window handle (HWND).
+    pub hwnd: isize,
+    /// The handle of the owner window, or `0` if the window has no owner.
+    pub owner_hwnd: isize,
+    /// The process ID (PID) that owns the window.
+    pub pid: u32,
+    /// The ID of the thread that created the window.
+    pub thread_id: u32,
+    /// The 1-based index from the enumeration.
+    pub index: usize,
+    /// The window title.
+    pub title: *const c_char,
+    /// The window class name.
+    pub class_name: *const c_char,
+    /// The name of the process executable.
+    pub process_name: *const c_char,
+    /// The full path of the process executable.
+    pub process_file: *const c_char,
+    /// The x-coordinate of the top-left corner in screen coordinates.
+    pub x: i32,
+    /// The y-coordinate of the top-left corner in screen coordinates.
+    pub y: i32,
+    /// The width of the window in pixels.
+    pub width: i32,
+    /// The height of the window in pixels.
+    pub height: i32,
+    /// Whether the window is visible.
+    pub is_visible: bool,
+    /// Whether the window is minimized.
+    pub is_minimized: bool,
+    /// Whether the window is maximized.
+    pub is_maximized: bool,
+    /// Whether the window is cloaked by DWM.
+    pub is_cloaked: bool,
+    /// Whether the window did not respond to messages.
+    pub is_hung: bool,
+}
+
+/// An opaque list of windows returned by [`we_enumerate`] and
+/// [`we_filter`].
+pub struct WindowListC {
+    windows: Vec<WindowInfo>,
+    items: Vec<WindowInfoC>,
+    /// The strings the items point into.
+    _strings: Vec<CString>,
+}
+
+impl WindowListC {
+    fn new(windows: Vec<WindowInfo>) -> Self {
+        let mut strings = Vec::with_capacity(windows.len() * 4);
+        let mut text = |value: &str| {
+            // Interior NULs would cut the string short in C
+            let string = CString::new(value.replace('\0', "")).unwrap_or_default();
+            let pointer = string.as_ptr();
+            strings.push(string);
+            pointer
+        };
+
+        let items = windows
+            .iter()
+            .map(|window| WindowInfoC {
+                hwnd: window.hwnd.as_raw(),
+                owner_hwnd: window.owner_hwnd.map_or(0, |owner| owner.as_raw()),
+                pid: window.pid,
+                thread_id: window.thread_id,
+                index: window.index,
+                title: text(&window.title),
+                class_name: text(&window.class_name),
+                process_name: text(&window.process_name),
+                process_file: text(&window.process_file.to_string_lossy()),
+                x: window.position.x,
+                y: window.position.y,
+                width: window.position.width,
+                height: window.position.height,
+                is_visible: window.is_visible,
+                is_minimized: window.is_minimized,
+                is_maximized: window.state() == WindowState::Maximized,
+                is_cloaked: window.is_cloaked,
+                is_hung: window.is_hung,
+            })
+            .collect();
+
+        Self {
+            windows,
+            items,
+            _strings: strings,
+        }
+    }
+}
+
+/// Runs `call`, turning errors and panics into `NULL` and a message for
+/// [`we_last_error`].
+fn guard(call: impl FnOnce() -> Result<WindowListC>) -> *mut WindowListC {
+    let result = catch_unwind(AssertUnwindSafe(call))
+        .unwrap_or_else(|_| Err(WindowError::Other("window-enumerator panicked".to_string())));
+    match result {
+        Ok(list) => {
+            set_last_error(None);
+            Box::into_raw(Box::new(list))
+        }
+        Err(error) => {
+            set_last_error(Some(error.to_string()));
+            ptr::null_mut()
+        }
+    }
+}
+
+fn set_last_error(message: Option<String>) {
+    let message =
+        message.map(|message| CString::new(message.replace('\0', "")).unwrap_or_default());
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// Enumerates the visible top-level windows with the default options.
+///
+/// Returns `NULL` on failure. The list must be released with
+/// [`we_free_list`].
+#[no_mangle]
+pub extern "C" fn we_enumerate() -> *mut WindowListC {
+    guard(|| {
+        let mut enumerator = WindowEnumerator::new();
+        enumerator.enumerate_all_windows()?;
+        Ok(WindowListC::new(enumerator.get_windows().to_vec()))
+    })
+}
+
+/// Filters, sorts, and selects the windows of a list with a query string,
+/// e.g. `"process~chrome sort:title select:1-3"`.
+///
+/// See [`parse_query`](crate::parse_query) for the syntax. Returns a new
+/// list, or `NULL` if an argument is `NULL` or the query is invalid. The
+/// given list stays valid.
+///
+/// # Safety
+///
+/// `list` must come from this library and not have been freed, and
+/// `query` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn we_filter(
+    list: *const WindowListC,
+    query: *const c_char,
+) -> *mut WindowListC {
+    guard(|| {
+        if list.is_null() || query.is_null() {
+            return Err(WindowError::Other(
+                "list and query must not be NULL".to_string(),
+            ));
+        }
+        let list = &*list;
+        let query = CStr::from_ptr(query).to_string_lossy();
+
+        let (criteria, sort_keys, selection) = utils::parse_query(&query)?;
+        let windows = WindowQuery::new(&list.windows)
+            .filter(&criteria)
+            .sort(&sort_keys)
+            .select(&selection)
+            .collect();
+        Ok(WindowListC::new(windows))
+    })
+}
+
+/// Returns the number of windows in a list, or `0` for `NULL`.
+///
+/// # Safety
+///
+/// `list` must be `NULL` or come from this library and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn we_list_len(list: *const WindowListC) -> usize {
+    list.as_ref().map_or(0, |list| list.items.len())
+}
+
+/// Returns the window at a 0-based position of a list, or `NULL` if the
+/// position is out of range.
+///
+/// The window is valid until the list is freed.
+///
+/// # Safety
+///
+/// `list` must be `NULL` or come from this library and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn we_list_get(
+    list: *const WindowListC,
+    position: usize,
+) -> *const WindowInfoC {
+    list.as_ref()
+        .and_then(|list| list.items.get(position))
+        .map_or(ptr::null(), |item| item as *const WindowInfoC)
+}
+
+/// Releases a list and all of its windows and strings. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `list` must be `NULL` or come from this library and not have been freed
+/// already.
+#[no_mangle]
+pub unsafe extern "C" fn we_free_list(list: *mut WindowListC) {
+    if !list.is_null() {
+        drop(Box::from_raw(list));
+    }
+}
+
+/// Returns the error message of the last failed call on this thread, or
+/// `NULL` if the last call succeeded.
+///
+/// The message is valid until the next call on the same thread.
+#[no_mangle]
+pub extern "C" fn we_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
@@ -0,0 +1,180 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::Result;
+use crate::types::{FocusRecord, ProcessFocusRecord, WindowEvent, WindowHandle};
+use crate::watcher::{lock, HookThread};
+
+/// Focus durations accumulated by a [`FocusTracker`].
+#[derive(Default)]
+struct FocusState {
+    /// The current foreground window and since when it has been focused.
+    current: Option<(WindowHandle, Instant)>,
+    records: HashMap<WindowHandle, FocusRecord>,
+}
+
+impl FocusState {
+    /// Ends the focus span of the previous foreground window and starts one
+    /// for the window of `record`, if any.
+    fn focus(&mut self, record: Option<FocusRecord>, now: Instant) {
+        self.close_span(now);
+        if let Some(record) = record {
+            let hwnd = record.hwnd;
+            self.records.entry(hwnd).or_insert(record);
+            self.current = Some((hwnd, now));
+        }
+    }
+
+    /// Adds the time since the current span started to its window.
+    fn close_span(&mut self, now: Instant) {
+        if let Some((hwnd, since)) = self.current.take() {
+            if let Some(record) = self.records.get_mut(&hwnd) {
+                record.total_focused += now.saturating_duration_since(since);
+            }
+        }
+    }
+
+    /// Returns the records including the still running span.
+    fn snapshot(&self, now: Instant) -> Vec<FocusRecord> {
+        let mut records: Vec<FocusRecord> = self.records.values().cloned().collect();
+        if let Some((hwnd, since)) = self.current {
+            if let Some(record) = records.iter_mut().find(|record| record.hwnd == hwnd) {
+                record.total_focused += now.saturating_duration_since(since);
+            }
+        }
+        records
+    }
+}
+
+/// Creates an empty record, resolving the owning process of the window.
+///
+/// Opens the process, so it is called before locking the state rather than
+/// while the hook thread holds the lock. Returns `None` when no window is
+/// in the foreground.
+fn new_record(hwnd: WindowHandle) -> Option<FocusRecord> {
+    if hwnd.0 == 0 {
+        return None;
+    }
+    let (_, pid) = unsafe { WindowEnumerator::get_thread_process_id(HWND::from(hwnd)) };
+    let process_name = unsafe { WindowEnumerator::get_process_info(pid) }
+        .map(|(name, _, _)| name)
+        .unwrap_or_default();
+    Some(FocusRecord {
+        hwnd,
+        pid,
+        process_name,
+        total_focused: Duration::ZERO,
+    })
+}
+
+/// Accumulates how long each window and process spends in the foreground.
+///
+/// Foreground changes are observed with a WinEvent hook on a dedicated
+/// thread, so tracking costs nothing between focus changes. Time starts
+/// counting when the tracker is created and stops when it is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use window_enumerator::FocusTracker;
+///
+/// let tracker = FocusTracker::start().unwrap();
+/// std::thread::sleep(std::time::Duration::from_secs(60));
+///
+/// for record in tracker.process_records() {
+///     println!("{}: {:?}", record.process_name, record.total_focused);
+/// }
+/// ```
+pub struct FocusTracker {
+    state: Arc<Mutex<FocusState>>,
+    _thread: HookThread,
+}
+
+impl FocusTracker {
+    /// Starts tracking foreground time, beginning with the current
+    /// foreground window.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::Other`](crate::WindowError::Other) if the event
+    /// hooks cannot be installed.
+    pub fn start() -> Result<Self> {
+        let state = Arc::new(Mutex::new(FocusState::default()));
+        let shared = Arc::clone(&state);
+        let thread = HookThread::spawn(move || {
+            Box::new(move |event| {
+                if let WindowEvent::Foreground { hwnd } = event {
+                    let now = Instant::now();
+                    let record = new_record(hwnd);
+                    lock(&shared).focus(record, now);
+                }
+            })
+        })?;
+
+        let foreground = unsafe { GetForegroundWindow() };
+        let now = Instant::now();
+        let record = new_record(foreground.into());
+        lock(&state).focus(record, now);
+
+        Ok(Self {
+            state,
+            _thread: thread,
+        })
+    }
+
+    /// Returns the focus time of every window that has been in the
+    /// foreground, longest first.
+    pub fn records(&self) -> Vec<FocusRecord> {
+        let mut records = lock(&self.state).snapshot(Instant::now());
+        records.sort_by_key(|record| Reverse(record.total_focused));
+        records
+    }
+
+    /// Returns the focus time of every process whose windows have been in
+    /// the foreground, longest first.
+    pub fn process_records(&self) -> Vec<ProcessFocusRecord> {
+        let mut by_pid: HashMap<u32, ProcessFocusRecord> = HashMap::new();
+        for record in lock(&self.state).snapshot(Instant::now()) {
+            by_pid
+                .entry(record.pid)
+                .or_insert_with(|| ProcessFocusRecord {
+                    pid: record.pid,
+                    process_name: record.process_name.clone(),
+                    total_focused: Duration::ZERO,
+                })
+                .total_focused += record.total_focused;
+        }
+
+        let mut records: Vec<ProcessFocusRecord> = by_pid.into_values().collect();
+        records.sort_by_key(|record| Reverse(record.total_focused));
+        records
+    }
+
+    /// Returns the window currently tracked as being in the foreground.
+    pub fn current(&self) -> Option<WindowHandle> {
+        lock(&self.state).current.map(|(hwnd, _)| hwnd)
+    }
+
+    /// Discards all accumulated durations, keeping the current foreground
+    /// window focused from now on.
+    pub fn reset(&self) {
+        let mut state = lock(&self.state);
+        let current = state
+            .current
+            .and_then(|(hwnd, _)| state.records.remove(&hwnd));
+        *state = FocusState::default();
+        if let Some(record) = current {
+            let record = FocusRecord {
+                total_focused: Duration::ZERO,
+                ..record
+            };
+            state.focus(Some(record), Instant::now());
+        }
+    }
+}
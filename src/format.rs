@@ -0,0 +1,794 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::{Result, WindowError};
+use crate::types::{
+    FormatField, FormatSegment, FormatSpec, TableColumn, TableStyle, TitleOverflow, TreeRelation,
+    WindowInfo, WindowInspection, WindowList, WindowTree, WindowTreeNode,
+};
+
+/// Compact one-line output; use the alternate form (`{:#}`) for a detailed,
+/// multi-line description.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::{WindowHandle, WindowInfo};
+///
+/// let window = WindowInfo {
+///     hwnd: WindowHandle::new(0x1a2b),
+///     pid: 1234,
+///     title: "Test".to_string(),
+///     index: 1,
+///     ..Default::default()
+/// };
+/// assert_eq!(window.to_string(), "[1] 0x1a2b (PID: 1234) @ (0,0) - Test");
+/// assert!(format!("{:#}", window).contains("Title: Test"));
+/// ```
+impl fmt::Display for WindowInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            writeln!(f, "Index: {}", self.index)?;
+            writeln!(f, "Window Handle: 0x{:x}", self.hwnd)?;
+            writeln!(f, "Process ID: {}", self.pid)?;
+            writeln!(f, "Title: {}", self.title)?;
+            writeln!(f, "Class Name: {}", self.class_name)?;
+            writeln!(f, "Process Name: {}", self.process_name)?;
+            writeln!(f, "Process File: {}", self.process_file.display())?;
+            write!(
+                f,
+                "Position: ({}, {}) Size: {}x{}",
+                self.position.x, self.position.y, self.position.width, self.position.height
+            )
+        } else {
+            write!(
+                f,
+                "[{}] 0x{:x} (PID: {}) @ ({},{}) - {}",
+                self.index, self.hwnd, self.pid, self.position.x, self.position.y, self.title
+            )
+        }
+    }
+}
+
+impl WindowInfo {
+    /// Returns the compact one-line description, the same text as the
+    /// [`Display`](fmt::Display) form.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowHandle, WindowInfo};
+    ///
+    /// let window = WindowInfo {
+    ///     hwnd: WindowHandle::new(0x1a2b),
+    ///     pid: 1234,
+    ///     title: "Test".to_string(),
+    ///     index: 1,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(window.fmt_compact(), "[1] 0x1a2b (PID: 1234) @ (0,0) - Test");
+    /// ```
+    pub fn fmt_compact(&self) -> String {
+        self.to_string()
+    }
+
+    /// Renders the window through an output template.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{FormatSpec, WindowInfo};
+    ///
+    /// let window = WindowInfo {
+    ///     index: 3,
+    ///     pid: 1234,
+    ///     title: "Notepad".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let spec: FormatSpec = "{index}\t{pid}\t{title}".parse().unwrap();
+    /// assert_eq!(window.format(&spec), "3\t1234\tNotepad");
+    /// ```
+    pub fn format(&self, spec: &FormatSpec) -> String {
+        let mut output = String::new();
+        for segment in &spec.segments {
+            match segment {
+                FormatSegment::Literal(text) => output.push_str(text),
+                FormatSegment::Field(field) => output.push_str(&self.field_value(*field)),
+            }
+        }
+        output
+    }
+
+    /// Returns the textual value of a template field.
+    fn field_value(&self, field: FormatField) -> String {
+        match field {
+            FormatField::Index => self.index.to_string(),
+            FormatField::Hwnd => self.hwnd.to_string(),
+            FormatField::Pid => self.pid.to_string(),
+            FormatField::ThreadId => self.thread_id.to_string(),
+            FormatField::Title => self.title.clone(),
+            FormatField::ClassName => self.class_name.clone(),
+            FormatField::ProcessName => self.process_name.clone(),
+            FormatField::ProcessFile => self.process_file.display().to_string(),
+            FormatField::CommandLine => self.process_command_line.clone().unwrap_or_default(),
+            FormatField::Aumid => self.aumid.clone().unwrap_or_default(),
+            FormatField::X => self.position.x.to_string(),
+            FormatField::Y => self.position.y.to_string(),
+            FormatField::Width => self.position.width.to_string(),
+            FormatField::Height => self.position.height.to_string(),
+            FormatField::Monitor => self
+                .monitor_index
+                .map(|index| index.to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl FormatSpec {
+    /// Parses an output template.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::FormatSpec;
+    ///
+    /// assert!(FormatSpec::new("{hwnd} {{{title}}}").is_ok());
+    /// assert!(FormatSpec::new("{colour}").is_err());
+    /// assert!(FormatSpec::new("{title").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`WindowError::InvalidFormatTemplate`] for unknown or
+    /// unterminated placeholders.
+    pub fn new(template: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.next_if_eq(&'{').is_some() => literal.push('{'),
+                '}' if chars.next_if_eq(&'}').is_some() => literal.push('}'),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(WindowError::InvalidFormatTemplate(name)),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(FormatSegment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(FormatSegment::Field(parse_field(name.trim())?));
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(FormatSegment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl FromStr for FormatSpec {
+    type Err = WindowError;
+
+    fn from_str(template: &str) -> Result<Self> {
+        Self::new(template)
+    }
+}
+
+/// Maps a placeholder name to its field.
+fn parse_field(name: &str) -> Result<FormatField> {
+    let field = match name {
+        "index" => FormatField::Index,
+        "hwnd" => FormatField::Hwnd,
+        "pid" => FormatField::Pid,
+        "thread_id" => FormatField::ThreadId,
+        "title" => FormatField::Title,
+        "class_name" | "class" => FormatField::ClassName,
+        "process_name" | "process" => FormatField::ProcessName,
+        "process_file" | "file" => FormatField::ProcessFile,
+        "command_line" => FormatField::CommandLine,
+        "aumid" => FormatField::Aumid,
+        "x" => FormatField::X,
+        "y" => FormatField::Y,
+        "width" => FormatField::Width,
+        "height" => FormatField::Height,
+        "monitor" => FormatField::Monitor,
+        _ => return Err(WindowError::InvalidFormatTemplate(name.to_string())),
+    };
+    Ok(field)
+}
+
+impl WindowList {
+    /// Renders the windows as a table with index, handle, PID, position,
+    /// size, and title columns.
+    ///
+    /// Markdown cells escape `|`, and line breaks in Markdown and TSV cells
+    /// as well as tabs in TSV cells become spaces.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{TableStyle, WindowInfo, WindowList};
+    ///
+    /// let list = WindowList::new(vec![WindowInfo {
+    ///     index: 1,
+    ///     title: "Notepad".to_string(),
+    ///     ..Default::default()
+    /// }]);
+    /// let table = list.to_table(TableStyle::Markdown);
+    /// assert!(table.starts_with("| Index |"));
+    /// assert!(table.lines().nth(2).unwrap().ends_with("| Notepad |"));
+    ///
+    /// let list = WindowList::new(vec![WindowInfo {
+    ///     title: "a | b\tc".to_string(),
+    ///     ..Default::default()
+    /// }]);
+    /// assert!(list.to_table(TableStyle::Markdown).contains("| a \\| b\tc |"));
+    /// assert!(list.to_table(TableStyle::Tsv).ends_with("\ta | b c"));
+    /// ```
+    pub fn to_table(&self, style: TableStyle) -> String {
+        TablePrinter::new().style(style).render(self.iter())
+    }
+}
+
+/// The windows as a plain table, see [`WindowList::to_table`].
+impl fmt::Display for WindowList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_table(TableStyle::Plain))
+    }
+}
+
+/// A multi-line property dump, one `Name: value` pair per line.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::{WindowHandle, WindowInfo, WindowInspection};
+///
+/// let inspection = WindowInspection {
+///     info: WindowInfo {
+///         hwnd: WindowHandle::new(0x1a2b),
+///         class_name: "Notepad".to_string(),
+///         style: 0x1000_0000,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let dump = inspection.to_string();
+/// assert!(dump.contains("Window Handle: 0x1a2b"));
+/// assert!(dump.contains("Styles: 0x10000000 (WS_VISIBLE)"));
+/// ```
+impl fmt::Display for WindowInspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let info = &self.info;
+        let position = |position: &crate::types::WindowPosition| {
+            format!(
+                "({}, {}) {}x{}",
+                position.x, position.y, position.width, position.height
+            )
+        };
+
+        writeln!(f, "Window Handle: 0x{:x}", info.hwnd)?;
+        writeln!(f, "Title: {:?}", info.title)?;
+        writeln!(f, "Class Name: {}", info.class_name)?;
+        writeln!(f, "Process: {} (PID {})", info.process_name, info.pid)?;
+        writeln!(f, "Thread ID: {}", info.thread_id)?;
+        writeln!(f, "Process File: {}", info.process_file.display())?;
+        writeln!(f, "Window Rect: {}", position(&info.position))?;
+        writeln!(f, "Client Rect: {}", position(&info.geometry.client))?;
+        writeln!(f, "Frame Bounds: {}", position(&info.geometry.frame_bounds))?;
+        writeln!(
+            f,
+            "Styles: 0x{:08x} ({})",
+            info.style,
+            info.style_names().join(" | ")
+        )?;
+        writeln!(
+            f,
+            "Extended Styles: 0x{:08x} ({})",
+            info.ex_style,
+            info.ex_style_names().join(" | ")
+        )?;
+        if let Some(parent) = self.parent {
+            writeln!(f, "Parent: 0x{:x}", parent)?;
+        }
+        if let Some(owner) = info.owner_hwnd {
+            writeln!(f, "Owner: 0x{:x}", owner)?;
+        }
+        writeln!(f, "Control ID: {}", self.control_id)?;
+        writeln!(f, "Window Proc: 0x{:x}", self.window_proc)?;
+        writeln!(f, "Instance: 0x{:x}", self.instance)?;
+        writeln!(f, "User Data: 0x{:x}", self.user_data)?;
+        writeln!(f, "Unicode: {}", self.is_unicode)?;
+        writeln!(f, "Enabled: {}", self.is_enabled)?;
+        writeln!(f, "Visible: {}", info.is_visible)?;
+        writeln!(f, "Cloaked: {}", info.is_cloaked)?;
+        writeln!(f, "Hung: {}", info.is_hung)?;
+
+        let class = &self.class;
+        writeln!(f, "Class Atom: 0x{:04x}", class.atom)?;
+        writeln!(
+            f,
+            "Class Styles: 0x{:08x} ({})",
+            class.style,
+            class.style_names().join(" | ")
+        )?;
+        writeln!(f, "Class Module: 0x{:x}", class.module)?;
+        writeln!(f, "Class Proc: 0x{:x}", class.window_proc)?;
+        writeln!(
+            f,
+            "Class/Window Extra Bytes: {}/{}",
+            class.class_extra_bytes, class.window_extra_bytes
+        )?;
+        writeln!(
+            f,
+            "Class Icon/Cursor/Background: 0x{:x}/0x{:x}/0x{:x}",
+            class.icon, class.cursor, class.background
+        )?;
+
+        write!(f, "Properties:")?;
+        if self.properties.is_empty() {
+            write!(f, " none")?;
+        }
+        for property in &self.properties {
+            write!(f, "\n  {} = 0x{:x}", property.name, property.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// One line per window, indented by depth. Owned and hidden windows are
+/// tagged, since they are easy to mistake for children or to overlook.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::{WindowHandle, WindowInfo, WindowTree};
+///
+/// let tree = WindowTree::from_windows(&[
+///     WindowInfo {
+///         hwnd: WindowHandle::new(0x10),
+///         pid: 42,
+///         title: "Editor".to_string(),
+///         class_name: "EditorFrame".to_string(),
+///         is_visible: true,
+///         ..Default::default()
+///     },
+///     WindowInfo {
+///         hwnd: WindowHandle::new(0x20),
+///         pid: 42,
+///         title: "Save As".to_string(),
+///         class_name: "#32770".to_string(),
+///         owner_hwnd: Some(WindowHandle::new(0x10)),
+///         ..Default::default()
+///     },
+/// ]);
+/// assert_eq!(
+///     tree.to_string(),
+///     "0x10 \"Editor\" EditorFrame (PID 42)\n  \
+///        0x20 \"Save As\" #32770 (PID 42) [owned] [hidden]\n"
+/// );
+/// ```
+impl fmt::Display for WindowTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_node(
+            f: &mut fmt::Formatter<'_>,
+            node: &WindowTreeNode,
+            depth: usize,
+        ) -> fmt::Result {
+            write!(
+                f,
+                "{:indent$}0x{:x} {:?} {} (PID {})",
+                "",
+                node.hwnd,
+                node.title,
+                node.class_name,
+                node.pid,
+                indent = depth * 2
+            )?;
+            if node.relation == TreeRelation::Owned {
+                f.write_str(" [owned]")?;
+            }
+            if !node.is_visible {
+                f.write_str(" [hidden]")?;
+            }
+            writeln!(f)?;
+            node.children
+                .iter()
+                .try_for_each(|child| write_node(f, child, depth + 1))
+        }
+
+        self.roots
+            .iter()
+            .try_for_each(|root| write_node(f, root, 0))
+    }
+}
+
+impl WindowTree {
+    /// Renders the tree as a Graphviz DOT graph.
+    ///
+    /// Parent-child edges are solid and ownership edges dashed; hidden
+    /// windows are drawn in gray. Render it with e.g.
+    /// `dot -Tsvg windows.dot -o windows.svg`.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowHandle, WindowInfo, WindowTree};
+    ///
+    /// let tree = WindowTree::from_windows(&[
+    ///     WindowInfo { hwnd: WindowHandle::new(0x10), ..Default::default() },
+    ///     WindowInfo {
+    ///         hwnd: WindowHandle::new(0x20),
+    ///         owner_hwnd: Some(WindowHandle::new(0x10)),
+    ///         ..Default::default()
+    ///     },
+    /// ]);
+    /// let dot = tree.to_dot();
+    /// assert!(dot.starts_with("digraph windows {"));
+    /// assert!(dot.contains("\"0x10\" -> \"0x20\" [style=dashed];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        fn escape(text: &str) -> String {
+            text.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+        fn write_node(out: &mut String, node: &WindowTreeNode) {
+            out.push_str(&format!(
+                "    \"0x{:x}\" [label=\"0x{:x}\\n{}\\n{}\"{}];\n",
+                node.hwnd,
+                node.hwnd,
+                escape(&node.class_name),
+                escape(&node.title),
+                if node.is_visible {
+                    ""
+                } else {
+                    ", color=gray, fontcolor=gray"
+                }
+            ));
+            for child in &node.children {
+                let style = match child.relation {
+                    TreeRelation::Owned => " [style=dashed]",
+                    _ => "",
+                };
+                out.push_str(&format!(
+                    "    \"0x{:x}\" -> \"0x{:x}\"{};\n",
+                    node.hwnd, child.hwnd, style
+                ));
+                write_node(out, child);
+            }
+        }
+
+        let mut out = String::from("digraph windows {\n    node [shape=box];\n");
+        for root in &self.roots {
+            write_node(&mut out, root);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Renders windows as a table with a chosen set of columns.
+///
+/// Every column is as wide as its widest cell, so large PIDs and long
+/// titles stay aligned. Titles can be limited to a maximum width, either
+/// truncated or wrapped onto continuation lines; Markdown tables join
+/// wrapped lines with `<br>`, and TSV output is never shortened.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::{TableColumn, TablePrinter, TitleOverflow, WindowInfo};
+///
+/// let windows = vec![WindowInfo {
+///     index: 1,
+///     pid: 123456,
+///     title: "Untitled - Notepad".to_string(),
+///     ..Default::default()
+/// }];
+///
+/// let printer = TablePrinter::new()
+///     .columns(&[TableColumn::Index, TableColumn::Pid, TableColumn::Title])
+///     .max_title_width(10)
+///     .title_overflow(TitleOverflow::Wrap);
+/// let table = printer.render(&windows);
+/// assert_eq!(
+///     table.lines().collect::<Vec<_>>(),
+///     [
+///         "Index | PID    | Title",
+///         "------|--------|-----------",
+///         "1     | 123456 | Untitled -",
+///         "      |        | Notepad",
+///     ]
+/// );
+///
+/// let table = printer.title_overflow(TitleOverflow::Truncate).render(&windows);
+/// assert!(table.ends_with("| Untitled …"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TablePrinter {
+    columns: Vec<TableColumn>,
+    style: TableStyle,
+    max_title_width: Option<usize>,
+    title_overflow: TitleOverflow,
+}
+
+impl TablePrinter {
+    /// The columns of a new printer and of [`WindowList::to_table`].
+    pub const DEFAULT_COLUMNS: [TableColumn; 6] = [
+        TableColumn::Index,
+        TableColumn::Hwnd,
+        TableColumn::Pid,
+        TableColumn::Position,
+        TableColumn::Size,
+        TableColumn::Title,
+    ];
+
+    /// Creates a plain table printer with the default columns and titles of
+    /// any length.
+    pub fn new() -> Self {
+        Self {
+            columns: Self::DEFAULT_COLUMNS.to_vec(),
+            style: TableStyle::Plain,
+            max_title_width: None,
+            title_overflow: TitleOverflow::Truncate,
+        }
+    }
+
+    /// Sets the columns, in display order.
+    pub fn columns(mut self, columns: &[TableColumn]) -> Self {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    /// Sets the table layout.
+    pub fn style(mut self, style: TableStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Limits titles to `width` characters, see
+    /// [`title_overflow`](Self::title_overflow).
+    pub fn max_title_width(mut self, width: usize) -> Self {
+        self.max_title_width = Some(width.max(1));
+        self
+    }
+
+    /// Sets how titles longer than the maximum title width are shortened.
+    pub fn title_overflow(mut self, overflow: TitleOverflow) -> Self {
+        self.title_overflow = overflow;
+        self
+    }
+
+    /// Renders the windows as a table string.
+    pub fn render<'a>(&self, windows: impl IntoIterator<Item = &'a WindowInfo>) -> String {
+        let style = self.style;
+        let headers: Vec<&str> = self.columns.iter().map(|column| column.header()).collect();
+
+        // Each window takes several lines once its title wraps
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for window in windows {
+            let cells: Vec<Vec<String>> = self
+                .columns
+                .iter()
+                .map(|column| {
+                    let value = column.value(window);
+                    let lines = match self.max_title_width {
+                        Some(width)
+                            if *column == TableColumn::Title && style != TableStyle::Tsv =>
+                        {
+                            fit_title(value, width, self.title_overflow)
+                        }
+                        _ => vec![value],
+                    };
+                    lines.iter().map(|line| escape_cell(line, style)).collect()
+                })
+                .collect();
+
+            if style == TableStyle::Markdown {
+                rows.push(cells.iter().map(|lines| lines.join("<br>")).collect());
+                continue;
+            }
+            let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+            for line in 0..height {
+                rows.push(
+                    cells
+                        .iter()
+                        .map(|lines| lines.get(line).cloned().unwrap_or_default())
+                        .collect(),
+                );
+            }
+        }
+
+        let mut widths: Vec<usize> = headers
+            .iter()
+            .map(|header| header.chars().count())
+            .collect();
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let render_row = |cells: &[&str]| -> String {
+            match style {
+                TableStyle::Tsv => cells.join("\t"),
+                TableStyle::Plain | TableStyle::Markdown => {
+                    let last = cells.len().saturating_sub(1);
+                    let padded: Vec<String> = cells
+                        .iter()
+                        .zip(&widths)
+                        .enumerate()
+                        .map(|(column, (cell, width))| {
+                            // Plain tables leave the trailing column unpadded
+                            if column == last && style == TableStyle::Plain {
+                                cell.to_string()
+                            } else {
+                                format!("{:width$}", cell, width = width)
+                            }
+                        })
+                        .collect();
+                    match style {
+                        TableStyle::Markdown => format!("| {} |", padded.join(" | ")),
+                        _ => padded.join(" | ").trim_end().to_string(),
+                    }
+                }
+            }
+        };
+
+        let mut lines = vec![render_row(&headers)];
+        match style {
+            TableStyle::Plain => lines.push(
+                widths
+                    .iter()
+                    .map(|width| "-".repeat(*width))
+                    .collect::<Vec<_>>()
+                    .join("-|-"),
+            ),
+            TableStyle::Markdown => lines.push(format!(
+                "|{}|",
+                widths
+                    .iter()
+                    .map(|width| "-".repeat(width + 2))
+                    .collect::<Vec<_>>()
+                    .join("|")
+            )),
+            TableStyle::Tsv => {}
+        }
+        for row in &rows {
+            let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+            lines.push(render_row(&cells));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Prints the windows as a table to stdout.
+    ///
+    /// Requires the `console` feature.
+    #[cfg(feature = "console")]
+    pub fn print<'a>(&self, windows: impl IntoIterator<Item = &'a WindowInfo>) {
+        println!("{}", self.render(windows));
+    }
+}
+
+impl Default for TablePrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableColumn {
+    /// Returns the column heading.
+    fn header(self) -> &'static str {
+        match self {
+            TableColumn::Index => "Index",
+            TableColumn::Hwnd => "Handle",
+            TableColumn::Pid => "PID",
+            TableColumn::ClassName => "Class",
+            TableColumn::ProcessName => "Process",
+            TableColumn::Position => "Position",
+            TableColumn::Size => "Size",
+            TableColumn::Title => "Title",
+        }
+    }
+
+    /// Returns the cell text of a window.
+    fn value(self, window: &WindowInfo) -> String {
+        match self {
+            TableColumn::Index => window.index.to_string(),
+            TableColumn::Hwnd => format!("0x{:08x}", window.hwnd),
+            TableColumn::Pid => window.pid.to_string(),
+            TableColumn::ClassName => window.class_name.clone(),
+            TableColumn::ProcessName => window.process_name.clone(),
+            TableColumn::Position => format!("{},{}", window.position.x, window.position.y),
+            TableColumn::Size => format!("{}x{}", window.position.width, window.position.height),
+            TableColumn::Title => window.title.clone(),
+        }
+    }
+}
+
+impl FromStr for TableColumn {
+    type Err = WindowError;
+
+    /// Parses a column name.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::TableColumn;
+    ///
+    /// assert_eq!("class".parse::<TableColumn>().unwrap(), TableColumn::ClassName);
+    /// assert!("memory".parse::<TableColumn>().is_err());
+    /// ```
+    fn from_str(name: &str) -> Result<Self> {
+        let column = match name.trim().to_ascii_lowercase().as_str() {
+            "index" => TableColumn::Index,
+            "hwnd" | "handle" => TableColumn::Hwnd,
+            "pid" => TableColumn::Pid,
+            "class_name" | "class" => TableColumn::ClassName,
+            "process_name" | "process" => TableColumn::ProcessName,
+            "position" => TableColumn::Position,
+            "size" => TableColumn::Size,
+            "title" => TableColumn::Title,
+            _ => return Err(WindowError::InvalidTableColumn(name.to_string())),
+        };
+        Ok(column)
+    }
+}
+
+/// Escapes the characters of a cell that would break the row structure of
+/// `style`.
+fn escape_cell(cell: &str, style: TableStyle) -> String {
+    match style {
+        TableStyle::Plain => cell.to_string(),
+        TableStyle::Markdown => cell.replace('|', "\\|").replace(['\r', '\n'], " "),
+        TableStyle::Tsv => cell.replace(['\t', '\r', '\n'], " "),
+    }
+}
+
+/// Shortens a title to at most `width` characters per line.
+fn fit_title(title: String, width: usize, overflow: TitleOverflow) -> Vec<String> {
+    if title.chars().count() <= width {
+        return vec![title];
+    }
+    match overflow {
+        TitleOverflow::Truncate => {
+            let mut truncated: String = title.chars().take(width - 1).collect();
+            truncated.push('…');
+            vec![truncated]
+        }
+        TitleOverflow::Wrap => wrap_text(&title, width),
+    }
+}
+
+/// Breaks text into lines of at most `width` characters, between words
+/// where possible.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_len = 0;
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        loop {
+            let gap = usize::from(line_len > 0);
+            if line_len + gap + word.len() <= width {
+                if gap > 0 {
+                    line.push(' ');
+                }
+                line.extend(&word);
+                line_len += gap + word.len();
+                break;
+            }
+            if line_len > 0 {
+                lines.push(std::mem::take(&mut line));
+                line_len = 0;
+                continue;
+            }
+            // A word longer than a whole line is split wherever it must
+            lines.push(word.drain(..width).collect());
+        }
+    }
+    if line_len > 0 || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
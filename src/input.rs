@@ -0,0 +1,211 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::System::Threading::GetCurrentProcessId;
+use windows::Win32::UI::Input::KeyboardAndMouse::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::{Result, WindowError};
+use crate::types::{VirtualKey, WindowInfo};
+
+impl VirtualKey {
+    /// The Backspace key.
+    pub const BACKSPACE: Self = Self(0x08);
+    /// The Tab key.
+    pub const TAB: Self = Self(0x09);
+    /// The Enter key.
+    pub const ENTER: Self = Self(0x0D);
+    /// The Shift key.
+    pub const SHIFT: Self = Self(0x10);
+    /// The Ctrl key.
+    pub const CONTROL: Self = Self(0x11);
+    /// The Alt key.
+    pub const ALT: Self = Self(0x12);
+    /// The Esc key.
+    pub const ESCAPE: Self = Self(0x1B);
+    /// The Space bar.
+    pub const SPACE: Self = Self(0x20);
+    /// The Page Up key.
+    pub const PAGE_UP: Self = Self(0x21);
+    /// The Page Down key.
+    pub const PAGE_DOWN: Self = Self(0x22);
+    /// The End key.
+    pub const END: Self = Self(0x23);
+    /// The Home key.
+    pub const HOME: Self = Self(0x24);
+    /// The Left Arrow key.
+    pub const LEFT: Self = Self(0x25);
+    /// The Up Arrow key.
+    pub const UP: Self = Self(0x26);
+    /// The Right Arrow key.
+    pub const RIGHT: Self = Self(0x27);
+    /// The Down Arrow key.
+    pub const DOWN: Self = Self(0x28);
+    /// The Insert key.
+    pub const INSERT: Self = Self(0x2D);
+    /// The Delete key.
+    pub const DELETE: Self = Self(0x2E);
+    /// The left Windows key.
+    pub const WINDOWS: Self = Self(0x5B);
+
+    /// Returns the key for an ASCII letter, digit, or space.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => Some(Self(c.to_ascii_uppercase() as u16)),
+            ' ' => Some(Self::SPACE),
+            _ => None,
+        }
+    }
+
+    /// Returns the function key F1 to F24 for `n` from 1 to 24.
+    pub fn function(n: u8) -> Option<Self> {
+        (1..=24).contains(&n).then(|| Self(0x70 + u16::from(n) - 1))
+    }
+
+    /// Navigation keys must be sent as extended keys, or they arrive as
+    /// their numeric keypad counterparts.
+    fn is_extended(self) -> bool {
+        matches!(self.0, 0x21..=0x28 | 0x2D | 0x2E | 0x5B)
+    }
+}
+
+/// Keyboard input for [`WindowInfo`].
+///
+/// Requires the `input` feature.
+impl WindowInfo {
+    /// Brings the window to the foreground and types `text` into it
+    /// (`SendInput`).
+    ///
+    /// Characters are sent as Unicode input, independent of the keyboard
+    /// layout; line breaks are sent as the Enter key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::InputBlocked`] if the window belongs to a
+    /// process with a higher integrity level or the input was otherwise
+    /// rejected, and [`WindowError::Other`] if the window cannot be brought
+    /// to the foreground.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{FilterCriteria, VirtualKey, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// let criteria = FilterCriteria::builder().process_name_contains("notepad").build();
+    /// if let Some(notepad) = enumerator.filter_windows(&criteria).first() {
+    ///     notepad.send_text("Hello from window-enumerator\n").unwrap();
+    ///     notepad.send_keys(&[VirtualKey::CONTROL, VirtualKey::from_char('s').unwrap()]).unwrap();
+    /// }
+    /// ```
+    pub fn send_text(&self, text: &str) -> Result<()> {
+        let mut inputs = Vec::new();
+        for c in text.chars().filter(|&c| c != '\r') {
+            if c == '\n' {
+                inputs.push(key_input(VirtualKey::ENTER, false));
+                inputs.push(key_input(VirtualKey::ENTER, true));
+                continue;
+            }
+            let mut units = [0u16; 2];
+            for &unit in c.encode_utf16(&mut units).iter() {
+                inputs.push(unicode_input(unit, false));
+                inputs.push(unicode_input(unit, true));
+            }
+        }
+        self.send_input(&inputs)
+    }
+
+    /// Brings the window to the foreground and presses `keys` as a chord:
+    /// each key is pressed in order, then all are released in reverse
+    /// order, so `[CONTROL, S]` sends Ctrl+S.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::InputBlocked`] if the window belongs to a
+    /// process with a higher integrity level or the input was otherwise
+    /// rejected, and [`WindowError::Other`] if the window cannot be brought
+    /// to the foreground.
+    pub fn send_keys(&self, keys: &[VirtualKey]) -> Result<()> {
+        let inputs: Vec<INPUT> = keys
+            .iter()
+            .map(|&key| key_input(key, false))
+            .chain(keys.iter().rev().map(|&key| key_input(key, true)))
+            .collect();
+        self.send_input(&inputs)
+    }
+
+    /// Foregrounds the window and sends the input events, detecting input
+    /// blocked by UIPI.
+    fn send_input(&self, inputs: &[INPUT]) -> Result<()> {
+        if inputs.is_empty() {
+            return Ok(());
+        }
+
+        // UIPI silently drops input to windows of higher integrity levels
+        let (_, own_level) =
+            unsafe { WindowEnumerator::get_process_token_info(GetCurrentProcessId()) };
+        let (_, target_level) = unsafe { WindowEnumerator::get_process_token_info(self.pid) };
+        if let (Some(own), Some(target)) = (own_level, target_level) {
+            if target > own {
+                return Err(WindowError::InputBlocked { hwnd: self.hwnd });
+            }
+        }
+
+        let hwnd = HWND::from(self.hwnd);
+        unsafe {
+            if IsIconic(hwnd).as_bool() {
+                ShowWindow(hwnd, SW_RESTORE);
+            }
+            SetForegroundWindow(hwnd);
+            if GetForegroundWindow() != hwnd {
+                return Err(WindowError::Other(format!(
+                    "Window {} could not be brought to the foreground",
+                    self.hwnd
+                )));
+            }
+
+            let sent = SendInput(inputs, std::mem::size_of::<INPUT>() as i32);
+            if (sent as usize) < inputs.len() {
+                return Err(WindowError::InputBlocked { hwnd: self.hwnd });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds a key press or release of a virtual key.
+fn key_input(key: VirtualKey, release: bool) -> INPUT {
+    let mut flags = KEYBD_EVENT_FLAGS(0);
+    if key.is_extended() {
+        flags |= KEYEVENTF_EXTENDEDKEY;
+    }
+    if release {
+        flags |= KEYEVENTF_KEYUP;
+    }
+    keyboard_input(VIRTUAL_KEY(key.0), 0, flags)
+}
+
+/// Builds a press or release of a UTF-16 code unit.
+fn unicode_input(unit: u16, release: bool) -> INPUT {
+    let mut flags = KEYEVENTF_UNICODE;
+    if release {
+        flags |= KEYEVENTF_KEYUP;
+    }
+    keyboard_input(VIRTUAL_KEY(0), unit, flags)
+}
+
+/// Builds a keyboard input event.
+fn keyboard_input(key: VIRTUAL_KEY, scan: u16, flags: KEYBD_EVENT_FLAGS) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: key,
+                wScan: scan,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }
+}
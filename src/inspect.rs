@@ -0,0 +1,145 @@
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::{Result, WindowError};
+use crate::types::{
+    ClassDetails, EnumerationOptions, WindowHandle, WindowInspection, WindowProperty,
+};
+
+/// Deep inspection of a single window.
+impl WindowEnumerator {
+    /// Gathers everything known about one window, like the property pages
+    /// of Spy++, without enumerating the desktop.
+    ///
+    /// Besides the [`WindowInfo`](crate::WindowInfo) with its optional
+    /// properties (account, version information, class details, metrics,
+    /// and parent chain) loaded, this reads the window procedure, instance, and user
+    /// data, the class registration, and the attached window properties.
+    ///
+    /// The class is read with `GetClassLongPtrW` rather than
+    /// `GetClassInfoExW`, which only finds classes registered by the
+    /// calling process or system-wide.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::WindowNotFound`] if the window does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let foreground = WindowEnumerator::new().foreground_window().unwrap();
+    /// println!("{}", WindowEnumerator::inspect(foreground.hwnd).unwrap());
+    /// ```
+    pub fn inspect(hwnd: WindowHandle) -> Result<WindowInspection> {
+        let handle = HWND::from(hwnd);
+        if !unsafe { IsWindow(handle) }.as_bool() {
+            return Err(WindowError::WindowNotFound);
+        }
+
+        let mut enumerator = Self::new();
+        enumerator.options = EnumerationOptions {
+            include_hidden: true,
+            include_user_name: true,
+            include_version_info: true,
+            include_class_details: true,
+            ..Default::default()
+        };
+        let mut info = enumerator.get_single_window_info(handle)?;
+        info.load_process_metrics();
+        info.load_parent_chain();
+
+        unsafe {
+            let parent = GetAncestor(handle, GA_PARENT);
+            let style = GetWindowLongW(handle, GWL_STYLE) as u32;
+            let is_child = style & WS_CHILD.0 != 0;
+            Ok(WindowInspection {
+                info,
+                parent: (is_child && parent.0 != 0).then(|| parent.into()),
+                control_id: window_long_ptr(handle, GWLP_ID),
+                window_proc: window_long_ptr(handle, GWLP_WNDPROC) as usize,
+                instance: window_long_ptr(handle, GWLP_HINSTANCE) as usize,
+                user_data: window_long_ptr(handle, GWLP_USERDATA) as usize,
+                is_unicode: IsWindowUnicode(handle).as_bool(),
+                is_enabled: style & WS_DISABLED.0 == 0,
+                class: class_details(handle),
+                properties: window_properties(handle),
+            })
+        }
+    }
+}
+
+/// Reads the registration of the class of a window.
+pub(crate) unsafe fn class_details(hwnd: HWND) -> ClassDetails {
+    ClassDetails {
+        atom: class_long_ptr(hwnd, GCW_ATOM) as u16,
+        style: class_long_ptr(hwnd, GCL_STYLE) as u32,
+        module: class_long_ptr(hwnd, GCLP_HMODULE),
+        window_proc: class_long_ptr(hwnd, GCLP_WNDPROC),
+        class_extra_bytes: class_long_ptr(hwnd, GCL_CBCLSEXTRA) as u32,
+        window_extra_bytes: class_long_ptr(hwnd, GCL_CBWNDEXTRA) as u32,
+        icon: class_long_ptr(hwnd, GCLP_HICON),
+        cursor: class_long_ptr(hwnd, GCLP_HCURSOR),
+        background: class_long_ptr(hwnd, GCLP_HBRBACKGROUND),
+    }
+}
+
+/// Lists the properties attached to a window (`EnumPropsExW`).
+unsafe fn window_properties(hwnd: HWND) -> Vec<WindowProperty> {
+    let mut properties: Vec<WindowProperty> = Vec::new();
+    EnumPropsExW(
+        hwnd,
+        Some(collect_property_proc),
+        LPARAM(&mut properties as *mut _ as isize),
+    );
+    properties
+}
+
+/// Collects a window property into the `Vec<WindowProperty>` behind
+/// `lparam`.
+unsafe extern "system" fn collect_property_proc(
+    _hwnd: HWND,
+    name: PCWSTR,
+    value: HANDLE,
+    lparam: usize,
+) -> BOOL {
+    let properties = &mut *(lparam as *mut Vec<WindowProperty>);
+    // Properties named by an integer atom pass the atom instead of a string
+    let name = if name.0 as usize <= 0xFFFF {
+        format!("#{}", name.0 as usize)
+    } else {
+        name.to_string().unwrap_or_default()
+    };
+    properties.push(WindowProperty {
+        name,
+        value: value.0 as usize,
+    });
+    TRUE
+}
+
+/// Reads a pointer-sized window value.
+#[cfg(target_pointer_width = "64")]
+unsafe fn window_long_ptr(hwnd: HWND, index: WINDOW_LONG_PTR_INDEX) -> isize {
+    GetWindowLongPtrW(hwnd, index)
+}
+
+/// Reads a pointer-sized window value.
+#[cfg(not(target_pointer_width = "64"))]
+unsafe fn window_long_ptr(hwnd: HWND, index: WINDOW_LONG_PTR_INDEX) -> isize {
+    GetWindowLongW(hwnd, index) as isize
+}
+
+/// Reads a pointer-sized class value.
+#[cfg(target_pointer_width = "64")]
+unsafe fn class_long_ptr(hwnd: HWND, index: GET_CLASS_LONG_INDEX) -> usize {
+    GetClassLongPtrW(hwnd, index)
+}
+
+/// Reads a pointer-sized class value.
+#[cfg(not(target_pointer_width = "64"))]
+unsafe fn class_long_ptr(hwnd: HWND, index: GET_CLASS_LONG_INDEX) -> usize {
+    GetClassLongW(hwnd, index) as usize
+}
@@ -0,0 +1,167 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::{Result, WindowError};
+use crate::types::{Layout, LayoutEngine, MonitorInfo, WindowHandle, WindowInfo, WindowPosition};
+
+/// Moves and resizes several windows at once (`DeferWindowPos`), so they are
+/// redrawn together instead of one after another.
+///
+/// The moves are applied by [`commit`](Self::commit). The system offers no
+/// way to discard a batch, so dropping an uncommitted mover applies the
+/// moves as well.
+///
+/// # Examples
+///
+/// ```no_run
+/// use window_enumerator::{WindowBatchMover, WindowEnumerator, WindowPosition};
+///
+/// let mut enumerator = WindowEnumerator::new();
+/// enumerator.enumerate_all_windows().unwrap();
+/// let windows = enumerator.get_windows();
+///
+/// let mut mover = WindowBatchMover::begin(windows.len()).unwrap();
+/// for (i, window) in windows.iter().enumerate() {
+///     let offset = 40 * i as i32;
+///     let position = WindowPosition { x: offset, y: offset, width: 800, height: 600 };
+///     mover = mover.move_window(window.hwnd, position).unwrap();
+/// }
+/// mover.commit().unwrap();
+/// ```
+pub struct WindowBatchMover {
+    /// The pending batch; `None` once committed or freed by a failed move.
+    batch: Option<HDWP>,
+}
+
+impl WindowBatchMover {
+    /// Starts a batch, reserving room for `count` moves (more are allowed).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the batch cannot be allocated.
+    pub fn begin(count: usize) -> Result<Self> {
+        let batch = unsafe { BeginDeferWindowPos(count as i32) }
+            .map_err(|e| WindowError::api("BeginDeferWindowPos", e))?;
+        Ok(Self { batch: Some(batch) })
+    }
+
+    /// Adds moving and resizing a window to the batch, without changing its
+    /// Z-order or activating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the window cannot be
+    /// positioned. The system discards the whole batch in that case.
+    pub fn move_window(mut self, hwnd: WindowHandle, position: WindowPosition) -> Result<Self> {
+        let Some(batch) = self.batch.take() else {
+            return Ok(self);
+        };
+        let batch = unsafe {
+            DeferWindowPos(
+                batch,
+                HWND::from(hwnd),
+                HWND::default(),
+                position.x,
+                position.y,
+                position.width,
+                position.height,
+                SWP_NOZORDER | SWP_NOACTIVATE,
+            )
+        }
+        .map_err(|e| WindowError::api("DeferWindowPos", e).for_window(hwnd))?;
+        self.batch = Some(batch);
+        Ok(self)
+    }
+
+    /// Applies all moves of the batch at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the windows cannot be moved.
+    pub fn commit(mut self) -> Result<()> {
+        match self.batch.take() {
+            Some(batch) => unsafe { EndDeferWindowPos(batch) }
+                .map_err(|e| WindowError::api("EndDeferWindowPos", e)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for WindowBatchMover {
+    fn drop(&mut self) {
+        if let Some(batch) = self.batch.take() {
+            let _ = unsafe { EndDeferWindowPos(batch) };
+        }
+    }
+}
+
+/// Applying layouts to windows.
+impl LayoutEngine {
+    /// Creates an engine arranging windows within the work area of a monitor.
+    pub fn for_monitor(monitor: &MonitorInfo) -> Self {
+        Self::new(monitor.work_area)
+    }
+
+    /// Creates an engine arranging windows within the work area of the
+    /// primary monitor.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if the monitors cannot be
+    /// enumerated, and [`WindowError::Other`] if none is primary.
+    pub fn primary_monitor() -> Result<Self> {
+        WindowEnumerator::enumerate_monitors()?
+            .iter()
+            .find(|monitor| monitor.is_primary)
+            .map(Self::for_monitor)
+            .ok_or_else(|| WindowError::Other("No primary monitor found".to_string()))
+    }
+
+    /// Moves and resizes `windows` according to `layout`, in one
+    /// [`WindowBatchMover`] batch so they are repositioned together.
+    ///
+    /// Minimized and maximized windows are restored first, since the
+    /// system does not move them otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if a window cannot be
+    /// positioned. No window is moved in that case, although minimized and
+    /// maximized windows may already have been restored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{FilterCriteria, Layout, LayoutEngine, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// let criteria = FilterCriteria::builder().process_name_contains("code").build();
+    /// let windows = enumerator.filter_windows(&criteria);
+    ///
+    /// let engine = LayoutEngine::primary_monitor().unwrap().with_gap(8);
+    /// engine.apply(&Layout::Columns, &windows).unwrap();
+    /// ```
+    pub fn apply(&self, layout: &Layout, windows: &[WindowInfo]) -> Result<()> {
+        let positions = self.compute(layout, windows.len());
+        if positions.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            for window in &windows[..positions.len()] {
+                let hwnd = HWND::from(window.hwnd);
+                if IsIconic(hwnd).as_bool() || IsZoomed(hwnd).as_bool() {
+                    ShowWindow(hwnd, SW_RESTORE);
+                }
+            }
+        }
+
+        let mut mover = WindowBatchMover::begin(positions.len())?;
+        for (window, &position) in windows.iter().zip(&positions) {
+            mover = mover.move_window(window.hwnd, position)?;
+        }
+        mover.commit()
+    }
+}
@@ -9,6 +9,7 @@
 //! - **Advanced Filtering**: Filter windows by PID, title, class name, process name, and file path
 //! - **Sorting**: Sort windows by PID, title, or position (with `sorting` feature)
 //! - **Selection**: Select specific windows by index (with `selection` feature)
+//! - **Window Events**: Watch windows being created, destroyed, renamed, or moved
 //!
 //! # Examples
 //!
@@ -25,7 +26,7 @@
 //! };
 //! let chrome_windows = enumerator.filter_windows(&criteria);
 //! for window in chrome_windows {
-//!     window.print_compact();
+//!     println!("{}", window);
 //! }
 //!
 //! // Use filtering criteria
@@ -41,10 +42,32 @@
 //! - `windows`: Enables Windows API functionality (enabled by default)
 //! - `sorting`: Enables window sorting capabilities
 //! - `selection`: Enables window selection by indices
+//! - `console`: Enables the deprecated `print*` helpers writing to stdout
+//!   (enabled by default); `Display` and `fmt_compact` format without side
+//!   effects
+//! - `cli`: Builds the `window-enumerator` command-line executable
+//! - `command-line`: Captures each window's process command line
+//! - `capture`: Enables capturing window images with `WindowInfo::capture`
+//! - `signing`: Verifies the Authenticode signature and publisher of each
+//!   process executable
+//! - `uia`: Reads the UI Automation name, control type, and automation ID
+//!   of each window
+//! - `input`: Enables typing into windows with `WindowInfo::send_text` and
+//!   `WindowInfo::send_keys`
+//! - `tokio`: Enables `WindowEventStream`, an async stream of window events
+//! - `parallel`: Gathers per-window metadata on the rayon thread pool
+//! - `aumid`: Reads per-window Application User Model IDs from the shell property store
+//! - `tracing`: Emits `tracing` spans and events for enumeration passes,
+//!   skipped windows, and failed Windows API calls
+//! - `serde`: Derives `Serialize` and `Deserialize` for `WindowRuleSet` and
+//!   `WindowPlacement`
+//! - `ffi`: Exposes a C interface (`we_enumerate`, `we_filter`, ...) and
+//!   generates its header with cbindgen, see the `ffi` module
 
 #![warn(missing_docs)]
 
 mod errors;
+mod format;
 mod models;
 mod types;
 // 条件性导出整个 utils 模块
@@ -62,18 +85,72 @@ pub mod utils;
 #[cfg(feature = "windows")]
 mod enumerator;
 
+#[cfg(feature = "windows")]
+mod watcher;
+
+#[cfg(feature = "windows")]
+mod actions;
+
+#[cfg(feature = "windows")]
+mod wait;
+
+#[cfg(feature = "windows")]
+mod focus;
+
+#[cfg(feature = "windows")]
+mod layout;
+
+#[cfg(feature = "windows")]
+mod controls;
+
+#[cfg(feature = "windows")]
+mod tree;
+
+#[cfg(feature = "windows")]
+mod inspect;
+
+#[cfg(feature = "capture")]
+mod capture;
+
+#[cfg(feature = "input")]
+mod input;
+
+#[cfg(feature = "signing")]
+mod signing;
+
+#[cfg(feature = "uia")]
+mod uia;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(all(feature = "sorting", feature = "selection"))]
+mod rules;
+
 pub use errors::*;
 pub use models::*;
 pub use types::*;
 
-
+pub use format::TablePrinter;
 
 // 公开导出工具函数
 #[cfg(feature = "selection")]
 pub use utils::parse_selection;
 
 #[cfg(feature = "sorting")]
-pub use utils::parse_position_sort;
+pub use utils::{parse_position_sort, parse_sort};
+
+#[cfg(all(feature = "sorting", feature = "selection"))]
+pub use utils::parse_query;
 
 #[cfg(feature = "windows")]
 pub use enumerator::*;
+
+#[cfg(feature = "windows")]
+pub use watcher::*;
+
+#[cfg(feature = "windows")]
+pub use focus::*;
+
+#[cfg(feature = "windows")]
+pub use layout::*;
@@ -0,0 +1,390 @@
+//! Command-line interface for the `window-enumerator` library.
+//!
+//! Requires the `cli` feature.
+
+use clap::{Parser, ValueEnum};
+use window_enumerator::{
+    parse_position_sort, parse_selection, parse_sort, EnumerationOptions, FilterCriteria,
+    ProcessArch, Result, SortKey, SortOrder, StringFilter, TableColumn, TablePrinter,
+    TitleOverflow, WindowEnumerator, WindowError, WindowInfo,
+};
+
+/// Enumerate, filter, sort, and select windows.
+#[derive(Debug, Parser)]
+#[command(name = "window-enumerator", version, about)]
+struct Cli {
+    /// Only include "real" application windows, as shown by Alt-Tab.
+    #[arg(long)]
+    app_windows: bool,
+
+    /// Filter by exact process ID.
+    #[arg(long)]
+    pid: Option<u32>,
+
+    /// Filter by title substring (case-insensitive).
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Filter by title glob pattern, e.g. "* - Notepad" (case-insensitive).
+    #[arg(long)]
+    title_glob: Option<String>,
+
+    /// Filter by class name substring (case-insensitive).
+    #[arg(long)]
+    class: Option<String>,
+
+    /// Filter by class name glob pattern (case-insensitive).
+    #[arg(long)]
+    class_glob: Option<String>,
+
+    /// Filter by process name substring (case-insensitive).
+    #[arg(long)]
+    process: Option<String>,
+
+    /// Filter by process name glob pattern, e.g. "chrom*.exe" (case-insensitive).
+    #[arg(long)]
+    process_glob: Option<String>,
+
+    /// Match the glob filters case-sensitively.
+    #[arg(long)]
+    case_sensitive: bool,
+
+    /// Filter by process file path substring (case-insensitive).
+    #[arg(long)]
+    file: Option<String>,
+
+    /// Filter by process command line substring (case-insensitive).
+    #[arg(long)]
+    command_line: Option<String>,
+
+    /// Filter by owning account substring, e.g. `DOMAIN\user` (case-insensitive).
+    #[arg(long)]
+    user: Option<String>,
+
+    /// Filter by the product name of the executable, e.g. "Microsoft Office" (case-insensitive).
+    #[arg(long)]
+    product: Option<String>,
+
+    /// Filter by any ancestor process name, e.g. the launcher that started the process (case-insensitive).
+    #[arg(long)]
+    spawned_by: Option<String>,
+
+    /// Filter by whether the executable has a valid Authenticode signature.
+    #[cfg(feature = "signing")]
+    #[arg(long)]
+    signed: Option<bool>,
+
+    /// Filter by minimum window width.
+    #[arg(long)]
+    min_width: Option<i32>,
+
+    /// Filter by minimum window height.
+    #[arg(long)]
+    min_height: Option<i32>,
+
+    /// Filter by maximum window width.
+    #[arg(long)]
+    max_width: Option<i32>,
+
+    /// Filter by maximum window height.
+    #[arg(long)]
+    max_height: Option<i32>,
+
+    /// Filter by the session of the owning process.
+    #[arg(long)]
+    session: Option<u32>,
+
+    /// Enumerate the windows of the named desktop instead of the current one.
+    #[arg(long)]
+    desktop: Option<String>,
+
+    /// Filter by 1-based monitor index.
+    #[arg(long)]
+    monitor: Option<usize>,
+
+    /// Exclude tool windows.
+    #[arg(long)]
+    exclude_tool_windows: bool,
+
+    /// Exclude windows cloaked by DWM.
+    #[arg(long)]
+    exclude_cloaked: bool,
+
+    /// Exclude ghost windows shown in place of hung windows.
+    #[arg(long)]
+    exclude_ghost: bool,
+
+    /// Exclude desktop shell and system windows (taskbar, desktop, tooltips, ...).
+    #[arg(long)]
+    exclude_system: bool,
+
+    /// Only include windows of elevated (true) or non-elevated (false) processes.
+    #[arg(long)]
+    elevated: Option<bool>,
+
+    /// Only include windows of processes of this architecture (x86, x64, arm, arm64).
+    #[arg(long, value_parser = parse_arch)]
+    arch: Option<ProcessArch>,
+
+    /// Only include fullscreen (true) or non-fullscreen (false) windows.
+    #[arg(long)]
+    fullscreen: Option<bool>,
+
+    /// Only include always-on-top windows.
+    #[arg(long)]
+    topmost: bool,
+
+    /// Only include windows that are responding.
+    #[arg(long, conflicts_with = "hung_only")]
+    responding_only: bool,
+
+    /// Only include windows that are not responding.
+    #[arg(long)]
+    hung_only: bool,
+
+    /// Gather memory, CPU time, handle, and thread figures of each process.
+    #[arg(long)]
+    metrics: bool,
+
+    /// Sort specification, e.g. "pid:-1,title:1,x:1". These keys take
+    /// precedence over the individual sort flags below.
+    #[arg(long, allow_hyphen_values = true)]
+    sort: Option<String>,
+
+    /// Sort by PID (1: ascending, -1: descending).
+    #[arg(long, allow_hyphen_values = true)]
+    sort_pid: Option<i8>,
+
+    /// Sort by title (1: ascending, -1: descending).
+    #[arg(long, allow_hyphen_values = true)]
+    sort_title: Option<i8>,
+
+    /// Sort by position, e.g. "x1", "y-1", or "x1|y1".
+    #[arg(long, allow_hyphen_values = true)]
+    sort_position: Option<String>,
+
+    /// Select windows by position in the filtered and sorted results, e.g.
+    /// "all", "1,2,3", "1-3", "3-", "1-9:2", "-1", "last", or "all,!3".
+    #[arg(long, default_value = "all")]
+    select: String,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Table columns, e.g. "index,pid,process,title". Available: index,
+    /// hwnd, pid, class, process, position, size, title.
+    #[arg(long, value_delimiter = ',')]
+    columns: Vec<TableColumn>,
+
+    /// Shorten table titles to at most this many characters.
+    #[arg(long)]
+    max_title_width: Option<usize>,
+
+    /// Wrap table titles longer than --max-title-width instead of truncating them.
+    #[arg(long, requires = "max_title_width")]
+    wrap_titles: bool,
+}
+
+/// Supported output formats.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// An aligned table with one window per line.
+    Table,
+    /// A JSON array of window objects.
+    Json,
+}
+
+/// Parses the `--arch` value.
+fn parse_arch(name: &str) -> std::result::Result<ProcessArch, String> {
+    ProcessArch::from_name(name).ok_or_else(|| format!("unknown architecture `{}`", name))
+}
+
+/// Converts a `--sort-pid` or `--sort-title` value, rejecting anything but 1 and -1.
+fn sort_order(order: i8) -> Result<SortOrder> {
+    match order {
+        1 => Ok(SortOrder::Asc),
+        -1 => Ok(SortOrder::Desc),
+        _ => Err(WindowError::InvalidSortOrder),
+    }
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let printer = table_printer(&cli);
+    let glob = |pattern: String| {
+        let filter = StringFilter::glob(pattern);
+        if cli.case_sensitive {
+            filter.case_sensitive()
+        } else {
+            filter
+        }
+    };
+
+    let criteria = FilterCriteria {
+        pid: cli.pid,
+        title_contains: cli.title,
+        class_name_contains: cli.class,
+        process_name_contains: cli.process,
+        process_file_contains: cli.file,
+        title_matches: cli.title_glob.map(glob),
+        class_name_matches: cli.class_glob.map(glob),
+        process_name_matches: cli.process_glob.map(glob),
+        command_line_contains: cli.command_line,
+        user_contains: cli.user,
+        product_name_contains: cli.product,
+        spawned_by_contains: cli.spawned_by,
+        #[cfg(feature = "signing")]
+        signed: cli.signed,
+        min_width: cli.min_width,
+        min_height: cli.min_height,
+        max_width: cli.max_width,
+        max_height: cli.max_height,
+        session_id: cli.session,
+        monitor_index: cli.monitor,
+        exclude_tool_windows: cli.exclude_tool_windows,
+        exclude_cloaked: cli.exclude_cloaked,
+        exclude_ghost_windows: cli.exclude_ghost,
+        exclude_system_windows: cli.exclude_system,
+        elevated: cli.elevated,
+        process_arch: cli.arch,
+        topmost: cli.topmost.then_some(true),
+        fullscreen: cli.fullscreen,
+        responding_only: cli.responding_only,
+        hung_only: cli.hung_only,
+        ..Default::default()
+    };
+
+    let mut sort_keys: Vec<SortKey> = match cli.sort {
+        Some(ref sort) => parse_sort(sort)?,
+        None => Vec::new(),
+    };
+    if let Some(order) = cli.sort_pid {
+        sort_keys.push(SortKey::Pid(sort_order(order)?));
+    }
+    if let Some(order) = cli.sort_title {
+        sort_keys.push(SortKey::Title(sort_order(order)?));
+    }
+    if let Some(ref sort) = cli.sort_position {
+        if let Some(position) = parse_position_sort(sort)? {
+            sort_keys.extend(position.to_keys());
+        }
+    }
+
+    let selection = parse_selection(&cli.select)?.by_position();
+
+    // Account names and process metrics are costly, so only gather them when needed
+    let sorts_by_metrics = sort_keys
+        .iter()
+        .any(|key| matches!(key, SortKey::Memory(_) | SortKey::StartTime(_)));
+    let options = EnumerationOptions {
+        include_user_name: criteria.user_contains.is_some(),
+        include_version_info: criteria.product_name_contains.is_some(),
+        include_parent_chain: criteria.spawned_by_contains.is_some(),
+        include_process_metrics: cli.metrics || sorts_by_metrics,
+        desktop: cli.desktop,
+        #[cfg(feature = "signing")]
+        verify_signatures: criteria.signed.is_some(),
+        ..Default::default()
+    };
+    let mut enumerator = WindowEnumerator::new();
+    if cli.app_windows {
+        enumerator.enumerate_app_windows_with(&options)?;
+    } else {
+        enumerator.enumerate_all_windows_with(&options)?;
+    }
+
+    let windows =
+        enumerator.filter_sort_windows_with_selection_refs(&criteria, &sort_keys, &selection);
+
+    match cli.format {
+        OutputFormat::Table => println!("{}", printer.render(windows.iter().copied())),
+        OutputFormat::Json => print_json(&windows),
+    }
+
+    Ok(())
+}
+
+/// Configures the table output from the command-line options.
+fn table_printer(cli: &Cli) -> TablePrinter {
+    let mut printer = TablePrinter::new();
+    if !cli.columns.is_empty() {
+        printer = printer.columns(&cli.columns);
+    }
+    if let Some(width) = cli.max_title_width {
+        printer = printer.max_title_width(width);
+    }
+    if cli.wrap_titles {
+        printer = printer.title_overflow(TitleOverflow::Wrap);
+    }
+    printer
+}
+
+/// Prints windows as a JSON array.
+fn print_json(windows: &[&WindowInfo]) {
+    let values: Vec<serde_json::Value> = windows
+        .iter()
+        .map(|window| {
+            serde_json::json!({
+                "index": window.index,
+                "hwnd": window.hwnd.as_raw(),
+                "owner_hwnd": window.owner_hwnd.map(|owner| owner.as_raw()),
+                "pid": window.pid,
+                "title": window.title,
+                "class_name": window.class_name,
+                "process_name": window.process_name,
+                "process_file": window.process_file.to_string_lossy(),
+                "process_device_path": window.process_device_path.to_string_lossy(),
+                "process_command_line": window.process_command_line,
+                "aumid": window.aumid,
+                "process_info_error": window.process_info_error.as_ref().map(ToString::to_string),
+                "position": {
+                    "x": window.position.x,
+                    "y": window.position.y,
+                    "width": window.position.width,
+                    "height": window.position.height,
+                },
+                "monitor_index": window.monitor_index,
+                "is_cloaked": window.is_cloaked,
+                "is_hung": window.is_hung,
+                "is_fullscreen": window.is_fullscreen(),
+                "ghost_of": window.ghost_of.map(|hung| hung.as_raw()),
+                "console_client_pid": window.console_client_pid,
+                "is_elevated": window.is_elevated,
+                "integrity_level": window.integrity_level.map(|level| format!("{:?}", level)),
+                "process_arch": window.process_arch.map(|arch| arch.to_string()),
+                "user_name": window.user_name,
+                "session_id": window.session_id,
+                "product_name": window.product_name,
+                "company_name": window.company_name,
+                "file_version": window.file_version,
+                "signature_status": window.signature_status.map(|status| format!("{:?}", status)),
+                "publisher": window.publisher,
+                "parent_pid": window.parent_pid,
+                "parent_chain": window.parent_chain,
+                "process_metrics": window.process_metrics.map(|metrics| serde_json::json!({
+                    "working_set": metrics.working_set,
+                    "private_bytes": metrics.private_bytes,
+                    "cpu_time_ms": metrics.cpu_time.as_millis() as u64,
+                    "handle_count": metrics.handle_count,
+                    "thread_count": metrics.thread_count,
+                    "start_time": metrics.start_time
+                        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|since| since.as_secs()),
+                })),
+                "kind": format!("{:?}", window.kind),
+            })
+        })
+        .collect();
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&values).unwrap_or_else(|_| "[]".to_string())
+    );
+}
@@ -1,97 +1,2518 @@
-use crate::types::WindowInfo;
+#[cfg(feature = "sorting")]
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::Ordering;
+
+use crate::types::{
+    CancellationToken, ClassDetails, ControlKind, FilterCriteria, IntegrityLevel, KnownWindowClass,
+    Layout, LayoutEngine, MatchMode, ProcessArch, ScoreCriteria, StringFilter, TreeRelation,
+    WindowEvent, WindowHandle, WindowInfo, WindowKind, WindowList, WindowPosition, WindowQuery,
+    WindowState, WindowTree, WindowTreeNode,
+};
+
+#[cfg(feature = "sorting")]
+#[allow(deprecated)]
+use crate::types::{PositionSort, SortCriteria, SortKey, SortKeys, SortOrder};
+
+#[cfg(feature = "selection")]
+use crate::types::{Selection, SelectionIndex, SelectionRange};
+
+use crate::utils::fold_case;
+
+#[cfg(feature = "sorting")]
+use crate::utils::{locale_cmp, matches_criteria, natural_cmp};
+
+impl WindowHandle {
+    /// Creates a handle from a raw HWND value.
+    pub const fn new(raw: isize) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw HWND value.
+    pub const fn as_raw(self) -> isize {
+        self.0
+    }
+
+    /// Returns `true` if this is the null handle.
+    pub const fn is_null(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checks if the handle still refers to an existing window.
+    #[cfg(feature = "windows")]
+    pub fn is_valid(self) -> bool {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::IsWindow;
+
+        !self.is_null() && unsafe { IsWindow(HWND::from(self)).as_bool() }
+    }
+}
+
+impl fmt::Display for WindowHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:x}", self.0)
+    }
+}
+
+impl fmt::LowerHex for WindowHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::UpperHex for WindowHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl From<isize> for WindowHandle {
+    fn from(raw: isize) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<WindowHandle> for isize {
+    fn from(handle: WindowHandle) -> Self {
+        handle.0
+    }
+}
+
+#[cfg(feature = "windows")]
+impl From<windows::Win32::Foundation::HWND> for WindowHandle {
+    fn from(hwnd: windows::Win32::Foundation::HWND) -> Self {
+        Self(hwnd.0)
+    }
+}
+
+#[cfg(feature = "windows")]
+impl From<WindowHandle> for windows::Win32::Foundation::HWND {
+    fn from(handle: WindowHandle) -> Self {
+        Self(handle.0)
+    }
+}
+
+/// Geometry helpers for [`WindowPosition`].
+impl WindowPosition {
+    /// Returns `true` if this rectangle overlaps `other` by at least one pixel.
+    ///
+    /// Rectangles that merely touch along an edge do not intersect.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::WindowPosition;
+    ///
+    /// let a = WindowPosition { x: 0, y: 0, width: 100, height: 100 };
+    /// let b = WindowPosition { x: 50, y: 50, width: 100, height: 100 };
+    /// let c = WindowPosition { x: 100, y: 0, width: 10, height: 10 };
+    /// assert!(a.intersects(&b));
+    /// assert!(!a.intersects(&c));
+    /// ```
+    pub fn intersects(&self, other: &WindowPosition) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+impl WindowEvent {
+    /// Returns the window the event is about.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowEvent, WindowHandle};
+    ///
+    /// let event = WindowEvent::TitleChanged {
+    ///     hwnd: WindowHandle(0x1234),
+    ///     title: "Untitled - Notepad".to_string(),
+    /// };
+    /// assert_eq!(event.hwnd(), WindowHandle(0x1234));
+    /// ```
+    pub fn hwnd(&self) -> WindowHandle {
+        match self {
+            WindowEvent::Created { hwnd }
+            | WindowEvent::Destroyed { hwnd }
+            | WindowEvent::TitleChanged { hwnd, .. }
+            | WindowEvent::Moved { hwnd, .. }
+            | WindowEvent::MoveSizeEnded { hwnd, .. }
+            | WindowEvent::StateChanged { hwnd, .. }
+            | WindowEvent::Foreground { hwnd } => *hwnd,
+        }
+    }
+}
+
+impl FilterCriteria {
+    /// Creates a [`FilterBuilder`] for constructing criteria with chainable methods.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::FilterCriteria;
+    ///
+    /// let criteria = FilterCriteria::builder()
+    ///     .title_contains("Chrome")
+    ///     .pid(1234)
+    ///     .build();
+    /// assert_eq!(criteria.pid, Some(1234));
+    /// assert_eq!(criteria.title_contains.as_deref(), Some("Chrome"));
+    /// ```
+    pub fn builder() -> FilterBuilder {
+        FilterBuilder::default()
+    }
+}
+
+impl KnownWindowClass {
+    /// All known window classes.
+    pub const ALL: [KnownWindowClass; 18] = [
+        KnownWindowClass::ConsoleWindow,
+        KnownWindowClass::WindowsTerminal,
+        KnownWindowClass::ExplorerCabinet,
+        KnownWindowClass::ChromeWidget,
+        KnownWindowClass::Firefox,
+        KnownWindowClass::ApplicationFrame,
+        KnownWindowClass::CoreWindow,
+        KnownWindowClass::ShellTray,
+        KnownWindowClass::SecondaryShellTray,
+        KnownWindowClass::Progman,
+        KnownWindowClass::WorkerW,
+        KnownWindowClass::Dialog,
+        KnownWindowClass::Menu,
+        KnownWindowClass::Tooltip,
+        KnownWindowClass::Notepad,
+        KnownWindowClass::Ime,
+        KnownWindowClass::NotifyIconOverflow,
+        KnownWindowClass::Ghost,
+    ];
+
+    /// Returns the window class name registered for this class.
+    pub fn class_name(self) -> &'static str {
+        match self {
+            KnownWindowClass::ConsoleWindow => "ConsoleWindowClass",
+            KnownWindowClass::WindowsTerminal => "CASCADIA_HOSTING_WINDOW_CLASS",
+            KnownWindowClass::ExplorerCabinet => "CabinetWClass",
+            KnownWindowClass::ChromeWidget => "Chrome_WidgetWin_1",
+            KnownWindowClass::Firefox => "MozillaWindowClass",
+            KnownWindowClass::ApplicationFrame => "ApplicationFrameWindow",
+            KnownWindowClass::CoreWindow => "Windows.UI.Core.CoreWindow",
+            KnownWindowClass::ShellTray => "Shell_TrayWnd",
+            KnownWindowClass::SecondaryShellTray => "Shell_SecondaryTrayWnd",
+            KnownWindowClass::Progman => "Progman",
+            KnownWindowClass::WorkerW => "WorkerW",
+            KnownWindowClass::Dialog => "#32770",
+            KnownWindowClass::Menu => "#32768",
+            KnownWindowClass::Tooltip => "tooltips_class32",
+            KnownWindowClass::Notepad => "Notepad",
+            KnownWindowClass::Ime => "IME",
+            KnownWindowClass::NotifyIconOverflow => "NotifyIconOverflowWindow",
+            KnownWindowClass::Ghost => "Ghost",
+        }
+    }
+
+    /// Looks up the known class with the given class name.
+    ///
+    /// Window class names are case-insensitive, so the lookup is too.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::KnownWindowClass;
+    ///
+    /// assert_eq!(
+    ///     KnownWindowClass::from_class_name("shell_traywnd"),
+    ///     Some(KnownWindowClass::ShellTray)
+    /// );
+    /// assert_eq!(KnownWindowClass::from_class_name("MyAppWindow"), None);
+    /// ```
+    pub fn from_class_name(class_name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|known| known.matches(class_name))
+    }
+
+    /// Checks whether `class_name` is this class.
+    pub fn matches(self, class_name: &str) -> bool {
+        self.class_name().eq_ignore_ascii_case(class_name)
+    }
+}
+
+impl fmt::Display for KnownWindowClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.class_name())
+    }
+}
+
+impl ClassDetails {
+    /// Names the class style flags ([`style`](Self::style)), listing
+    /// unknown bits as one hex value at the end.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::ClassDetails;
+    ///
+    /// let class = ClassDetails {
+    ///     style: 0x0000_000B,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(class.style_names(), ["CS_VREDRAW", "CS_HREDRAW", "CS_DBLCLKS"]);
+    /// ```
+    pub fn style_names(&self) -> Vec<String> {
+        flag_names(self.style, &CLASS_STYLE_NAMES)
+    }
+}
+
+impl ControlKind {
+    /// Recognizes a standard control by its window class name.
+    ///
+    /// Besides the system classes, rich edit controls and the
+    /// Windows Forms wrappers around standard controls (e.g.
+    /// `WindowsForms10.EDIT.app.0.2bf8098_r6_ad1`) are recognized.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::ControlKind;
+    ///
+    /// assert_eq!(ControlKind::from_class_name("Edit"), Some(ControlKind::Edit));
+    /// assert_eq!(ControlKind::from_class_name("RichEdit20W"), Some(ControlKind::Edit));
+    /// assert_eq!(
+    ///     ControlKind::from_class_name("WindowsForms10.STATIC.app.0.2bf8098_r6_ad1"),
+    ///     Some(ControlKind::Static)
+    /// );
+    /// assert_eq!(ControlKind::from_class_name("SysListView32"), None);
+    /// ```
+    pub fn from_class_name(class_name: &str) -> Option<Self> {
+        let class_name = class_name.to_ascii_lowercase();
+        let base = class_name
+            .strip_prefix("windowsforms10.")
+            .and_then(|rest| rest.split('.').next())
+            .unwrap_or(&class_name);
+        match base {
+            "edit" => Some(ControlKind::Edit),
+            "static" => Some(ControlKind::Static),
+            "button" => Some(ControlKind::Button),
+            "listbox" => Some(ControlKind::ListBox),
+            "combobox" => Some(ControlKind::ComboBox),
+            _ if base.starts_with("richedit") => Some(ControlKind::Edit),
+            _ => None,
+        }
+    }
+}
+
+impl IntegrityLevel {
+    /// Converts the relative identifier of an integrity level SID
+    /// (`SECURITY_MANDATORY_*_RID`) into a level.
+    ///
+    /// Values between the well-known identifiers round down.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::IntegrityLevel;
+    ///
+    /// assert_eq!(IntegrityLevel::from_rid(0x2000), IntegrityLevel::Medium);
+    /// assert_eq!(IntegrityLevel::from_rid(0x3000), IntegrityLevel::High);
+    /// assert!(IntegrityLevel::High > IntegrityLevel::Medium);
+    /// ```
+    pub fn from_rid(rid: u32) -> Self {
+        match rid {
+            0x5000.. => IntegrityLevel::Protected,
+            0x4000.. => IntegrityLevel::System,
+            0x3000.. => IntegrityLevel::High,
+            0x2100.. => IntegrityLevel::MediumPlus,
+            0x2000.. => IntegrityLevel::Medium,
+            0x1000.. => IntegrityLevel::Low,
+            _ => IntegrityLevel::Untrusted,
+        }
+    }
+}
+
+impl ProcessArch {
+    /// The architecture of the current process.
+    pub fn current() -> Self {
+        if cfg!(target_arch = "x86_64") {
+            ProcessArch::X64
+        } else if cfg!(target_arch = "aarch64") {
+            ProcessArch::Arm64
+        } else if cfg!(target_arch = "arm") {
+            ProcessArch::Arm
+        } else {
+            ProcessArch::X86
+        }
+    }
+
+    /// Parses an architecture name: `x86`, `x64` (or `amd64`), `arm`, or
+    /// `arm64`, case-insensitively.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::ProcessArch;
+    ///
+    /// assert_eq!(ProcessArch::from_name("AMD64"), Some(ProcessArch::X64));
+    /// assert_eq!(ProcessArch::from_name("mips"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "x86" | "i386" => Some(ProcessArch::X86),
+            "x64" | "amd64" | "x86_64" => Some(ProcessArch::X64),
+            "arm" => Some(ProcessArch::Arm),
+            "arm64" | "aarch64" => Some(ProcessArch::Arm64),
+            _ => None,
+        }
+    }
+
+    /// Whether pointers are 64 bits wide in this architecture.
+    pub fn is_64_bit(self) -> bool {
+        matches!(self, ProcessArch::X64 | ProcessArch::Arm64)
+    }
+}
+
+impl fmt::Display for ProcessArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProcessArch::X86 => "x86",
+            ProcessArch::X64 => "x64",
+            ProcessArch::Arm => "arm",
+            ProcessArch::Arm64 => "arm64",
+        })
+    }
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// let shared = token.clone();
+    /// assert!(!token.is_cancelled());
+    /// shared.cancel();
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of every enumeration using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl WindowKind {
+    /// Returns `true` for windows of the desktop shell or the system rather
+    /// than of an application.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::WindowKind;
+    ///
+    /// assert!(WindowKind::Taskbar.is_system());
+    /// assert!(!WindowKind::AppWindow.is_system());
+    /// ```
+    pub fn is_system(self) -> bool {
+        !matches!(self, WindowKind::AppWindow | WindowKind::Other)
+    }
+}
+
+impl LayoutEngine {
+    /// Creates an engine arranging windows within `area`, without gaps.
+    pub fn new(area: WindowPosition) -> Self {
+        Self { area, gap: 0 }
+    }
+
+    /// Leaves `gap` pixels between neighbouring windows.
+    ///
+    /// Negative gaps are treated as zero.
+    pub fn with_gap(mut self, gap: i32) -> Self {
+        self.gap = gap.max(0);
+        self
+    }
+
+    /// Computes the bounds of `count` windows arranged by `layout`.
+    ///
+    /// An [`Explicit`](Layout::Explicit) layout yields at most as many
+    /// bounds as it lists.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{Layout, LayoutEngine, WindowPosition};
+    ///
+    /// let engine = LayoutEngine::new(WindowPosition { x: 0, y: 0, width: 1000, height: 600 });
+    /// let cells = engine.compute(&Layout::Grid { columns: None }, 4);
+    /// assert_eq!(cells[3], WindowPosition { x: 500, y: 300, width: 500, height: 300 });
+    ///
+    /// let columns = engine.with_gap(10).compute(&Layout::Columns, 2);
+    /// assert_eq!(columns[1], WindowPosition { x: 505, y: 0, width: 495, height: 600 });
+    ///
+    /// // The fifth window would stick out, so it starts over at the corner
+    /// let cascade = engine.compute(&Layout::Cascade { offset: 100 }, 5);
+    /// assert_eq!(cascade[4], WindowPosition { x: 0, y: 0, width: 600, height: 300 });
+    /// ```
+    pub fn compute(&self, layout: &Layout, count: usize) -> Vec<WindowPosition> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        match layout {
+            Layout::Grid { columns } => {
+                let columns = columns
+                    .unwrap_or_else(|| (count as f64).sqrt().ceil() as usize)
+                    .clamp(1, count);
+                let rows = count.div_ceil(columns);
+                (0..count)
+                    .map(|i| self.cell(i % columns, columns, i / columns, rows))
+                    .collect()
+            }
+            Layout::Columns => (0..count).map(|i| self.cell(i, count, 0, 1)).collect(),
+            Layout::Rows => (0..count).map(|i| self.cell(0, 1, i, count)).collect(),
+            Layout::Cascade { offset } => {
+                // Keep every window at least half the size of the area
+                let offset = (*offset).max(0);
+                let steps = i32::try_from(count - 1).unwrap_or(i32::MAX);
+                let shift = offset.saturating_mul(steps);
+                let width = self
+                    .area
+                    .width
+                    .saturating_sub(shift)
+                    .max(self.area.width / 2);
+                let height = self
+                    .area
+                    .height
+                    .saturating_sub(shift)
+                    .max(self.area.height / 2);
+
+                // Start over from the corner once a window would leave the area
+                let fits = |length: i32, size: i32| match offset {
+                    0 => i32::MAX,
+                    _ => (length.saturating_sub(size) / offset)
+                        .saturating_add(1)
+                        .max(1),
+                };
+                let positions = fits(self.area.width, width).min(fits(self.area.height, height));
+                (0..count)
+                    .map(|i| {
+                        let shift = offset.saturating_mul(i as i32 % positions);
+                        WindowPosition {
+                            x: self.area.x.saturating_add(shift),
+                            y: self.area.y.saturating_add(shift),
+                            width,
+                            height,
+                        }
+                    })
+                    .collect()
+            }
+            Layout::Explicit(positions) => positions.iter().take(count).copied().collect(),
+        }
+    }
+
+    /// Returns the bounds of one cell of an evenly divided area, splitting
+    /// the gaps between neighbouring cells.
+    ///
+    /// Cells are at least one pixel in size, even when the gaps leave no
+    /// room for them.
+    fn cell(&self, column: usize, columns: usize, row: usize, rows: usize) -> WindowPosition {
+        let span = |start: i32, length: i32, index: usize, parts: usize| {
+            // Widened so that large areas, gaps, and counts cannot overflow
+            let (index, parts, gap) = (index as i64, parts as i64, i64::from(self.gap));
+            let room = (i64::from(length) - gap * (parts - 1)).max(0);
+            let from = room * index / parts;
+            let to = room * (index + 1) / parts;
+            let start = i64::from(start) + from + gap * index;
+            let clamp = |value: i64| value.clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+            (clamp(start), clamp(to - from).max(1))
+        };
+        let (x, width) = span(self.area.x, self.area.width, column, columns);
+        let (y, height) = span(self.area.y, self.area.height, row, rows);
+        WindowPosition {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+impl ScoreCriteria {
+    /// Ranks windows by their score, best candidate first.
+    ///
+    /// `windows` must be in Z-order, topmost first, as enumerated, for the
+    /// recency bonus to be meaningful. Windows that are no candidates are
+    /// left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{ScoreCriteria, WindowHandle, WindowInfo};
+    ///
+    /// let criteria = ScoreCriteria {
+    ///     title: Some("Report.docx - Word".to_string()),
+    ///     process_name: Some("winword".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let windows = vec![
+    ///     WindowInfo {
+    ///         hwnd: WindowHandle(1),
+    ///         title: "Report.docx - Word".to_string(),
+    ///         process_name: "WINWORD.EXE".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     WindowInfo {
+    ///         hwnd: WindowHandle(2),
+    ///         title: "Word".to_string(),
+    ///         process_name: "WINWORD.EXE".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     WindowInfo {
+    ///         hwnd: WindowHandle(3),
+    ///         title: "Notepad".to_string(),
+    ///         ..Default::default()
+    ///     },
+    /// ];
+    ///
+    /// let ranked = criteria.rank(&windows, Some(WindowHandle(2)));
+    /// assert_eq!(ranked.len(), 2);
+    /// assert_eq!(ranked[0].0.hwnd, WindowHandle(1));
+    /// assert!(ranked[0].1 > ranked[1].1);
+    /// ```
+    pub fn rank(
+        &self,
+        windows: &[WindowInfo],
+        foreground: Option<WindowHandle>,
+    ) -> Vec<(WindowInfo, f32)> {
+        let weights = &self.weights;
+        let targets = [
+            (&self.title, weights.title_exact, weights.title_contains),
+            (
+                &self.process_name,
+                weights.process_exact,
+                weights.process_contains,
+            ),
+            (
+                &self.class_name,
+                weights.class_exact,
+                weights.class_contains,
+            ),
+        ]
+        .map(|(target, exact, contains)| (target.as_deref().map(fold_case), exact, contains));
+        let has_targets = targets.iter().any(|(target, _, _)| target.is_some());
+
+        let mut ranked: Vec<(WindowInfo, f32)> = windows
+            .iter()
+            .enumerate()
+            .filter_map(|(position, window)| {
+                let values = [&window.title, &window.process_name, &window.class_name];
+                let mut score = 0.0;
+                let mut matched = false;
+                for ((target, exact, contains), value) in targets.iter().zip(values) {
+                    let Some(target) = target else { continue };
+                    let value = fold_case(value);
+                    if value == *target {
+                        score += exact;
+                        matched = true;
+                    } else if !target.is_empty() && value.contains(target.as_str()) {
+                        score += contains;
+                        matched = true;
+                    }
+                }
+                if has_targets && !matched {
+                    return None;
+                }
+
+                if foreground == Some(window.hwnd) {
+                    score += weights.foreground;
+                }
+                score += weights.recency * (1.0 - position as f32 / windows.len() as f32);
+                if window.kind == WindowKind::AppWindow {
+                    score += weights.app_window;
+                }
+                Some((window.clone(), score))
+            })
+            .collect();
+
+        ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        ranked
+    }
+}
+
+impl StringFilter {
+    /// Creates a case-insensitive filter matching `pattern` in the given mode.
+    pub fn new(pattern: impl Into<String>, mode: MatchMode) -> Self {
+        Self {
+            pattern: pattern.into(),
+            mode,
+            case_sensitive: false,
+        }
+    }
+
+    /// Creates a filter matching values that contain `pattern`.
+    pub fn contains(pattern: impl Into<String>) -> Self {
+        Self::new(pattern, MatchMode::Contains)
+    }
+
+    /// Creates a filter matching values equal to `pattern`.
+    pub fn exact(pattern: impl Into<String>) -> Self {
+        Self::new(pattern, MatchMode::Exact)
+    }
+
+    /// Creates a filter matching values against the glob `pattern`.
+    pub fn glob(pattern: impl Into<String>) -> Self {
+        Self::new(pattern, MatchMode::Glob)
+    }
+
+    /// Makes the filter distinguish letter case.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::StringFilter;
+    ///
+    /// let filter = StringFilter::exact("Edit").case_sensitive();
+    /// assert!(filter.matches("Edit"));
+    /// assert!(!filter.matches("EDIT"));
+    /// ```
+    pub fn case_sensitive(mut self) -> Self {
+        self.case_sensitive = true;
+        self
+    }
+
+    /// Checks whether `value` matches the filter.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::StringFilter;
+    ///
+    /// assert!(StringFilter::contains("note").matches("Notepad"));
+    /// assert!(!StringFilter::exact("Edit").matches("RichEdit20W"));
+    /// assert!(StringFilter::glob("*- Notepad").matches("todo.txt - Notepad"));
+    /// assert!(StringFilter::glob("Chrome_WidgetWin_?").matches("chrome_widgetwin_1"));
+    /// ```
+    pub fn matches(&self, value: &str) -> bool {
+        if self.case_sensitive {
+            return Self::matches_mode(self.mode, &self.pattern, value);
+        }
+        Self::matches_mode(self.mode, &fold_case(&self.pattern), &fold_case(value))
+    }
+
+    /// Matches `value` against `pattern` in the given mode, respecting case.
+    fn matches_mode(mode: MatchMode, pattern: &str, value: &str) -> bool {
+        match mode {
+            MatchMode::Contains => value.contains(pattern),
+            MatchMode::Exact => value == pattern,
+            MatchMode::Glob => crate::utils::glob_match(pattern, value),
+        }
+    }
+}
+
+/// A chainable builder for [`FilterCriteria`].
+///
+/// Every method sets a single criterion; criteria that are never set stay
+/// unconstrained.
+#[derive(Debug, Clone, Default)]
+pub struct FilterBuilder {
+    criteria: FilterCriteria,
+}
+
+impl FilterBuilder {
+    /// Creates a builder with no criteria set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches windows owned by the given process ID.
+    pub fn pid(mut self, pid: u32) -> Self {
+        self.criteria.pid = Some(pid);
+        self
+    }
+
+    /// Matches windows owned by any of the given process IDs.
+    pub fn pids(mut self, pids: impl IntoIterator<Item = u32>) -> Self {
+        self.criteria.pids = Some(pids.into_iter().collect());
+        self
+    }
+
+    /// Excludes windows owned by any of the given process IDs.
+    pub fn exclude_pids(mut self, pids: impl IntoIterator<Item = u32>) -> Self {
+        self.criteria.exclude_pids = Some(pids.into_iter().collect());
+        self
+    }
+
+    /// Matches windows created by the given thread.
+    pub fn thread_id(mut self, thread_id: u32) -> Self {
+        self.criteria.thread_id = Some(thread_id);
+        self
+    }
+
+    /// Matches windows whose process runs in the given session.
+    ///
+    /// Combine it with `WindowEnumerator::current_session_id` to keep only
+    /// the windows of the current user's session on a terminal server.
+    pub fn session_id(mut self, session_id: u32) -> Self {
+        self.criteria.session_id = Some(session_id);
+        self
+    }
+
+    /// Matches windows whose title contains `value` (case-insensitive).
+    pub fn title_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.title_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose class name contains `value` (case-insensitive).
+    pub fn class_name_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.class_name_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose process name contains `value` (case-insensitive).
+    pub fn process_name_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.process_name_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose process file path contains `value` (case-insensitive).
+    pub fn process_file_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.process_file_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows of a well-known class.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, KnownWindowClass, WindowInfo};
+    ///
+    /// let criteria = FilterCriteria::builder()
+    ///     .known_class(KnownWindowClass::ExplorerCabinet)
+    ///     .build();
+    /// let window = WindowInfo {
+    ///     class_name: "CabinetWClass".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(utils::matches_criteria(&window, &criteria));
+    /// ```
+    pub fn known_class(mut self, class: KnownWindowClass) -> Self {
+        self.criteria.known_class = Some(class);
+        self
+    }
+
+    /// Matches windows whose process is (`true`) or is not (`false`) elevated.
+    pub fn elevated(mut self, elevated: bool) -> Self {
+        self.criteria.elevated = Some(elevated);
+        self
+    }
+
+    /// Matches windows whose executable has (`true`) or lacks (`false`) a
+    /// valid signature.
+    ///
+    /// Signatures are only verified with the `signing` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, SignatureStatus, WindowInfo};
+    ///
+    /// let unsigned = FilterCriteria::builder().signed(false).build();
+    /// let window = WindowInfo {
+    ///     signature_status: Some(SignatureStatus::NotSigned),
+    ///     ..Default::default()
+    /// };
+    /// assert!(utils::matches_criteria(&window, &unsigned));
+    /// assert!(!utils::matches_criteria(&WindowInfo::default(), &unsigned));
+    /// ```
+    pub fn signed(mut self, signed: bool) -> Self {
+        self.criteria.signed = Some(signed);
+        self
+    }
+
+    /// Matches windows whose process integrity level is at most `level`.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, IntegrityLevel, WindowInfo};
+    ///
+    /// let criteria = FilterCriteria::builder()
+    ///     .max_integrity_level(IntegrityLevel::Medium)
+    ///     .build();
+    /// let admin_console = WindowInfo {
+    ///     integrity_level: Some(IntegrityLevel::High),
+    ///     ..Default::default()
+    /// };
+    /// assert!(!utils::matches_criteria(&admin_console, &criteria));
+    /// ```
+    pub fn max_integrity_level(mut self, level: IntegrityLevel) -> Self {
+        self.criteria.max_integrity_level = Some(level);
+        self
+    }
+
+    /// Matches windows whose process runs as `arch`.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, ProcessArch, WindowInfo};
+    ///
+    /// let same_bitness = FilterCriteria::builder()
+    ///     .process_arch(ProcessArch::current())
+    ///     .build();
+    /// let window = WindowInfo {
+    ///     process_arch: Some(ProcessArch::current()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(utils::matches_criteria(&window, &same_bitness));
+    /// assert!(!utils::matches_criteria(&WindowInfo::default(), &same_bitness));
+    /// ```
+    pub fn process_arch(mut self, arch: ProcessArch) -> Self {
+        self.criteria.process_arch = Some(arch);
+        self
+    }
+
+    /// Excludes desktop shell and system windows (see [`WindowKind::is_system`]).
+    pub fn exclude_system_windows(mut self) -> Self {
+        self.criteria.exclude_system_windows = true;
+        self
+    }
+
+    /// Matches windows whose title matches `filter`.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, StringFilter, WindowInfo};
+    ///
+    /// let criteria = FilterCriteria::builder()
+    ///     .title_matches(StringFilter::glob("* - Notepad"))
+    ///     .build();
+    /// let window = WindowInfo {
+    ///     title: "notes.txt - Notepad".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(utils::matches_criteria(&window, &criteria));
+    /// ```
+    pub fn title_matches(mut self, filter: StringFilter) -> Self {
+        self.criteria.title_matches = Some(filter);
+        self
+    }
+
+    /// Matches windows whose class name matches `filter`.
+    pub fn class_name_matches(mut self, filter: StringFilter) -> Self {
+        self.criteria.class_name_matches = Some(filter);
+        self
+    }
+
+    /// Matches windows whose process name matches `filter`.
+    pub fn process_name_matches(mut self, filter: StringFilter) -> Self {
+        self.criteria.process_name_matches = Some(filter);
+        self
+    }
+
+    /// Matches windows whose process file path matches `filter`.
+    pub fn process_file_matches(mut self, filter: StringFilter) -> Self {
+        self.criteria.process_file_matches = Some(filter);
+        self
+    }
+
+    /// Matches windows whose process command line matches `filter`.
+    pub fn command_line_matches(mut self, filter: StringFilter) -> Self {
+        self.criteria.command_line_matches = Some(filter);
+        self
+    }
+
+    /// Excludes windows whose title contains `value` (case-insensitive).
+    pub fn title_not_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.title_not_contains = Some(value.into());
+        self
+    }
+
+    /// Excludes windows whose class name contains `value` (case-insensitive).
+    pub fn class_name_not_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.class_name_not_contains = Some(value.into());
+        self
+    }
+
+    /// Excludes windows whose process name contains `value` (case-insensitive).
+    pub fn process_name_not_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.process_name_not_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose AUMID contains `value` (case-insensitive).
+    pub fn aumid_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.aumid_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose owning account contains `value` (case-insensitive).
+    ///
+    /// User names are only resolved when enumerating with
+    /// [`EnumerationOptions::include_user_name`](crate::EnumerationOptions::include_user_name).
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, WindowInfo};
+    ///
+    /// let criteria = FilterCriteria::builder().user_contains("alice").build();
+    /// let window = WindowInfo {
+    ///     user_name: Some(r"CONTOSO\Alice".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(utils::matches_criteria(&window, &criteria));
+    /// assert!(!utils::matches_criteria(&WindowInfo::default(), &criteria));
+    /// ```
+    pub fn user_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.user_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose executable's product name contains `value`
+    /// (case-insensitive).
+    ///
+    /// Product names are only read when enumerating with
+    /// [`EnumerationOptions::include_version_info`](crate::EnumerationOptions::include_version_info).
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, WindowInfo};
+    ///
+    /// let criteria = FilterCriteria::builder().product_name_contains("office").build();
+    /// let window = WindowInfo {
+    ///     product_name: Some("Microsoft Office".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(utils::matches_criteria(&window, &criteria));
+    /// assert!(!utils::matches_criteria(&WindowInfo::default(), &criteria));
+    /// ```
+    pub fn product_name_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.product_name_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose process was started, directly or indirectly,
+    /// by a process whose executable name contains `value`
+    /// (case-insensitive).
+    ///
+    /// Ancestors are only resolved when enumerating with
+    /// [`EnumerationOptions::include_parent_chain`](crate::EnumerationOptions::include_parent_chain).
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils, FilterCriteria, WindowInfo};
+    ///
+    /// let criteria = FilterCriteria::builder().spawned_by_contains("launcher").build();
+    /// let window = WindowInfo {
+    ///     parent_chain: Some(vec!["explorer.exe".to_string(), "Launcher.exe".to_string()]),
+    ///     ..Default::default()
+    /// };
+    /// assert!(utils::matches_criteria(&window, &criteria));
+    /// assert!(!utils::matches_criteria(&WindowInfo::default(), &criteria));
+    /// ```
+    pub fn spawned_by_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.spawned_by_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows whose process command line contains `value` (case-insensitive).
+    pub fn command_line_contains(mut self, value: impl Into<String>) -> Self {
+        self.criteria.command_line_contains = Some(value.into());
+        self
+    }
+
+    /// Matches windows at least `width` pixels wide.
+    pub fn min_width(mut self, width: i32) -> Self {
+        self.criteria.min_width = Some(width);
+        self
+    }
+
+    /// Matches windows at least `height` pixels tall.
+    pub fn min_height(mut self, height: i32) -> Self {
+        self.criteria.min_height = Some(height);
+        self
+    }
+
+    /// Matches windows at most `width` pixels wide.
+    pub fn max_width(mut self, width: i32) -> Self {
+        self.criteria.max_width = Some(width);
+        self
+    }
+
+    /// Matches windows at most `height` pixels tall.
+    pub fn max_height(mut self, height: i32) -> Self {
+        self.criteria.max_height = Some(height);
+        self
+    }
+
+    /// Matches windows overlapping the given screen rectangle.
+    pub fn intersects_rect(mut self, rect: WindowPosition) -> Self {
+        self.criteria.intersects_rect = Some(rect);
+        self
+    }
+
+    /// Matches windows on the monitor with the given 1-based index.
+    pub fn monitor_index(mut self, index: usize) -> Self {
+        self.criteria.monitor_index = Some(index);
+        self
+    }
+
+    /// Excludes tool windows (`WS_EX_TOOLWINDOW`).
+    pub fn exclude_tool_windows(mut self) -> Self {
+        self.criteria.exclude_tool_windows = true;
+        self
+    }
+
+    /// Excludes windows cloaked by DWM.
+    pub fn exclude_cloaked(mut self) -> Self {
+        self.criteria.exclude_cloaked = true;
+        self
+    }
+
+    /// Excludes ghost windows shown in place of hung windows.
+    pub fn exclude_ghost_windows(mut self) -> Self {
+        self.criteria.exclude_ghost_windows = true;
+        self
+    }
+
+    /// Keeps only windows that are (or, with `false`, are not) fullscreen.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::FilterCriteria;
+    ///
+    /// // Is a fullscreen app running? Check before showing a notification.
+    /// let criteria = FilterCriteria::builder().fullscreen(true).build();
+    /// assert_eq!(criteria.fullscreen, Some(true));
+    /// ```
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.criteria.fullscreen = Some(fullscreen);
+        self
+    }
+
+    /// Keeps only windows that are (or, with `false`, are not) always-on-top.
+    pub fn topmost(mut self, topmost: bool) -> Self {
+        self.criteria.topmost = Some(topmost);
+        self
+    }
+
+    /// Keeps only windows that are responding.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{utils::matches_criteria, FilterCriteria, WindowInfo};
+    ///
+    /// let frozen = WindowInfo { is_hung: true, ..Default::default() };
+    /// let responding = FilterCriteria::builder().responding_only().build();
+    /// let hung = FilterCriteria::builder().hung_only().build();
+    /// assert!(!matches_criteria(&frozen, &responding));
+    /// assert!(matches_criteria(&frozen, &hung));
+    /// ```
+    pub fn responding_only(mut self) -> Self {
+        self.criteria.responding_only = true;
+        self
+    }
+
+    /// Keeps only windows that are not responding.
+    pub fn hung_only(mut self) -> Self {
+        self.criteria.hung_only = true;
+        self
+    }
+
+    /// Finishes building and returns the [`FilterCriteria`].
+    pub fn build(self) -> FilterCriteria {
+        self.criteria
+    }
+}
+
+pub(crate) const WS_EX_TOPMOST: u32 = 0x0000_0008;
+const WS_EX_TOOLWINDOW: u32 = 0x0000_0080;
+const WS_EX_APPWINDOW: u32 = 0x0004_0000;
+const WS_EX_LAYERED: u32 = 0x0008_0000;
+const WS_CAPTION: u32 = 0x00C0_0000;
+pub(crate) const WS_MAXIMIZE: u32 = 0x0100_0000;
+
+/// The window style flags with their names, from WinUser.h.
+///
+/// `WS_CAPTION` combines `WS_BORDER` and `WS_DLGFRAME`, and the minimize
+/// and maximize box bits double as `WS_GROUP` and `WS_TABSTOP` in child
+/// windows, so they are decoded separately.
+const STYLE_NAMES: [(u32, &str); 12] = [
+    (0x8000_0000, "WS_POPUP"),
+    (0x4000_0000, "WS_CHILD"),
+    (0x2000_0000, "WS_MINIMIZE"),
+    (0x1000_0000, "WS_VISIBLE"),
+    (0x0800_0000, "WS_DISABLED"),
+    (0x0400_0000, "WS_CLIPSIBLINGS"),
+    (0x0200_0000, "WS_CLIPCHILDREN"),
+    (0x0100_0000, "WS_MAXIMIZE"),
+    (0x0020_0000, "WS_VSCROLL"),
+    (0x0010_0000, "WS_HSCROLL"),
+    (0x0008_0000, "WS_SYSMENU"),
+    (0x0004_0000, "WS_THICKFRAME"),
+];
+const WS_CHILD: u32 = 0x4000_0000;
+const WS_BORDER: u32 = 0x0080_0000;
+const WS_DLGFRAME: u32 = 0x0040_0000;
+const WS_MINIMIZEBOX: u32 = 0x0002_0000;
+const WS_MAXIMIZEBOX: u32 = 0x0001_0000;
+
+/// The extended window style flags with their names, from WinUser.h.
+const EX_STYLE_NAMES: [(u32, &str); 22] = [
+    (0x0000_0001, "WS_EX_DLGMODALFRAME"),
+    (0x0000_0004, "WS_EX_NOPARENTNOTIFY"),
+    (0x0000_0008, "WS_EX_TOPMOST"),
+    (0x0000_0010, "WS_EX_ACCEPTFILES"),
+    (0x0000_0020, "WS_EX_TRANSPARENT"),
+    (0x0000_0040, "WS_EX_MDICHILD"),
+    (0x0000_0080, "WS_EX_TOOLWINDOW"),
+    (0x0000_0100, "WS_EX_WINDOWEDGE"),
+    (0x0000_0200, "WS_EX_CLIENTEDGE"),
+    (0x0000_0400, "WS_EX_CONTEXTHELP"),
+    (0x0000_1000, "WS_EX_RIGHT"),
+    (0x0000_2000, "WS_EX_RTLREADING"),
+    (0x0000_4000, "WS_EX_LEFTSCROLLBAR"),
+    (0x0001_0000, "WS_EX_CONTROLPARENT"),
+    (0x0002_0000, "WS_EX_STATICEDGE"),
+    (0x0004_0000, "WS_EX_APPWINDOW"),
+    (0x0008_0000, "WS_EX_LAYERED"),
+    (0x0010_0000, "WS_EX_NOINHERITLAYOUT"),
+    (0x0020_0000, "WS_EX_NOREDIRECTIONBITMAP"),
+    (0x0040_0000, "WS_EX_LAYOUTRTL"),
+    (0x0200_0000, "WS_EX_COMPOSITED"),
+    (0x0800_0000, "WS_EX_NOACTIVATE"),
+];
+
+/// The class style flags with their names, from WinUser.h.
+const CLASS_STYLE_NAMES: [(u32, &str); 13] = [
+    (0x0000_0001, "CS_VREDRAW"),
+    (0x0000_0002, "CS_HREDRAW"),
+    (0x0000_0008, "CS_DBLCLKS"),
+    (0x0000_0020, "CS_OWNDC"),
+    (0x0000_0040, "CS_CLASSDC"),
+    (0x0000_0080, "CS_PARENTDC"),
+    (0x0000_0200, "CS_NOCLOSE"),
+    (0x0000_0800, "CS_SAVEBITS"),
+    (0x0000_1000, "CS_BYTEALIGNCLIENT"),
+    (0x0000_2000, "CS_BYTEALIGNWINDOW"),
+    (0x0000_4000, "CS_GLOBALCLASS"),
+    (0x0001_0000, "CS_IME"),
+    (0x0002_0000, "CS_DROPSHADOW"),
+];
+
+/// Names the flags set in `value`, followed by the remaining bits in hex
+/// if any are not in `names`.
+fn flag_names(value: u32, names: &[(u32, &'static str)]) -> Vec<String> {
+    let mut remaining = value;
+    let mut result: Vec<String> = names
+        .iter()
+        .filter(|&&(flag, _)| value & flag == flag)
+        .map(|&(flag, name)| {
+            remaining &= !flag;
+            name.to_string()
+        })
+        .collect();
+    if remaining != 0 {
+        result.push(format!("0x{:08x}", remaining));
+    }
+    result
+}
+
+/// Extension methods for [`WindowInfo`] providing display and validation functionality.
+impl WindowInfo {
+    /// Returns `true` if the window is always-on-top (`WS_EX_TOPMOST`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::WindowInfo;
+    /// let window = WindowInfo {
+    ///     ex_style: 0x0000_0008,
+    ///     ..Default::default()
+    /// };
+    /// assert!(window.is_topmost());
+    /// assert!(!window.is_tool_window());
+    /// ```
+    pub fn is_topmost(&self) -> bool {
+        self.ex_style & WS_EX_TOPMOST != 0
+    }
+
+    /// Names the window style flags ([`style`](Self::style)).
+    ///
+    /// The low 16 bits hold control-specific styles (`ES_*`, `BS_*`, ...);
+    /// those and other unknown bits are listed as one hex value at the end.
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::WindowInfo;
+    /// let window = WindowInfo {
+    ///     style: 0x10CF_0000, // WS_OVERLAPPEDWINDOW | WS_VISIBLE
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     window.style_names(),
+    ///     [
+    ///         "WS_VISIBLE",
+    ///         "WS_SYSMENU",
+    ///         "WS_THICKFRAME",
+    ///         "WS_CAPTION",
+    ///         "WS_MINIMIZEBOX",
+    ///         "WS_MAXIMIZEBOX"
+    ///     ]
+    /// );
+    /// ```
+    pub fn style_names(&self) -> Vec<String> {
+        let style = self.style;
+        let mut names = flag_names(
+            style & !(WS_BORDER | WS_DLGFRAME | WS_MINIMIZEBOX | WS_MAXIMIZEBOX),
+            &STYLE_NAMES,
+        );
+        // Unknown bits are listed last, so insert before them
+        let at = names
+            .iter()
+            .position(|name| name.starts_with("0x"))
+            .unwrap_or(names.len());
+
+        let mut extra = Vec::new();
+        match style & (WS_BORDER | WS_DLGFRAME) {
+            0 => {}
+            WS_BORDER => extra.push("WS_BORDER"),
+            WS_DLGFRAME => extra.push("WS_DLGFRAME"),
+            _ => extra.push("WS_CAPTION"),
+        }
+        let is_child = style & WS_CHILD != 0;
+        if style & WS_MINIMIZEBOX != 0 {
+            extra.push(if is_child {
+                "WS_GROUP"
+            } else {
+                "WS_MINIMIZEBOX"
+            });
+        }
+        if style & WS_MAXIMIZEBOX != 0 {
+            extra.push(if is_child {
+                "WS_TABSTOP"
+            } else {
+                "WS_MAXIMIZEBOX"
+            });
+        }
+        names.splice(at..at, extra.into_iter().map(String::from));
+        names
+    }
+
+    /// Names the extended window style flags ([`ex_style`](Self::ex_style)),
+    /// listing unknown bits as one hex value at the end.
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::WindowInfo;
+    /// let window = WindowInfo {
+    ///     ex_style: 0x0000_0088,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(window.ex_style_names(), ["WS_EX_TOPMOST", "WS_EX_TOOLWINDOW"]);
+    /// ```
+    pub fn ex_style_names(&self) -> Vec<String> {
+        flag_names(self.ex_style, &EX_STYLE_NAMES)
+    }
+
+    /// Returns `true` if the window covers its whole monitor, like games,
+    /// video players, and presentations do.
+    ///
+    /// The visible DWM frame bounds are compared rather than the window
+    /// rectangle, whose invisible resize borders make ordinary maximized
+    /// windows overhang their monitor. Maximized windows with a title bar
+    /// never count, even with an auto-hidden taskbar; neither do hidden,
+    /// minimized, cloaked, or shell windows such as the desktop.
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::{WindowGeometry, WindowInfo, WindowPosition};
+    /// let monitor = WindowPosition { x: 0, y: 0, width: 1920, height: 1080 };
+    /// let game = WindowInfo {
+    ///     is_visible: true,
+    ///     monitor_bounds: monitor,
+    ///     geometry: WindowGeometry { frame_bounds: monitor, ..Default::default() },
+    ///     ..Default::default()
+    /// };
+    /// assert!(game.is_fullscreen());
+    ///
+    /// let maximized = WindowInfo {
+    ///     style: 0x01C0_0000, // WS_MAXIMIZE | WS_CAPTION
+    ///     ..game.clone()
+    /// };
+    /// assert!(!maximized.is_fullscreen());
+    /// ```
+    pub fn is_fullscreen(&self) -> bool {
+        let monitor = self.monitor_bounds;
+        if monitor.width <= 0
+            || monitor.height <= 0
+            || !self.is_visible
+            || self.is_minimized
+            || self.is_cloaked
+            || self.kind.is_system()
+        {
+            return false;
+        }
+        if self.style & WS_MAXIMIZE != 0 && self.style & WS_CAPTION == WS_CAPTION {
+            return false;
+        }
+
+        let frame = self.geometry.frame_bounds;
+        let bounds = if frame.width > 0 && frame.height > 0 {
+            frame
+        } else {
+            self.position
+        };
+        bounds.x <= monitor.x
+            && bounds.y <= monitor.y
+            && bounds.x + bounds.width >= monitor.x + monitor.width
+            && bounds.y + bounds.height >= monitor.y + monitor.height
+    }
+
+    /// Returns `true` if this is a ghost window, which the system shows
+    /// with a "(Not Responding)" title in place of a hung window.
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::WindowInfo;
+    /// let window = WindowInfo {
+    ///     class_name: "Ghost".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(window.is_ghost());
+    /// ```
+    pub fn is_ghost(&self) -> bool {
+        self.ghost_of.is_some() || KnownWindowClass::Ghost.matches(&self.class_name)
+    }
+
+    /// Returns whether the window is minimized, maximized, or neither.
+    ///
+    /// Maximization is read from the style, so it is only detected if styles
+    /// were enumerated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::{WindowInfo, WindowState};
+    /// let window = WindowInfo {
+    ///     style: 0x0100_0000,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(window.state(), WindowState::Maximized);
+    /// assert_eq!(WindowInfo::default().state(), WindowState::Normal);
+    /// ```
+    pub fn state(&self) -> WindowState {
+        if self.is_minimized {
+            WindowState::Minimized
+        } else if self.style & WS_MAXIMIZE != 0 {
+            WindowState::Maximized
+        } else {
+            WindowState::Normal
+        }
+    }
+
+    /// Classifies the window from its class name and styles.
+    ///
+    /// Known shell and system classes map to their kind. Other windows are
+    /// application windows if they have a caption or the
+    /// `WS_EX_APPWINDOW` style and are not tool windows; when styles were
+    /// not gathered, any unknown class counts as an application window.
+    /// This is the value stored in [`kind`](Self::kind) during enumeration.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowInfo, WindowKind};
+    ///
+    /// let taskbar = WindowInfo {
+    ///     class_name: "Shell_TrayWnd".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(taskbar.classify(), WindowKind::Taskbar);
+    ///
+    /// let editor = WindowInfo {
+    ///     class_name: "Notepad".to_string(),
+    ///     style: 0x00CF_0000, // WS_OVERLAPPEDWINDOW
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(editor.classify(), WindowKind::AppWindow);
+    /// ```
+    pub fn classify(&self) -> WindowKind {
+        match self.known_class() {
+            Some(KnownWindowClass::ShellTray | KnownWindowClass::SecondaryShellTray) => {
+                return WindowKind::Taskbar
+            }
+            Some(KnownWindowClass::Progman | KnownWindowClass::WorkerW) => {
+                return WindowKind::Desktop
+            }
+            Some(KnownWindowClass::NotifyIconOverflow) => return WindowKind::Tray,
+            Some(KnownWindowClass::Ime) => return WindowKind::ImeWindow,
+            Some(KnownWindowClass::Tooltip) => return WindowKind::Tooltip,
+            Some(KnownWindowClass::Menu) => return WindowKind::Menu,
+            Some(KnownWindowClass::Dialog) => return WindowKind::SystemDialog,
+            _ => {}
+        }
+
+        // Text services create per-thread IME helper windows
+        if self.class_name == "MSCTFIME UI" {
+            return WindowKind::ImeWindow;
+        }
+        if self
+            .class_name
+            .starts_with("TopLevelWindowForOverflowXamlIsland")
+        {
+            return WindowKind::Tray;
+        }
+
+        // Without styles there is nothing more to go on
+        if self.style == 0 && self.ex_style == 0 {
+            return WindowKind::AppWindow;
+        }
+        if self.is_app_window() || (self.style & WS_CAPTION == WS_CAPTION && !self.is_tool_window())
+        {
+            WindowKind::AppWindow
+        } else {
+            WindowKind::Other
+        }
+    }
+
+    /// Returns the well-known class of the window, if its class is one.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{KnownWindowClass, WindowInfo};
+    ///
+    /// let window = WindowInfo {
+    ///     class_name: "ConsoleWindowClass".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(window.known_class(), Some(KnownWindowClass::ConsoleWindow));
+    /// ```
+    pub fn known_class(&self) -> Option<KnownWindowClass> {
+        KnownWindowClass::from_class_name(&self.class_name)
+    }
+
+    /// Returns `true` if the window is a console window, either a classic
+    /// console host window or a Windows Terminal window.
+    ///
+    /// The attached process is reported by
+    /// [`console_client_pid`](Self::console_client_pid).
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::WindowInfo;
+    /// let window = WindowInfo {
+    ///     class_name: "CASCADIA_HOSTING_WINDOW_CLASS".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(window.is_console());
+    /// ```
+    pub fn is_console(&self) -> bool {
+        matches!(
+            self.known_class(),
+            Some(KnownWindowClass::ConsoleWindow | KnownWindowClass::WindowsTerminal)
+        )
+    }
+
+    /// Returns `true` if the window is a tool window (`WS_EX_TOOLWINDOW`).
+    ///
+    /// Tool windows are hidden from the taskbar and from Alt-Tab.
+    pub fn is_tool_window(&self) -> bool {
+        self.ex_style & WS_EX_TOOLWINDOW != 0
+    }
+
+    /// Returns `true` if the window is a layered window (`WS_EX_LAYERED`).
+    pub fn is_layered(&self) -> bool {
+        self.ex_style & WS_EX_LAYERED != 0
+    }
+
+    /// Returns `true` if the window forces itself onto the taskbar (`WS_EX_APPWINDOW`).
+    pub fn is_app_window(&self) -> bool {
+        self.ex_style & WS_EX_APPWINDOW != 0
+    }
+
+    /// Prints detailed information about the window to stdout.
+    ///
+    /// Use the alternate [`Display`](std::fmt::Display) form (`{:#}`) to get
+    /// the same text as a string.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![allow(deprecated)]
+    /// # use window_enumerator::{WindowHandle, WindowInfo};
+    /// # let window = WindowInfo {
+    /// #     hwnd: WindowHandle::new(12345),
+    /// #     pid: 1234,
+    /// #     title: "Test".to_string(),
+    /// #     class_name: "TestClass".to_string(),
+    /// #     process_name: "test.exe".to_string(),
+    /// #     process_file: std::path::PathBuf::from("test.exe"),
+    /// #     index: 1,
+    /// #     ..Default::default()
+    /// # };
+    /// window.print();
+    /// ```
+    ///
+    /// Requires the `console` feature.
+    #[cfg(feature = "console")]
+    #[deprecated(note = "format the window with `{:#}` instead")]
+    pub fn print(&self) {
+        println!("{:#}", self);
+        println!("----------------------------------------");
+    }
+
+    /// Prints compact window information to stdout.
+    ///
+    /// Use [`fmt_compact`](Self::fmt_compact) to get the same text as a
+    /// string.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![allow(deprecated)]
+    /// # use window_enumerator::{WindowHandle, WindowInfo};
+    /// # let window = WindowInfo {
+    /// #     hwnd: WindowHandle::new(12345),
+    /// #     pid: 1234,
+    /// #     title: "Test".to_string(),
+    /// #     class_name: "TestClass".to_string(),
+    /// #     process_name: "test.exe".to_string(),
+    /// #     process_file: std::path::PathBuf::from("test.exe"),
+    /// #     index: 1,
+    /// #     ..Default::default()
+    /// # };
+    /// window.print_compact();
+    /// ```
+    ///
+    /// Requires the `console` feature.
+    #[cfg(feature = "console")]
+    #[deprecated(note = "format the window with `{}` or use `fmt_compact` instead")]
+    pub fn print_compact(&self) {
+        println!("{}", self);
+    }
+
+    /// Checks if the window handle is still valid.
+    ///
+    /// This verifies that the window still exists in the system.
+    ///
+    /// # Examples
+    /// ```
+    /// # use window_enumerator::{WindowHandle, WindowInfo};
+    /// # let window = WindowInfo {
+    /// #     hwnd: WindowHandle::new(12345),
+    /// #     pid: 1234,
+    /// #     title: "Test".to_string(),
+    /// #     class_name: "TestClass".to_string(),
+    /// #     process_name: "test.exe".to_string(),
+    /// #     process_file: std::path::PathBuf::from("test.exe"),
+    /// #     index: 1,
+    /// #     ..Default::default()
+    /// # };
+    /// let is_valid = window.is_valid();
+    /// ```
+    #[cfg(feature = "windows")]
+    pub fn is_valid(&self) -> bool {
+        self.hwnd.is_valid()
+    }
+
+    /// Loads the process name, executable path, and command line on demand.
+    ///
+    /// Use this after enumerating with
+    /// [`EnumerationOptions::include_process_info`](crate::EnumerationOptions::include_process_info)
+    /// disabled, for the few windows whose process details are actually needed.
+    #[cfg(feature = "windows")]
+    pub fn load_process_info(&mut self) {
+        if self.pid > 0 {
+            unsafe { crate::enumerator::WindowEnumerator::load_process_info(self) }
+        }
+    }
+
+    /// Resolves the account owning the process on demand.
+    ///
+    /// See [`EnumerationOptions::include_user_name`](crate::EnumerationOptions::include_user_name).
+    #[cfg(feature = "windows")]
+    pub fn load_user_name(&mut self) {
+        if self.pid > 0 {
+            self.user_name =
+                unsafe { crate::enumerator::WindowEnumerator::get_process_user_name(self.pid) };
+        }
+    }
+
+    /// Reads the product name, company, and file version of the executable
+    /// on demand.
+    ///
+    /// Requires [`process_file`](Self::process_file), so load the process
+    /// information first if it was skipped during enumeration.
+    #[cfg(feature = "windows")]
+    pub fn load_version_info(&mut self) {
+        let version =
+            unsafe { crate::enumerator::WindowEnumerator::get_exe_version(&self.process_file) };
+        self.product_name = version.product_name;
+        self.company_name = version.company_name;
+        self.file_version = version.file_version;
+    }
+
+    /// Reads the UI Automation name, control type, and automation ID on
+    /// demand.
+    ///
+    /// Blocks until the UI Automation timeout if the window is hung.
+    ///
+    /// Requires the `uia` feature.
+    #[cfg(feature = "uia")]
+    pub fn load_uia_info(&mut self) {
+        if let Some(client) = crate::uia::UiaClient::new() {
+            client.load_into(self);
+        }
+    }
+
+    /// Verifies the Authenticode signature of the executable on demand,
+    /// filling in [`signature_status`](Self::signature_status) and
+    /// [`publisher`](Self::publisher).
+    ///
+    /// Requires [`process_file`](Self::process_file), so load the process
+    /// information first if it was skipped during enumeration.
+    ///
+    /// Requires the `signing` feature.
+    #[cfg(feature = "signing")]
+    pub fn load_signature(&mut self) {
+        let signature = unsafe { crate::signing::verify_file(&self.process_file) };
+        self.signature_status = signature.as_ref().map(|(status, _)| *status);
+        self.publisher = signature.and_then(|(_, publisher)| publisher);
+    }
+
+    /// Queries the resource usage of the process on demand.
+    ///
+    /// See [`EnumerationOptions::include_process_metrics`](crate::EnumerationOptions::include_process_metrics).
+    #[cfg(feature = "windows")]
+    pub fn load_process_metrics(&mut self) {
+        use crate::enumerator::WindowEnumerator;
+
+        if self.pid > 0 {
+            self.process_metrics = unsafe {
+                WindowEnumerator::snapshot_processes()
+                    .iter()
+                    .find(|process| process.pid == self.pid)
+                    .and_then(|process| {
+                        WindowEnumerator::get_process_metrics(self.pid, process.thread_count)
+                    })
+            };
+        }
+    }
+
+    /// Resolves the parent process and the ancestor chain on demand.
+    ///
+    /// See [`EnumerationOptions::include_parent_chain`](crate::EnumerationOptions::include_parent_chain).
+    #[cfg(feature = "windows")]
+    pub fn load_parent_chain(&mut self) {
+        use crate::enumerator::WindowEnumerator;
+
+        if self.pid > 0 {
+            (self.parent_pid, self.parent_chain) = unsafe {
+                WindowEnumerator::get_parent_chain(
+                    &WindowEnumerator::snapshot_processes(),
+                    self.pid,
+                )
+            };
+        }
+    }
+
+    /// Loads the position, size, geometry, and monitor handle on demand.
+    ///
+    /// The monitor index is not resolved; compare [`monitor_handle`](Self::monitor_handle)
+    /// against the results of `WindowEnumerator::enumerate_monitors` if needed.
+    #[cfg(feature = "windows")]
+    pub fn load_position(&mut self) {
+        use crate::enumerator::WindowEnumerator;
+        use windows::Win32::Foundation::*;
+        use windows::Win32::Graphics::Gdi::*;
+
+        let hwnd = HWND::from(self.hwnd);
+        unsafe {
+            self.position = WindowEnumerator::get_window_position(hwnd);
+            self.geometry = WindowEnumerator::get_window_geometry(hwnd, &self.position);
+            self.monitor_handle = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST).0;
+            self.monitor_bounds = WindowEnumerator::get_monitor_bounds(self.monitor_handle);
+        }
+    }
+
+    /// Reads the registration of the window class on demand.
+    ///
+    /// See [`EnumerationOptions::include_class_details`](crate::EnumerationOptions::include_class_details).
+    #[cfg(feature = "windows")]
+    pub fn load_class_details(&mut self) {
+        let hwnd = windows::Win32::Foundation::HWND::from(self.hwnd);
+        self.class_details = Some(unsafe { crate::inspect::class_details(hwnd) });
+    }
+
+    /// Re-queries the title, position, and state of this window in place.
+    ///
+    /// This is much cheaper than re-enumerating all windows when only a few
+    /// long-lived [`WindowInfo`] values need to stay current. Process
+    /// information and the index are kept as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::WindowNotFound`](crate::WindowError::WindowNotFound)
+    /// if the window no longer exists, or if its handle now belongs to a
+    /// different window of another thread. The value is left unchanged then.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowEnumerator;
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// let mut window = enumerator.get_windows()[0].clone();
+    ///
+    /// // Later...
+    /// if window.refresh().is_ok() {
+    ///     println!("{} is now at {:?}", window.title, window.position);
+    /// }
+    /// ```
+    #[cfg(feature = "windows")]
+    pub fn refresh(&mut self) -> crate::errors::Result<()> {
+        use crate::enumerator::WindowEnumerator;
+        use crate::errors::WindowError;
+        use windows::Win32::Foundation::*;
+        use windows::Win32::UI::WindowsAndMessaging::*;
+
+        let hwnd = HWND::from(self.hwnd);
+        unsafe {
+            // Handles are recycled, so make sure it still names the same window
+            let (thread_id, pid) = WindowEnumerator::get_thread_process_id(hwnd);
+            if thread_id == 0 || thread_id != self.thread_id || pid != self.pid {
+                return Err(WindowError::WindowNotFound);
+            }
+
+            let owner = GetWindow(hwnd, GW_OWNER);
+            let mut refreshed = WindowInfo {
+                title: WindowEnumerator::get_window_text(hwnd),
+                owner_hwnd: (owner.0 != 0).then(|| owner.into()),
+                is_visible: IsWindowVisible(hwnd).as_bool(),
+                is_minimized: IsIconic(hwnd).as_bool(),
+                is_hung: IsHungAppWindow(hwnd).as_bool(),
+                style: GetWindowLongW(hwnd, GWL_STYLE) as u32,
+                ex_style: GetWindowLongW(hwnd, GWL_EXSTYLE) as u32,
+                is_cloaked: WindowEnumerator::is_window_cloaked(hwnd),
+                ..self.clone()
+            };
+            let previous_monitor = refreshed.monitor_handle;
+            refreshed.load_position();
+            if refreshed.monitor_handle != previous_monitor {
+                refreshed.monitor_index = WindowEnumerator::enumerate_monitors_for_metadata()
+                    .iter()
+                    .find(|monitor| monitor.handle == refreshed.monitor_handle)
+                    .map(|monitor| monitor.index);
+            }
+            refreshed.kind = refreshed.classify();
+
+            // Don't report data gathered from a window destroyed halfway through
+            if !IsWindow(hwnd).as_bool() {
+                return Err(WindowError::WindowNotFound);
+            }
+            *self = refreshed;
+        }
+        Ok(())
+    }
+}
+
+impl WindowList {
+    /// Creates a snapshot from a list of windows.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowInfo, WindowList};
+    ///
+    /// let list = WindowList::new(vec![WindowInfo::default()]);
+    /// let shared = list.clone();
+    /// std::thread::spawn(move || assert_eq!(shared.len(), 1))
+    ///     .join()
+    ///     .unwrap();
+    /// assert_eq!(list.len(), 1);
+    /// ```
+    pub fn new(windows: Vec<WindowInfo>) -> Self {
+        Self {
+            windows: windows.into(),
+        }
+    }
+
+    /// Returns the windows as a slice.
+    pub fn as_slice(&self) -> &[WindowInfo] {
+        &self.windows
+    }
+
+    /// Returns the number of windows in the snapshot.
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Returns `true` if the snapshot contains no windows.
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Returns an iterator over the windows in the snapshot.
+    pub fn iter(&self) -> std::slice::Iter<'_, WindowInfo> {
+        self.windows.iter()
+    }
+
+    /// Retrieves a window by its 1-based index.
+    pub fn get_by_index(&self, index: usize) -> Option<&WindowInfo> {
+        self.windows.iter().find(|w| w.index == index)
+    }
+
+    /// Retrieves a window by its handle.
+    pub fn get_by_hwnd(&self, hwnd: WindowHandle) -> Option<&WindowInfo> {
+        self.windows.iter().find(|w| w.hwnd == hwnd)
+    }
+
+    /// Returns an iterator over the windows matching the filter criteria.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{FilterCriteria, WindowInfo, WindowList};
+    ///
+    /// let list = WindowList::new(vec![
+    ///     WindowInfo { pid: 1, ..Default::default() },
+    ///     WindowInfo { pid: 2, ..Default::default() },
+    /// ]);
+    /// let criteria = FilterCriteria::builder().pid(2).build();
+    /// assert_eq!(list.filter(&criteria).count(), 1);
+    /// ```
+    pub fn filter<'a>(
+        &'a self,
+        criteria: &'a FilterCriteria,
+    ) -> impl Iterator<Item = &'a WindowInfo> + 'a {
+        self.windows
+            .iter()
+            .filter(move |window| crate::utils::matches_criteria(window, criteria))
+    }
+}
+
+impl WindowTree {
+    /// Builds a tree of enumerated windows, nesting owned windows under
+    /// their owners.
+    ///
+    /// Enumerations only gather top-level windows, so the tree contains no
+    /// child windows; use `WindowTree::capture` for the full tree.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{TreeRelation, WindowHandle, WindowInfo, WindowTree};
+    ///
+    /// let main = WindowInfo {
+    ///     hwnd: WindowHandle::new(0x10),
+    ///     title: "Editor".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let dialog = WindowInfo {
+    ///     hwnd: WindowHandle::new(0x20),
+    ///     title: "Save As".to_string(),
+    ///     owner_hwnd: Some(WindowHandle::new(0x10)),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let tree = WindowTree::from_windows(&[main, dialog]);
+    /// assert_eq!(tree.roots.len(), 1);
+    /// assert_eq!(tree.roots[0].children[0].relation, TreeRelation::Owned);
+    /// assert_eq!(tree.len(), 2);
+    /// ```
+    pub fn from_windows(windows: &[WindowInfo]) -> Self {
+        let present: std::collections::HashSet<WindowHandle> =
+            windows.iter().map(|window| window.hwnd).collect();
+        let mut visited = std::collections::HashSet::new();
+        let roots = windows
+            .iter()
+            .filter(|window| {
+                window
+                    .owner_hwnd
+                    .is_none_or(|owner| !present.contains(&owner))
+            })
+            .filter_map(|window| {
+                Self::owned_node(windows, window, TreeRelation::Root, &mut visited)
+            })
+            .collect();
+        Self { roots }
+    }
+
+    /// Builds the node of an enumerated window and, below it, the windows
+    /// it owns. Returns `None` for windows already in the tree, which
+    /// breaks ownership cycles.
+    fn owned_node(
+        windows: &[WindowInfo],
+        window: &WindowInfo,
+        relation: TreeRelation,
+        visited: &mut std::collections::HashSet<WindowHandle>,
+    ) -> Option<WindowTreeNode> {
+        if !visited.insert(window.hwnd) {
+            return None;
+        }
+        let children = windows
+            .iter()
+            .filter(|owned| owned.owner_hwnd == Some(window.hwnd))
+            .filter_map(|owned| Self::owned_node(windows, owned, TreeRelation::Owned, visited))
+            .collect();
+        Some(WindowTreeNode {
+            hwnd: window.hwnd,
+            title: window.title.clone(),
+            class_name: window.class_name.clone(),
+            pid: window.pid,
+            is_visible: window.is_visible,
+            relation,
+            children,
+        })
+    }
+
+    /// Returns the number of windows in the tree.
+    pub fn len(&self) -> usize {
+        fn count(node: &WindowTreeNode) -> usize {
+            1 + node.children.iter().map(count).sum::<usize>()
+        }
+        self.roots.iter().map(count).sum()
+    }
+
+    /// Checks whether the tree contains no windows.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Finds the node of a window anywhere in the tree.
+    pub fn find(&self, hwnd: WindowHandle) -> Option<&WindowTreeNode> {
+        fn find_in(nodes: &[WindowTreeNode], hwnd: WindowHandle) -> Option<&WindowTreeNode> {
+            nodes.iter().find_map(|node| {
+                if node.hwnd == hwnd {
+                    Some(node)
+                } else {
+                    find_in(&node.children, hwnd)
+                }
+            })
+        }
+        find_in(&self.roots, hwnd)
+    }
+}
+
+impl<'a> WindowQuery<'a> {
+    /// Starts a query matching every window of `windows`.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{FilterCriteria, WindowInfo, WindowQuery};
+    ///
+    /// let windows: Vec<WindowInfo> = [("a", "other.exe"), ("b", "app.exe"), ("c", "app.exe")]
+    ///     .into_iter()
+    ///     .map(|(title, process)| WindowInfo {
+    ///         title: title.to_string(),
+    ///         process_name: process.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .collect();
+    ///
+    /// let criteria = FilterCriteria::builder().process_name_contains("app").build();
+    /// let first = WindowQuery::new(&windows).filter(&criteria).first().unwrap();
+    /// assert_eq!(first.title, "b");
+    /// ```
+    pub fn new(windows: &'a [WindowInfo]) -> Self {
+        Self {
+            windows,
+            criteria: Vec::new(),
+            predicates: Vec::new(),
+            #[cfg(feature = "sorting")]
+            sort_keys: Vec::new(),
+            #[cfg(feature = "selection")]
+            selection: None,
+            skip: 0,
+            take: None,
+        }
+    }
+
+    /// Keeps only the windows matching `criteria`, in addition to the
+    /// filters added before.
+    pub fn filter(mut self, criteria: &FilterCriteria) -> Self {
+        self.criteria.push(criteria.clone());
+        self
+    }
+
+    /// Keeps only the windows for which `predicate` returns `true`, in
+    /// addition to the filters added before.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowInfo, WindowQuery};
+    ///
+    /// let windows = vec![
+    ///     WindowInfo { title: "Inbox".to_string(), ..Default::default() },
+    ///     WindowInfo { title: String::new(), ..Default::default() },
+    /// ];
+    /// let query = WindowQuery::new(&windows).filter_by(|window| !window.title.is_empty());
+    /// assert_eq!(query.count(), 1);
+    /// ```
+    pub fn filter_by<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&WindowInfo) -> bool + 'a,
+    {
+        self.predicates.push(Box::new(predicate));
+        self
+    }
 
-#[cfg(feature = "sorting")]
-use crate::types::{PositionSort, SortCriteria};
+    /// Sorts the matching windows by `sort_keys`, which break ties of the
+    /// keys added before.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{SortKey, SortOrder, WindowInfo, WindowQuery};
+    ///
+    /// let windows: Vec<WindowInfo> = ["b", "c", "a"]
+    ///     .into_iter()
+    ///     .map(|title| WindowInfo { title: title.to_string(), ..Default::default() })
+    ///     .collect();
+    ///
+    /// let titles: Vec<&str> = WindowQuery::new(&windows)
+    ///     .sort(&[SortKey::Title(SortOrder::Asc)])
+    ///     .take(2)
+    ///     .into_iter()
+    ///     .map(|window| window.title.as_str())
+    ///     .collect();
+    /// assert_eq!(titles, ["a", "b"]);
+    /// ```
+    #[cfg(feature = "sorting")]
+    pub fn sort(mut self, sort_keys: &[SortKey]) -> Self {
+        self.sort_keys.extend_from_slice(sort_keys);
+        self
+    }
 
-#[cfg(feature = "sorting")]
-use crate::utils::matches_criteria;
+    /// Picks windows from the sorted matches with `selection`, replacing
+    /// any selection made before.
+    ///
+    /// Index-based selections refer to [`WindowInfo::index`];
+    /// position-based ones to positions within the sorted matches.
+    #[cfg(feature = "selection")]
+    pub fn select(mut self, selection: &Selection) -> Self {
+        self.selection = Some(selection.clone());
+        self
+    }
 
-/// Extension methods for [`WindowInfo`] providing display and validation functionality.
-impl WindowInfo {
-    /// Prints detailed information about the window to stdout.
+    /// Skips the first `count` selected windows.
+    pub fn skip(mut self, count: usize) -> Self {
+        self.skip += count;
+        self
+    }
+
+    /// Yields at most `count` windows.
+    pub fn take(mut self, count: usize) -> Self {
+        self.take = Some(self.take.map_or(count, |take| take.min(count)));
+        self
+    }
+
+    /// Evaluates the query into clones of the resulting windows.
+    pub fn collect(self) -> Vec<WindowInfo> {
+        self.evaluate().into_iter().cloned().collect()
+    }
+
+    /// Evaluates the query into references to the resulting windows.
+    pub fn collect_refs(self) -> Vec<&'a WindowInfo> {
+        self.evaluate()
+    }
+
+    /// Returns the first resulting window.
+    pub fn first(self) -> Option<&'a WindowInfo> {
+        self.take(1).evaluate().into_iter().next()
+    }
+
+    /// Returns the number of resulting windows.
+    pub fn count(self) -> usize {
+        self.evaluate().len()
+    }
+
+    fn matches(&self, window: &WindowInfo) -> bool {
+        self.criteria
+            .iter()
+            .all(|criteria| crate::utils::matches_criteria(window, criteria))
+            && self.predicates.iter().all(|predicate| predicate(window))
+    }
+
+    /// Returns whether all matches must be known before the first result,
+    /// as for sorting.
+    fn needs_all_matches(&self) -> bool {
+        #[allow(unused_mut)]
+        let mut needs_all = false;
+        #[cfg(feature = "sorting")]
+        {
+            needs_all |= !self.sort_keys.is_empty();
+        }
+        #[cfg(feature = "selection")]
+        {
+            needs_all |= self.selection.is_some();
+        }
+        needs_all
+    }
+
+    fn evaluate(&self) -> Vec<&'a WindowInfo> {
+        let windows: &'a [WindowInfo] = self.windows;
+        let matching = windows.iter().filter(|window| self.matches(window));
+        let take = self.take.unwrap_or(usize::MAX);
+
+        // Without sorting or selection, stop as soon as enough windows matched
+        if !self.needs_all_matches() {
+            return matching.skip(self.skip).take(take).collect();
+        }
+
+        #[allow(unused_mut)]
+        let mut matched: Vec<&'a WindowInfo> = matching.collect();
+        #[cfg(feature = "sorting")]
+        WindowSorter::sort_window_refs(&mut matched, &self.sort_keys);
+        #[cfg(feature = "selection")]
+        if let Some(ref selection) = self.selection {
+            matched = selection.select(matched, crate::utils::max_index(windows));
+        }
+        matched.into_iter().skip(self.skip).take(take).collect()
+    }
+}
+
+impl<'a> IntoIterator for WindowQuery<'a> {
+    type Item = &'a WindowInfo;
+    type IntoIter = std::vec::IntoIter<&'a WindowInfo>;
+
+    /// Evaluates the query and iterates over the resulting windows.
+    fn into_iter(self) -> Self::IntoIter {
+        self.evaluate().into_iter()
+    }
+}
+
+impl std::ops::Deref for WindowList {
+    type Target = [WindowInfo];
+
+    fn deref(&self) -> &Self::Target {
+        &self.windows
+    }
+}
+
+impl From<Vec<WindowInfo>> for WindowList {
+    fn from(windows: Vec<WindowInfo>) -> Self {
+        Self::new(windows)
+    }
+}
+
+impl<'a> IntoIterator for &'a WindowList {
+    type Item = &'a WindowInfo;
+    type IntoIter = std::slice::Iter<'a, WindowInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "selection")]
+impl Selection {
+    /// Returns `true` if the given 1-based index is part of this selection.
     ///
     /// # Examples
     /// ```
-    /// # use window_enumerator::WindowInfo;
-    /// # use window_enumerator::WindowPosition;
-    /// # let window = WindowInfo {
-    /// #     hwnd: 12345,
-    /// #     pid: 1234,
-    /// #     title: "Test".to_string(),
-    /// #     class_name: "TestClass".to_string(),
-    /// #     process_name: "test.exe".to_string(),
-    /// #     process_file: std::path::PathBuf::from("test.exe"),
-    /// #     index: 1,
-    /// #     position: WindowPosition::default(),
-    /// # };
-    /// window.print();
+    /// use window_enumerator::Selection;
+    ///
+    /// let selection = Selection::Indices(vec![1, 3]);
+    /// assert!(selection.matches(3));
+    /// assert!(!selection.matches(2));
+    /// assert!(Selection::All.matches(42));
     /// ```
-    pub fn print(&self) {
-        println!("Index: {}", self.index);
-        println!("Window Handle: 0x{:x}", self.hwnd);
-        println!("Process ID: {}", self.pid);
-        println!("Title: {}", self.title);
-        println!("Class Name: {}", self.class_name);
-        println!("Process Name: {}", self.process_name);
-        println!("Process File: {}", self.process_file.display());
-        println!(
-            "Position: ({}, {}) Size: {}x{}",
-            self.position.x, self.position.y, self.position.width, self.position.height
-        );
-        println!("----------------------------------------");
+    ///
+    /// Indices counted from the end of the list never match here because
+    /// the list length is unknown; use [`matches_in`](Self::matches_in)
+    /// for such selections.
+    pub fn matches(&self, index: usize) -> bool {
+        self.matches_with(index, None)
     }
 
-    /// Prints compact window information to stdout.
+    /// Returns `true` if the given 1-based index is part of this selection
+    /// when applied to a list of `len` items.
     ///
     /// # Examples
     /// ```
-    /// # use window_enumerator::WindowInfo;
-    /// # use window_enumerator::WindowPosition;
-    /// # let window = WindowInfo {
-    /// #     hwnd: 12345,
-    /// #     pid: 1234,
-    /// #     title: "Test".to_string(),
-    /// #     class_name: "TestClass".to_string(),
-    /// #     process_name: "test.exe".to_string(),
-    /// #     process_file: std::path::PathBuf::from("test.exe"),
-    /// #     index: 1,
-    /// #     position: WindowPosition::default(),
-    /// # };
-    /// window.print_compact();
+    /// use window_enumerator::parse_selection;
+    ///
+    /// let last = parse_selection("last").unwrap();
+    /// assert!(last.matches_in(5, 5));
+    /// assert!(!last.matches_in(4, 5));
+    ///
+    /// let odd = parse_selection("1-:2").unwrap();
+    /// assert!(odd.matches_in(3, 5));
+    /// assert!(!odd.matches_in(4, 5));
     /// ```
-    pub fn print_compact(&self) {
-        println!(
-            "[{}] 0x{:x} (PID: {}) @ ({},{}) - {}",
-            self.index, self.hwnd, self.pid, self.position.x, self.position.y, self.title
-        );
+    pub fn matches_in(&self, index: usize, len: usize) -> bool {
+        self.matches_with(index, Some(len))
     }
 
-    /// Checks if the window handle is still valid.
+    /// Wraps this selection so it applies to result positions rather than
+    /// enumeration indices. See [`Selection::ByPosition`].
     ///
-    /// This verifies that the window still exists in the system.
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{parse_selection, Selection};
+    ///
+    /// let first = parse_selection("1").unwrap().by_position();
+    /// assert!(first.is_by_position());
+    /// assert_eq!(first.clone().by_position(), first);
+    /// ```
+    pub fn by_position(self) -> Selection {
+        match self {
+            Selection::ByPosition(_) => self,
+            other => Selection::ByPosition(Box::new(other)),
+        }
+    }
+
+    /// Returns `true` if this selection applies to result positions.
+    pub fn is_by_position(&self) -> bool {
+        matches!(self, Selection::ByPosition(_))
+    }
+
+    /// Applies this selection to an already filtered (and sorted) list.
+    ///
+    /// Position-based selections match 1-based positions within `windows`;
+    /// index-based selections match [`WindowInfo::index`] against
+    /// `max_index`, the highest index of the enumerated windows.
     ///
     /// # Examples
     /// ```
-    /// # use window_enumerator::WindowInfo;
-    /// # use window_enumerator::WindowPosition;
-    /// # let window = WindowInfo {
-    /// #     hwnd: 12345,
-    /// #     pid: 1234,
-    /// #     title: "Test".to_string(),
-    /// #     class_name: "TestClass".to_string(),
-    /// #     process_name: "test.exe".to_string(),
-    /// #     process_file: std::path::PathBuf::from("test.exe"),
-    /// #     index: 1,
-    /// #     position: WindowPosition::default(),
-    /// # };
-    /// let is_valid = window.is_valid();
+    /// use window_enumerator::{parse_selection, WindowInfo};
+    ///
+    /// let windows: Vec<WindowInfo> = [7, 3, 5]
+    ///     .into_iter()
+    ///     .map(|index| WindowInfo { index, ..Default::default() })
+    ///     .collect();
+    /// let refs: Vec<&WindowInfo> = windows.iter().collect();
+    ///
+    /// let by_index = parse_selection("3").unwrap();
+    /// assert_eq!(by_index.select(refs.clone(), 10)[0].index, 3);
+    ///
+    /// // Indices have gaps after a refresh; `last` is the highest one
+    /// let last = parse_selection("last").unwrap();
+    /// assert_eq!(last.select(refs.clone(), 7)[0].index, 7);
+    ///
+    /// let by_position = parse_selection("last").unwrap().by_position();
+    /// assert_eq!(by_position.select(refs, 10)[0].index, 5);
     /// ```
-    #[cfg(feature = "windows")]
-    pub fn is_valid(&self) -> bool {
-        use windows::Win32::Foundation::*;
-        use windows::Win32::UI::WindowsAndMessaging::*;
+    pub fn select<'a>(
+        &self,
+        windows: Vec<&'a WindowInfo>,
+        max_index: usize,
+    ) -> Vec<&'a WindowInfo> {
+        if self.is_by_position() {
+            let len = windows.len();
+            windows
+                .into_iter()
+                .enumerate()
+                .filter(|(position, _)| self.matches_in(position + 1, len))
+                .map(|(_, window)| window)
+                .collect()
+        } else {
+            windows
+                .into_iter()
+                .filter(|window| self.matches_in(window.index, max_index))
+                .collect()
+        }
+    }
+
+    fn matches_with(&self, index: usize, len: Option<usize>) -> bool {
+        match self {
+            Selection::All => true,
+            Selection::Indices(indices) => indices.contains(&index),
+            Selection::Ranges(ranges) => ranges.iter().any(|range| range.contains(index, len)),
+            Selection::ByPosition(inner) => inner.matches_with(index, len),
+            Selection::Except { include, exclude } => {
+                include.matches_with(index, len) && !exclude.matches_with(index, len)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "selection")]
+impl SelectionIndex {
+    /// Resolves this index to a 1-based position, which may be zero or
+    /// negative when counting past the start of the list.
+    fn resolve(self, len: Option<usize>) -> Option<i64> {
+        match self {
+            SelectionIndex::FromStart(n) => Some(n as i64),
+            SelectionIndex::FromEnd(n) => len.map(|len| len as i64 + 1 - n as i64),
+        }
+    }
+}
+
+#[cfg(feature = "selection")]
+impl SelectionRange {
+    /// Returns `true` if the given 1-based index falls within this range.
+    fn contains(&self, index: usize, len: Option<usize>) -> bool {
+        let Some(start) = self.start.resolve(len) else {
+            return false;
+        };
+        let end = match self.end {
+            Some(end) => match end.resolve(len) {
+                Some(end) => end,
+                None => return false,
+            },
+            None => len.map_or(i64::MAX, |len| len as i64),
+        };
 
-        unsafe { IsWindow(HWND(self.hwnd)).as_bool() }
+        let index = index as i64;
+        index >= start && index <= end && (index - start) % self.step.max(1) as i64 == 0
+    }
+}
+
+#[cfg(feature = "sorting")]
+impl SortOrder {
+    /// Converts a 1/-1/0 order value into a sort order (`None` for 0).
+    pub fn from_sign(order: i8) -> Option<Self> {
+        match order.signum() {
+            1 => Some(SortOrder::Asc),
+            -1 => Some(SortOrder::Desc),
+            _ => None,
+        }
+    }
+
+    /// Applies this order to an ascending comparison result.
+    pub fn apply(self, ordering: std::cmp::Ordering) -> std::cmp::Ordering {
+        match self {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+#[cfg(feature = "sorting")]
+impl SortKey {
+    /// Compares two windows by this key alone.
+    pub fn compare(&self, a: &WindowInfo, b: &WindowInfo) -> std::cmp::Ordering {
+        match *self {
+            SortKey::Pid(order) => order.apply(a.pid.cmp(&b.pid)),
+            SortKey::Title(order) => order.apply(fold_case(&a.title).cmp(&fold_case(&b.title))),
+            SortKey::TitleNatural(order) => order.apply(natural_cmp(&a.title, &b.title)),
+            SortKey::TitleLocale(order) => order.apply(locale_cmp(&a.title, &b.title)),
+            SortKey::X(order) => order.apply(a.position.x.cmp(&b.position.x)),
+            SortKey::Y(order) => order.apply(a.position.y.cmp(&b.position.y)),
+            SortKey::Area(order) => order.apply(area(a).cmp(&area(b))),
+            SortKey::Width(order) => order.apply(a.position.width.cmp(&b.position.width)),
+            SortKey::Height(order) => order.apply(a.position.height.cmp(&b.position.height)),
+            SortKey::Memory(order) => {
+                let memory = |w: &WindowInfo| w.process_metrics.map(|m| m.working_set);
+                order.apply(memory(a).cmp(&memory(b)))
+            }
+            SortKey::StartTime(order) => {
+                let started = |w: &WindowInfo| w.process_metrics.and_then(|m| m.start_time);
+                order.apply(started(a).cmp(&started(b)))
+            }
+        }
+    }
+}
+
+/// Returns the area of a window in pixels.
+#[cfg(feature = "sorting")]
+fn area(window: &WindowInfo) -> i64 {
+    window.position.width as i64 * window.position.height as i64
+}
+
+#[cfg(feature = "sorting")]
+impl PositionSort {
+    /// Converts this position sort into the equivalent sort keys.
+    pub fn to_keys(&self) -> Vec<SortKey> {
+        let key = |order: i8, make: fn(SortOrder) -> SortKey| SortOrder::from_sign(order).map(make);
+        match *self {
+            PositionSort::X(order) => key(order, SortKey::X).into_iter().collect(),
+            PositionSort::Y(order) => key(order, SortKey::Y).into_iter().collect(),
+            PositionSort::XY(x_order, y_order) => key(x_order, SortKey::X)
+                .into_iter()
+                .chain(key(y_order, SortKey::Y))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "sorting")]
+#[allow(deprecated)]
+impl SortCriteria {
+    /// Converts these criteria into the equivalent ordered sort keys.
+    ///
+    /// Keys are produced in the legacy precedence order: PID, title,
+    /// position, area, width, then height.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use window_enumerator::{SortCriteria, SortKey, SortOrder};
+    ///
+    /// let criteria = SortCriteria { pid: -1, title: 1, ..Default::default() };
+    /// assert_eq!(
+    ///     criteria.to_keys(),
+    ///     vec![SortKey::Pid(SortOrder::Desc), SortKey::Title(SortOrder::Asc)]
+    /// );
+    /// ```
+    pub fn to_keys(&self) -> Vec<SortKey> {
+        let mut keys = Vec::new();
+        keys.extend(SortOrder::from_sign(self.pid).map(SortKey::Pid));
+        keys.extend(SortOrder::from_sign(self.title).map(SortKey::Title));
+        if let Some(ref position) = self.position {
+            keys.extend(position.to_keys());
+        }
+        keys.extend(SortOrder::from_sign(self.area).map(SortKey::Area));
+        keys.extend(SortOrder::from_sign(self.width).map(SortKey::Width));
+        keys.extend(SortOrder::from_sign(self.height).map(SortKey::Height));
+        keys
+    }
+}
+
+#[cfg(feature = "sorting")]
+#[allow(deprecated)]
+impl From<&SortCriteria> for Vec<SortKey> {
+    fn from(criteria: &SortCriteria) -> Self {
+        criteria.to_keys()
+    }
+}
+
+#[cfg(feature = "sorting")]
+impl std::ops::Deref for SortKeys<'_> {
+    type Target = [SortKey];
+
+    fn deref(&self) -> &[SortKey] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "sorting")]
+impl<'a> From<&'a [SortKey]> for SortKeys<'a> {
+    fn from(keys: &'a [SortKey]) -> Self {
+        Self(Cow::Borrowed(keys))
+    }
+}
+
+#[cfg(feature = "sorting")]
+impl<'a, const N: usize> From<&'a [SortKey; N]> for SortKeys<'a> {
+    fn from(keys: &'a [SortKey; N]) -> Self {
+        Self(Cow::Borrowed(keys))
+    }
+}
+
+#[cfg(feature = "sorting")]
+impl<'a> From<&'a Vec<SortKey>> for SortKeys<'a> {
+    fn from(keys: &'a Vec<SortKey>) -> Self {
+        Self(Cow::Borrowed(keys))
+    }
+}
+
+#[cfg(feature = "sorting")]
+impl From<Vec<SortKey>> for SortKeys<'_> {
+    fn from(keys: Vec<SortKey>) -> Self {
+        Self(Cow::Owned(keys))
+    }
+}
+
+#[cfg(feature = "sorting")]
+#[allow(deprecated)]
+impl From<&SortCriteria> for SortKeys<'_> {
+    /// Converts deprecated criteria with [`SortCriteria::to_keys`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use window_enumerator::{SortCriteria, WindowInfo, WindowSorter};
+    ///
+    /// let mut windows = vec![
+    ///     WindowInfo { pid: 2, ..Default::default() },
+    ///     WindowInfo { pid: 1, ..Default::default() },
+    /// ];
+    /// let criteria = SortCriteria { pid: 1, ..Default::default() };
+    /// WindowSorter::sort_windows(&mut windows, &criteria);
+    /// assert_eq!(windows[0].pid, 1);
+    /// ```
+    fn from(criteria: &SortCriteria) -> Self {
+        Self(Cow::Owned(criteria.to_keys()))
     }
 }
 
@@ -101,98 +2522,106 @@ pub struct WindowSorter;
 
 #[cfg(feature = "sorting")]
 impl WindowSorter {
-    /// Sorts a vector of windows according to the specified criteria.
+    /// Sorts a vector of windows according to the specified sort keys.
     ///
     /// # Arguments
     ///
     /// * `windows` - The windows to sort (modified in-place)
-    /// * `sort_criteria` - The criteria to use for sorting
-    pub fn sort_windows(windows: &mut [WindowInfo], sort_criteria: &SortCriteria) {
-        // ← 修改参数类型为切片
-        if sort_criteria.pid == 0 && sort_criteria.title == 0 && sort_criteria.position.is_none() {
+    /// * `sort_keys` - The keys to sort by, in order of precedence; a
+    ///   deprecated [`SortCriteria`] is accepted as well
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{SortKey, SortOrder, WindowInfo, WindowPosition, WindowSorter};
+    ///
+    /// let small = WindowInfo {
+    ///     position: WindowPosition { x: 0, y: 0, width: 10, height: 10 },
+    ///     ..Default::default()
+    /// };
+    /// let large = WindowInfo {
+    ///     position: WindowPosition { x: 0, y: 0, width: 800, height: 600 },
+    ///     ..Default::default()
+    /// };
+    /// let mut windows = vec![small, large];
+    /// WindowSorter::sort_windows(&mut windows, &[SortKey::Area(SortOrder::Desc)]);
+    /// assert_eq!(windows[0].position.width, 800);
+    /// ```
+    pub fn sort_windows<'k>(windows: &mut [WindowInfo], sort_keys: impl Into<SortKeys<'k>>) {
+        let sort_keys = sort_keys.into();
+        if sort_keys.is_empty() {
             return; // No sorting criteria
         }
 
-        windows.sort_by(|a, b| {
-            let mut ordering = std::cmp::Ordering::Equal;
+        windows.sort_by(|a, b| Self::compare(a, b, &sort_keys));
+    }
 
-            // PID sorting
-            if sort_criteria.pid != 0 {
-                ordering = a.pid.cmp(&b.pid);
-                if sort_criteria.pid < 0 {
-                    ordering = ordering.reverse();
-                }
-                if ordering != std::cmp::Ordering::Equal {
-                    return ordering;
-                }
-            }
+    /// Sorts a slice of window references according to the specified sort keys.
+    ///
+    /// This is the borrowing counterpart of [`sort_windows`](Self::sort_windows),
+    /// useful when the windows themselves should not be cloned.
+    ///
+    /// # Arguments
+    ///
+    /// * `windows` - The window references to sort (modified in-place)
+    /// * `sort_keys` - The keys to sort by, in order of precedence
+    pub fn sort_window_refs(windows: &mut [&WindowInfo], sort_keys: &[SortKey]) {
+        if sort_keys.is_empty() {
+            return; // No sorting criteria
+        }
 
-            // Title sorting
-            if sort_criteria.title != 0 {
-                ordering = a.title.to_lowercase().cmp(&b.title.to_lowercase());
-                if sort_criteria.title < 0 {
-                    ordering = ordering.reverse();
-                }
-                if ordering != std::cmp::Ordering::Equal {
-                    return ordering;
-                }
-            }
+        windows.sort_by(|a, b| Self::compare(a, b, sort_keys));
+    }
 
-            // Position sorting
-            if let Some(ref position_sort) = sort_criteria.position {
-                ordering = Self::compare_positions(a, b, position_sort);
-                if ordering != std::cmp::Ordering::Equal {
-                    return ordering;
-                }
-            }
+    /// Sorts windows with a caller-supplied comparator.
+    ///
+    /// Use this when the built-in [`SortKey`]s do not cover the desired
+    /// ordering, e.g. distance from a point. The sort is stable.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowInfo, WindowPosition, WindowSorter};
+    ///
+    /// let near = WindowInfo {
+    ///     position: WindowPosition { x: 10, y: 10, width: 100, height: 100 },
+    ///     ..Default::default()
+    /// };
+    /// let far = WindowInfo {
+    ///     position: WindowPosition { x: 900, y: 700, width: 100, height: 100 },
+    ///     ..Default::default()
+    /// };
+    /// let distance = |w: &WindowInfo| w.position.x.pow(2) + w.position.y.pow(2);
+    ///
+    /// let mut windows = vec![far, near];
+    /// WindowSorter::sort_windows_by(&mut windows, |a, b| distance(a).cmp(&distance(b)));
+    /// assert_eq!(windows[0].position.x, 10);
+    /// ```
+    pub fn sort_windows_by<F>(windows: &mut [WindowInfo], mut compare: F)
+    where
+        F: FnMut(&WindowInfo, &WindowInfo) -> std::cmp::Ordering,
+    {
+        windows.sort_by(|a, b| compare(a, b));
+    }
 
-            ordering
-        });
-    }
-
-    /// Compares two windows based on position sorting criteria.
-    fn compare_positions(
-        a: &WindowInfo,
-        b: &WindowInfo,
-        position_sort: &PositionSort,
-    ) -> std::cmp::Ordering {
-        match position_sort {
-            PositionSort::X(order) => {
-                let ordering = a.position.x.cmp(&b.position.x);
-                if *order < 0 {
-                    ordering.reverse()
-                } else {
-                    ordering
-                }
-            }
-            PositionSort::Y(order) => {
-                let ordering = a.position.y.cmp(&b.position.y);
-                if *order < 0 {
-                    ordering.reverse()
-                } else {
-                    ordering
-                }
-            }
-            PositionSort::XY(x_order, y_order) => {
-                // Sort by X first
-                let x_ordering = a.position.x.cmp(&b.position.x);
-                if x_ordering != std::cmp::Ordering::Equal {
-                    return if *x_order < 0 {
-                        x_ordering.reverse()
-                    } else {
-                        x_ordering
-                    };
-                }
+    /// Sorts window references with a caller-supplied comparator.
+    ///
+    /// This is the borrowing counterpart of
+    /// [`sort_windows_by`](Self::sort_windows_by).
+    pub fn sort_window_refs_by<F>(windows: &mut [&WindowInfo], mut compare: F)
+    where
+        F: FnMut(&WindowInfo, &WindowInfo) -> std::cmp::Ordering,
+    {
+        windows.sort_by(|a, b| compare(a, b));
+    }
 
-                // If X is equal, sort by Y
-                let y_ordering = a.position.y.cmp(&b.position.y);
-                if *y_order < 0 {
-                    y_ordering.reverse()
-                } else {
-                    y_ordering
-                }
-            }
-        }
+    /// Compares two windows according to the specified sort keys.
+    ///
+    /// The first key that distinguishes the windows decides the ordering.
+    pub fn compare(a: &WindowInfo, b: &WindowInfo, sort_keys: &[SortKey]) -> std::cmp::Ordering {
+        sort_keys
+            .iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| *ordering != std::cmp::Ordering::Equal)
+            .unwrap_or(std::cmp::Ordering::Equal)
     }
 
     /// Filters and sorts windows according to the specified criteria.
@@ -201,15 +2630,16 @@ impl WindowSorter {
     ///
     /// * `windows` - The windows to filter and sort
     /// * `criteria` - The filter criteria
-    /// * `sort_criteria` - The sort criteria
+    /// * `sort_keys` - The keys to sort by, in order of precedence; a
+    ///   deprecated [`SortCriteria`] is accepted as well
     ///
     /// # Returns
     ///
     /// A new vector containing the filtered and sorted windows.
-    pub fn filter_and_sort_windows(
+    pub fn filter_and_sort_windows<'k>(
         windows: &[WindowInfo],
         criteria: &crate::types::FilterCriteria,
-        sort_criteria: &SortCriteria,
+        sort_keys: impl Into<SortKeys<'k>>,
     ) -> Vec<WindowInfo> {
         let mut filtered: Vec<WindowInfo> = windows
             .iter()
@@ -217,7 +2647,32 @@ impl WindowSorter {
             .cloned()
             .collect();
 
-        Self::sort_windows(&mut filtered, sort_criteria);
+        Self::sort_windows(&mut filtered, sort_keys);
+        filtered
+    }
+
+    /// Filters and sorts windows without cloning them.
+    ///
+    /// # Arguments
+    ///
+    /// * `windows` - The windows to filter and sort
+    /// * `criteria` - The filter criteria
+    /// * `sort_keys` - The keys to sort by, in order of precedence
+    ///
+    /// # Returns
+    ///
+    /// A vector of references to the filtered and sorted windows.
+    pub fn filter_and_sort_window_refs<'a>(
+        windows: &'a [WindowInfo],
+        criteria: &crate::types::FilterCriteria,
+        sort_keys: &[SortKey],
+    ) -> Vec<&'a WindowInfo> {
+        let mut filtered: Vec<&WindowInfo> = windows
+            .iter()
+            .filter(|window| matches_criteria(window, criteria))
+            .collect();
+
+        Self::sort_window_refs(&mut filtered, sort_keys);
         filtered
     }
 }
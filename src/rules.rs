@@ -0,0 +1,134 @@
+use crate::errors::{Result, WindowError};
+use crate::models::WindowSorter;
+use crate::types::{
+    FilterCriteria, RuleMatches, Selection, SortKey, WindowInfo, WindowRule, WindowRuleSet,
+};
+use crate::utils::{max_index, parse_query};
+
+impl WindowRule {
+    /// Creates a rule from a name and a query string.
+    pub fn new(name: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            query: query.into(),
+        }
+    }
+}
+
+impl WindowRuleSet {
+    /// Creates a rule set from a list of rules.
+    pub fn new(rules: Vec<WindowRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Checks that the query of every rule is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::InvalidRule`] for the first invalid rule.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowError, WindowRule, WindowRuleSet};
+    ///
+    /// let rules = WindowRuleSet::new(vec![WindowRule::new("broken", "size>10")]);
+    /// assert!(matches!(
+    ///     rules.validate(),
+    ///     Err(WindowError::InvalidRule { name, .. }) if name == "broken"
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        for rule in &self.rules {
+            parse_rule(rule)?;
+        }
+        Ok(())
+    }
+
+    /// Evaluates every rule against a list of windows.
+    ///
+    /// # Returns
+    ///
+    /// The matches of each rule, in the order of the rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::InvalidRule`] for the first invalid rule.
+    ///
+    /// # Examples
+    /// ```
+    /// use window_enumerator::{WindowInfo, WindowRule, WindowRuleSet};
+    ///
+    /// let rules = WindowRuleSet::new(vec![
+    ///     WindowRule::new("editor", "process=code.exe"),
+    ///     WindowRule::new("first", "sort:title select:1"),
+    /// ]);
+    /// let windows = vec![
+    ///     WindowInfo {
+    ///         title: "b".to_string(),
+    ///         process_name: "Code.exe".to_string(),
+    ///         index: 1,
+    ///         ..Default::default()
+    ///     },
+    ///     WindowInfo {
+    ///         title: "a".to_string(),
+    ///         index: 2,
+    ///         ..Default::default()
+    ///     },
+    /// ];
+    ///
+    /// let matches = rules.evaluate_windows(&windows).unwrap();
+    /// assert_eq!(matches[0].name, "editor");
+    /// assert_eq!(matches[0].windows.len(), 1);
+    /// assert_eq!(matches[1].windows[0].title, "a");
+    /// ```
+    pub fn evaluate_windows(&self, windows: &[WindowInfo]) -> Result<Vec<RuleMatches>> {
+        self.rules
+            .iter()
+            .map(|rule| {
+                let (criteria, sort_keys, selection) = parse_rule(rule)?;
+                let matched =
+                    WindowSorter::filter_and_sort_window_refs(windows, &criteria, &sort_keys);
+                Ok(RuleMatches {
+                    name: rule.name.clone(),
+                    windows: selection
+                        .select(matched, max_index(windows))
+                        .into_iter()
+                        .cloned()
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Evaluates every rule against the windows of an enumerator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::InvalidRule`] for the first invalid rule.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{WindowEnumerator, WindowRule, WindowRuleSet};
+    ///
+    /// let rules = WindowRuleSet::new(vec![WindowRule::new("terminal", "class=CASCADIA_HOSTING_WINDOW_CLASS")]);
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// for matches in rules.evaluate(&enumerator).unwrap() {
+    ///     println!("{}: {} windows", matches.name, matches.windows.len());
+    /// }
+    /// ```
+    #[cfg(feature = "windows")]
+    pub fn evaluate(&self, enumerator: &crate::WindowEnumerator) -> Result<Vec<RuleMatches>> {
+        self.evaluate_windows(enumerator.get_windows())
+    }
+}
+
+/// Parses the query of a rule, naming the rule in errors.
+fn parse_rule(rule: &WindowRule) -> Result<(FilterCriteria, Vec<SortKey>, Selection)> {
+    parse_query(&rule.query).map_err(|reason| WindowError::InvalidRule {
+        name: rule.name.clone(),
+        reason: Box::new(reason),
+    })
+}
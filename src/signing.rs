@@ -0,0 +1,201 @@
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+
+use windows::core::*;
+use windows::Win32::Foundation::*;
+use windows::Win32::Security::Cryptography::Catalog::*;
+use windows::Win32::Security::Cryptography::*;
+use windows::Win32::Security::WinTrust::*;
+use windows::Win32::Storage::FileSystem::*;
+
+use crate::types::SignatureStatus;
+
+/// Verifies the Authenticode signature of a file, falling back to the
+/// system catalogs for files signed that way, like most Windows binaries.
+///
+/// Returns the status and the publisher of the signing certificate, or
+/// `None` if the path is empty or the file cannot be opened. Revocation is
+/// not checked, so verification never goes to the network.
+pub(crate) unsafe fn verify_file(path: &Path) -> Option<(SignatureStatus, Option<String>)> {
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+
+    let mut file_info = WINTRUST_FILE_INFO {
+        cbStruct: std::mem::size_of::<WINTRUST_FILE_INFO>() as u32,
+        pcwszFilePath: PCWSTR(path.as_ptr()),
+        ..Default::default()
+    };
+    let data = WINTRUST_DATA {
+        dwUnionChoice: WTD_CHOICE_FILE,
+        Anonymous: WINTRUST_DATA_0 {
+            pFile: &mut file_info,
+        },
+        ..trust_data()
+    };
+    let (result, publisher) = verify(data);
+    if result != TRUST_E_NOSIGNATURE && result != TRUST_E_SUBJECT_FORM_UNKNOWN {
+        return Some((status_from(result), publisher));
+    }
+
+    verify_catalog(&path)
+}
+
+/// Verifies a file through the catalog listing its hash, if any.
+unsafe fn verify_catalog(path: &[u16]) -> Option<(SignatureStatus, Option<String>)> {
+    let file = CreateFileW(
+        PCWSTR(path.as_ptr()),
+        GENERIC_READ.0,
+        FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+        None,
+        OPEN_EXISTING,
+        FILE_ATTRIBUTE_NORMAL,
+        None,
+    )
+    .ok()?;
+
+    let mut admin = 0isize;
+    let result = if CryptCATAdminAcquireContext2(&mut admin, None, w!("SHA256"), None, 0).is_ok() {
+        let result = verify_catalog_member(admin, file, path);
+        CryptCATAdminReleaseContext(admin, 0);
+        result
+    } else {
+        None
+    };
+    CloseHandle(file).ok();
+    result
+}
+
+/// Looks up the catalog containing the hash of an open file and verifies
+/// the file as a member of it.
+unsafe fn verify_catalog_member(
+    admin: isize,
+    file: HANDLE,
+    path: &[u16],
+) -> Option<(SignatureStatus, Option<String>)> {
+    // The first call only reports the hash size
+    let mut hash_len = 0u32;
+    let _ = CryptCATAdminCalcHashFromFileHandle2(admin, file, &mut hash_len, None, 0);
+    let mut hash = vec![0u8; hash_len as usize];
+    CryptCATAdminCalcHashFromFileHandle2(admin, file, &mut hash_len, Some(hash.as_mut_ptr()), 0)
+        .ok()?;
+
+    let catalog = CryptCATAdminEnumCatalogFromHash(admin, &hash, 0, None);
+    if catalog == 0 {
+        return Some((SignatureStatus::NotSigned, None));
+    }
+    let mut catalog_info = CATALOG_INFO {
+        cbStruct: std::mem::size_of::<CATALOG_INFO>() as u32,
+        ..Default::default()
+    };
+    let found = CryptCATCatalogInfoFromContext(catalog, &mut catalog_info, 0).is_ok();
+    CryptCATAdminReleaseCatalogContext(admin, catalog, 0);
+    if !found {
+        return Some((SignatureStatus::NotSigned, None));
+    }
+
+    // Catalog members are tagged with the uppercase hex hash
+    let tag: Vec<u16> = hash
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<String>()
+        .encode_utf16()
+        .chain(Some(0))
+        .collect();
+    let mut member = WINTRUST_CATALOG_INFO {
+        cbStruct: std::mem::size_of::<WINTRUST_CATALOG_INFO>() as u32,
+        pcwszCatalogFilePath: PCWSTR(catalog_info.wszCatalogFile.as_ptr()),
+        pcwszMemberTag: PCWSTR(tag.as_ptr()),
+        pcwszMemberFilePath: PCWSTR(path.as_ptr()),
+        hMemberFile: file,
+        pbCalculatedFileHash: hash.as_mut_ptr(),
+        cbCalculatedFileHash: hash_len,
+        hCatAdmin: admin,
+        ..Default::default()
+    };
+    let data = WINTRUST_DATA {
+        dwUnionChoice: WTD_CHOICE_CATALOG,
+        Anonymous: WINTRUST_DATA_0 {
+            pCatalog: &mut member,
+        },
+        ..trust_data()
+    };
+    let (result, publisher) = verify(data);
+    Some((status_from(result), publisher))
+}
+
+/// Returns verification settings without UI, revocation checks, or
+/// network retrieval.
+fn trust_data() -> WINTRUST_DATA {
+    WINTRUST_DATA {
+        cbStruct: std::mem::size_of::<WINTRUST_DATA>() as u32,
+        dwUIChoice: WTD_UI_NONE,
+        fdwRevocationChecks: WTD_REVOKE_NONE,
+        dwStateAction: WTD_STATEACTION_VERIFY,
+        dwProvFlags: WTD_CACHE_ONLY_URL_RETRIEVAL,
+        ..Default::default()
+    }
+}
+
+/// Runs `WinVerifyTrust`, reading the publisher before releasing the
+/// verification state.
+unsafe fn verify(mut data: WINTRUST_DATA) -> (HRESULT, Option<String>) {
+    let mut action = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+    let result = HRESULT(WinVerifyTrust(
+        HWND::default(),
+        &mut action,
+        &mut data as *mut _ as *mut _,
+    ));
+    let publisher = signer_name(data.hWVTStateData);
+
+    data.dwStateAction = WTD_STATEACTION_CLOSE;
+    WinVerifyTrust(HWND::default(), &mut action, &mut data as *mut _ as *mut _);
+    (result, publisher)
+}
+
+/// Reads the subject name of the signing certificate from the state of a
+/// verification.
+unsafe fn signer_name(state: HANDLE) -> Option<String> {
+    let provider = WTHelperProvDataFromStateData(state);
+    if provider.is_null() {
+        return None;
+    }
+    let signer = WTHelperGetProvSignerFromChain(provider, 0, false, 0);
+    if signer.is_null() || (*signer).csCertChain == 0 || (*signer).pasCertChain.is_null() {
+        return None;
+    }
+    let certificate = (*(*signer).pasCertChain).pCert;
+    if certificate.is_null() {
+        return None;
+    }
+
+    let len = CertGetNameStringW(certificate, CERT_NAME_SIMPLE_DISPLAY_TYPE, 0, None, None);
+    let mut buffer = vec![0u16; len as usize];
+    let len = CertGetNameStringW(
+        certificate,
+        CERT_NAME_SIMPLE_DISPLAY_TYPE,
+        0,
+        None,
+        Some(&mut buffer),
+    );
+    // The length includes the terminator
+    let name = String::from_utf16_lossy(&buffer[..(len as usize).saturating_sub(1)]);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Maps a `WinVerifyTrust` result to a signature status.
+fn status_from(result: HRESULT) -> SignatureStatus {
+    match result {
+        S_OK => SignatureStatus::Valid,
+        TRUST_E_NOSIGNATURE | TRUST_E_SUBJECT_FORM_UNKNOWN | TRUST_E_PROVIDER_UNKNOWN => {
+            SignatureStatus::NotSigned
+        }
+        CERT_E_UNTRUSTEDROOT | CERT_E_CHAINING | TRUST_E_SUBJECT_NOT_TRUSTED => {
+            SignatureStatus::Untrusted
+        }
+        CERT_E_EXPIRED => SignatureStatus::Expired,
+        TRUST_E_EXPLICIT_DISTRUST => SignatureStatus::Distrusted,
+        _ => SignatureStatus::Invalid,
+    }
+}
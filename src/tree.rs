@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::enumerator::WindowEnumerator;
+use crate::types::{TreeRelation, WindowHandle, WindowTree, WindowTreeNode};
+
+/// Capturing the live window hierarchy.
+impl WindowTree {
+    /// Captures every window on the current desktop, hidden ones included:
+    /// unowned top-level windows at the top, their owned windows and child
+    /// windows below them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::WindowTree;
+    ///
+    /// let tree = WindowTree::capture();
+    /// std::fs::write("windows.dot", tree.to_dot()).unwrap();
+    /// ```
+    pub fn capture() -> Self {
+        unsafe {
+            let owners = top_level_owners();
+            let present: HashSet<isize> = owners.iter().map(|(hwnd, _)| hwnd.0).collect();
+            let mut visited = HashSet::new();
+            let mut roots = Vec::new();
+            for &(hwnd, owner) in &owners {
+                if (owner.0 == 0 || !present.contains(&owner.0)) && visited.insert(hwnd.0) {
+                    roots.push(build_node(hwnd, TreeRelation::Root, &owners, &mut visited));
+                }
+            }
+            Self { roots }
+        }
+    }
+
+    /// Captures a single window with its child windows and the windows it
+    /// owns, e.g. to see which of an application's windows a filter should
+    /// target.
+    ///
+    /// Returns an empty tree if the window does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{WindowEnumerator, WindowTree};
+    ///
+    /// let foreground = WindowEnumerator::new().foreground_window().unwrap();
+    /// print!("{}", WindowTree::capture_window(foreground.hwnd));
+    /// ```
+    pub fn capture_window(hwnd: WindowHandle) -> Self {
+        let hwnd = HWND::from(hwnd);
+        unsafe {
+            if !IsWindow(hwnd).as_bool() {
+                return Self::default();
+            }
+            let owners = top_level_owners();
+            let mut visited = HashSet::from([hwnd.0]);
+            Self {
+                roots: vec![build_node(hwnd, TreeRelation::Root, &owners, &mut visited)],
+            }
+        }
+    }
+}
+
+/// Lists every top-level window with its owner (a null handle if unowned).
+unsafe fn top_level_owners() -> Vec<(HWND, HWND)> {
+    let mut windows: Vec<HWND> = Vec::new();
+    let _ = EnumWindows(
+        Some(collect_window_proc),
+        LPARAM(&mut windows as *mut _ as isize),
+    );
+    windows
+        .into_iter()
+        .map(|hwnd| (hwnd, GetWindow(hwnd, GW_OWNER)))
+        .collect()
+}
+
+/// Collects every top-level window into the `Vec<HWND>` behind `lparam`.
+unsafe extern "system" fn collect_window_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let windows = &mut *(lparam.0 as *mut Vec<HWND>);
+    windows.push(hwnd);
+    TRUE
+}
+
+/// Builds the node of a window with its children in Z order, followed by
+/// the windows it owns.
+///
+/// Windows already in `visited` are skipped, so a window destroyed and
+/// recycled mid-walk cannot make the walk loop.
+unsafe fn build_node(
+    hwnd: HWND,
+    relation: TreeRelation,
+    owners: &[(HWND, HWND)],
+    visited: &mut HashSet<isize>,
+) -> WindowTreeNode {
+    let (_, pid) = WindowEnumerator::get_thread_process_id(hwnd);
+    let mut node = WindowTreeNode {
+        hwnd: hwnd.into(),
+        title: WindowEnumerator::get_window_text(hwnd),
+        class_name: WindowEnumerator::get_class_name(hwnd),
+        pid,
+        is_visible: IsWindowVisible(hwnd).as_bool(),
+        relation,
+        children: Vec::new(),
+    };
+
+    let mut child = GetWindow(hwnd, GW_CHILD);
+    while child.0 != 0 && visited.insert(child.0) {
+        node.children
+            .push(build_node(child, TreeRelation::Child, owners, visited));
+        child = GetWindow(child, GW_HWNDNEXT);
+    }
+
+    // Only top-level windows can own windows
+    if relation != TreeRelation::Child {
+        for &(owned, owner) in owners {
+            if owner == hwnd && visited.insert(owned.0) {
+                node.children
+                    .push(build_node(owned, TreeRelation::Owned, owners, visited));
+            }
+        }
+    }
+    node
+}
@@ -1,7 +1,19 @@
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// A type-safe window handle (HWND).
+///
+/// Wraps the raw handle value so it cannot be confused with other
+/// handle-like integers such as process IDs or monitor handles. Displays
+/// as a hexadecimal value (e.g. `0x1a2b`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WindowHandle(pub isize);
 
 /// Represents a window's position and dimensions on the screen.
-#[derive(Debug, Clone, Copy, Default)] // ← 添加 Default derive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)] // ← 添加 Default derive
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowPosition {
     /// The x-coordinate of the window's top-left corner in screen coordinates.
     pub x: i32,
@@ -15,25 +27,1057 @@ pub struct WindowPosition {
 
 // 删除手动实现的 Default for WindowPosition
 
-/// Comprehensive information about a Windows window.
+/// Additional geometry of a window beyond its outer window rectangle.
+///
+/// On Windows 10 and later, the window rectangle includes invisible
+/// resize borders and drop shadows; these rectangles describe what is
+/// actually visible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WindowGeometry {
+    /// The client area in screen coordinates.
+    pub client: WindowPosition,
+    /// The visible frame bounds reported by DWM (`DWMWA_EXTENDED_FRAME_BOUNDS`).
+    ///
+    /// Falls back to the window rectangle when DWM does not report them.
+    pub frame_bounds: WindowPosition,
+}
+
+/// Controls which windows are enumerated and which per-window properties
+/// are gathered for them.
+///
+/// Skipping expensive properties makes enumeration considerably faster:
+/// process information requires opening a handle to every owning process.
+/// Skipped properties keep their default values and can be loaded later
+/// on demand (e.g. with [`WindowInfo::load_process_info`]).
 #[derive(Debug, Clone)]
+pub struct EnumerationOptions {
+    /// Gather process name, executable path, and command line.
+    pub include_process_info: bool,
+    /// Gather window position, size, geometry, and monitor.
+    pub include_position: bool,
+    /// Gather window style flags and DWM cloaking state.
+    pub include_styles: bool,
+    /// Gather the registration of each window class into
+    /// [`WindowInfo::class_details`].
+    ///
+    /// Off by default. Reading it needs no access to the owning process.
+    pub include_class_details: bool,
+    /// Include top-level windows that have an owner, such as dialogs and
+    /// secondary popups of an application.
+    pub include_owned_windows: bool,
+    /// Include windows that are not visible, such as applications minimized
+    /// to the notification area.
+    pub include_hidden: bool,
+    /// Include minimized windows.
+    pub include_minimized: bool,
+    /// Include windows cloaked by DWM, such as windows on other virtual
+    /// desktops and suspended UWP apps.
+    ///
+    /// On by default. Excluding them costs one DWM query per window.
+    pub include_cloaked: bool,
+    /// Truncate window titles to at most this many UTF-16 code units.
+    ///
+    /// `None` (the default) retrieves titles of any length.
+    pub max_title_length: Option<usize>,
+    /// Retrieve titles with `WM_GETTEXT` via `SendMessageTimeoutW`, giving
+    /// up after this long.
+    ///
+    /// Windows that do not answer in time get an empty title and are marked
+    /// as [`WindowInfo::is_hung`]. `None` (the default) uses
+    /// `GetWindowTextW`.
+    pub title_timeout: Option<Duration>,
+    /// Resolve the account owning each process into
+    /// [`WindowInfo::user_name`].
+    ///
+    /// Off by default: looking up an account name may query a domain
+    /// controller. Requires `include_process_info`.
+    pub include_user_name: bool,
+    /// Enumerate the windows of the named desktop, e.g. `"Default"`,
+    /// instead of the desktop of the calling thread.
+    ///
+    /// The desktop must belong to the window station of the calling
+    /// process; a service cannot reach the desktops of another session this
+    /// way. `None` (the default) uses the calling thread's desktop.
+    pub desktop: Option<String>,
+    /// Stop gathering window information once this much time has passed.
+    ///
+    /// The windows gathered so far are kept and the enumeration returns
+    /// [`WindowError::TimedOut`](crate::WindowError::TimedOut). The limit is
+    /// checked between windows, so a single blocking query can still exceed
+    /// it; use `title_timeout` for windows that may hang.
+    pub timeout: Option<Duration>,
+    /// Stop gathering window information once this token is cancelled.
+    ///
+    /// Like `timeout`, but the enumeration returns
+    /// [`WindowError::Cancelled`](crate::WindowError::Cancelled).
+    pub cancellation: Option<CancellationToken>,
+    /// Gather memory, CPU, handle, and thread figures of each process into
+    /// [`WindowInfo::process_metrics`].
+    ///
+    /// Off by default. The figures are queried once per process and
+    /// enumeration, never cached across enumerations.
+    pub include_process_metrics: bool,
+    /// Read the product name, company, and file version from the version
+    /// resource of each process executable.
+    ///
+    /// Off by default; cached per process like the other process
+    /// information. Requires `include_process_info`.
+    pub include_version_info: bool,
+    /// Resolve the parent process and the chain of ancestor processes into
+    /// [`WindowInfo::parent_pid`] and [`WindowInfo::parent_chain`].
+    ///
+    /// Off by default. Takes one process snapshot per enumeration and walks
+    /// the ancestry of each process once.
+    pub include_parent_chain: bool,
+    /// Read the UI Automation name, control type, and automation ID of
+    /// each window into [`WindowInfo::uia_name`] and the related fields.
+    ///
+    /// Off by default. Hung windows are skipped, and every other window
+    /// costs a cross-process call.
+    #[cfg(feature = "uia")]
+    pub include_uia: bool,
+    /// Verify the Authenticode signature of each process executable into
+    /// [`WindowInfo::signature_status`] and [`WindowInfo::publisher`].
+    ///
+    /// Off by default; cached per process like the other process
+    /// information. Requires `include_process_info`.
+    #[cfg(feature = "signing")]
+    pub verify_signatures: bool,
+}
+
+/// A flag for cancelling an enumeration from another thread.
+///
+/// Clones share the same flag, so one clone can be passed in
+/// [`EnumerationOptions::cancellation`] while another is cancelled, e.g.
+/// when the user closes a dialog.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(pub(crate) Arc<AtomicBool>);
+
+impl Default for EnumerationOptions {
+    fn default() -> Self {
+        Self {
+            include_process_info: true,
+            include_position: true,
+            include_styles: true,
+            include_class_details: false,
+            include_owned_windows: true,
+            include_hidden: false,
+            include_minimized: true,
+            include_cloaked: true,
+            max_title_length: None,
+            title_timeout: None,
+            include_user_name: false,
+            desktop: None,
+            timeout: None,
+            cancellation: None,
+            include_process_metrics: false,
+            include_version_info: false,
+            include_parent_chain: false,
+            #[cfg(feature = "uia")]
+            include_uia: false,
+            #[cfg(feature = "signing")]
+            verify_signatures: false,
+        }
+    }
+}
+
+impl EnumerationOptions {
+    /// Options that gather only the handle, PID, title, and class name.
+    pub fn minimal() -> Self {
+        Self {
+            include_process_info: false,
+            include_position: false,
+            include_styles: false,
+            include_class_details: false,
+            include_owned_windows: true,
+            include_hidden: false,
+            include_minimized: true,
+            include_cloaked: true,
+            max_title_length: None,
+            title_timeout: None,
+            include_user_name: false,
+            desktop: None,
+            timeout: None,
+            cancellation: None,
+            include_process_metrics: false,
+            include_version_info: false,
+            include_parent_chain: false,
+            #[cfg(feature = "uia")]
+            include_uia: false,
+            #[cfg(feature = "signing")]
+            verify_signatures: false,
+        }
+    }
+}
+
+/// Information about a display monitor.
+#[derive(Debug, Clone, Default)]
+pub struct MonitorInfo {
+    /// The monitor handle (HMONITOR) as an isize.
+    pub handle: isize,
+    /// The 1-based index of this monitor in enumeration results.
+    pub index: usize,
+    /// The device name of the monitor (e.g. `\\.\DISPLAY1`).
+    pub device_name: String,
+    /// The full bounds of the monitor in virtual-screen coordinates.
+    pub bounds: WindowPosition,
+    /// The work area of the monitor, excluding the taskbar and docked toolbars.
+    pub work_area: WindowPosition,
+    /// Whether this is the primary monitor.
+    pub is_primary: bool,
+}
+
+/// Comprehensive information about a Windows window.
+#[derive(Debug, Clone, Default)]
 pub struct WindowInfo {
-    /// The window handle (HWND) as an isize.
-    pub hwnd: isize,
+    /// The window handle (HWND).
+    pub hwnd: WindowHandle,
     /// The process ID (PID) that owns the window.
     pub pid: u32,
+    /// The ID of the thread that created the window.
+    pub thread_id: u32,
     /// The window title text.
     pub title: String,
     /// The window class name.
     pub class_name: String,
     /// The name of the process executable.
     pub process_name: String,
-    /// The full path to the process executable file.
+    /// The full Win32 path to the process executable file
+    /// (e.g. `C:\Windows\explorer.exe`).
     pub process_file: PathBuf,
+    /// The NT device path to the process executable file
+    /// (e.g. `\Device\HarddiskVolume3\Windows\explorer.exe`).
+    pub process_device_path: PathBuf,
+    /// The Application User Model ID used for taskbar grouping and pinning.
+    ///
+    /// Taken from the window's shell property store (with the `aumid`
+    /// feature), otherwise from the owning packaged process or the hosted
+    /// UWP app. Only gathered together with the process information.
+    pub aumid: Option<String>,
+    /// The name of the window's UI Automation element, as read by screen
+    /// readers.
+    ///
+    /// Often meaningful where the Win32 title is empty, e.g. for apps that
+    /// draw their own title bar. Only read with the `uia` feature, through
+    /// `EnumerationOptions::include_uia` or `WindowInfo::load_uia_info`,
+    /// like `uia_control_type` and `uia_automation_id`.
+    pub uia_name: Option<String>,
+    /// The control type of the window's UI Automation element, e.g.
+    /// `Window` or `Pane`.
+    pub uia_control_type: Option<String>,
+    /// The automation ID of the window's UI Automation element, which UI
+    /// frameworks usually derive from the name of the window in code.
+    pub uia_automation_id: Option<String>,
+    /// The UWP app behind an `ApplicationFrameWindow`, whose own process
+    /// is always `ApplicationFrameHost.exe`.
+    ///
+    /// Only resolved together with the process information, and only while
+    /// the app is not minimized (minimized apps detach their core window
+    /// from the frame).
+    pub uwp_app: Option<UwpAppInfo>,
+    /// The process attached to a console window, whose own process is
+    /// `conhost.exe` or, for Windows Terminal, `WindowsTerminal.exe`.
+    ///
+    /// Only resolved together with the process information. For a terminal
+    /// with several tabs this is the most recently started shell.
+    pub console_client_pid: Option<u32>,
+    /// Why the process information above could not be retrieved, if it
+    /// could not.
+    ///
+    /// Distinguishes e.g. access being denied for an elevated process
+    /// (see [`WindowError::is_access_denied`](crate::WindowError::is_access_denied))
+    /// from a process name that is genuinely empty.
+    pub process_info_error: Option<crate::errors::WindowError>,
+    /// Whether the owning process runs elevated ("as administrator").
+    ///
+    /// `None` if the process token could not be queried, which usually
+    /// means the process is protected or runs as another user.
+    pub is_elevated: Option<bool>,
+    /// The mandatory integrity level of the owning process.
+    ///
+    /// User Interface Privilege Isolation (UIPI) blocks input and most
+    /// messages sent to windows of a higher integrity level than the sender.
+    pub integrity_level: Option<IntegrityLevel>,
+    /// The architecture the owning process runs as, e.g. to match the
+    /// bitness of a helper before attaching to the process.
+    ///
+    /// `None` if the process cannot be queried.
+    pub process_arch: Option<ProcessArch>,
+    /// The account owning the process, as `DOMAIN\user`.
+    ///
+    /// Only resolved with [`EnumerationOptions::include_user_name`] or
+    /// [`WindowInfo::load_user_name`]; `None` otherwise or if the process
+    /// token could not be queried.
+    pub user_name: Option<String>,
+    /// The Terminal Services session the owning process runs in.
+    ///
+    /// Session 0 hosts services; each logged-on user gets their own session.
+    pub session_id: Option<u32>,
+    /// The process that started the owning process, which may have exited
+    /// since.
+    ///
+    /// Only resolved with [`EnumerationOptions::include_parent_chain`] or
+    /// [`WindowInfo::load_parent_chain`], like `parent_chain`.
+    pub parent_pid: Option<u32>,
+    /// The executable names of the ancestors of the owning process, from
+    /// the oldest running ancestor down to the direct parent, e.g.
+    /// `["explorer.exe", "cmd.exe", "python.exe"]` for a window of a
+    /// program started from a Python script.
+    ///
+    /// The chain ends at the first ancestor that has exited, or whose PID
+    /// was reused by a process started later.
+    pub parent_chain: Option<Vec<String>>,
+    /// Resource usage of the owning process.
+    ///
+    /// Only gathered with [`EnumerationOptions::include_process_metrics`] or
+    /// [`WindowInfo::load_process_metrics`]; `None` otherwise or if the
+    /// process cannot be queried.
+    pub process_metrics: Option<ProcessMetrics>,
+    /// The product name from the version resource of the executable, e.g.
+    /// `Microsoft Office`.
+    ///
+    /// Only read with [`EnumerationOptions::include_version_info`] or
+    /// [`WindowInfo::load_version_info`], like `company_name` and
+    /// `file_version`.
+    pub product_name: Option<String>,
+    /// The company name from the version resource of the executable.
+    pub company_name: Option<String>,
+    /// The file version from the version resource of the executable, e.g.
+    /// `16.0.17928.20114`.
+    pub file_version: Option<String>,
+    /// The result of verifying the Authenticode signature of the executable,
+    /// either embedded or from a system catalog.
+    ///
+    /// Only verified with the `signing` feature, through
+    /// `EnumerationOptions::verify_signatures` or
+    /// `WindowInfo::load_signature`; `None` otherwise or if the executable
+    /// is unknown.
+    pub signature_status: Option<SignatureStatus>,
+    /// The subject name of the certificate the executable is signed with,
+    /// e.g. `Microsoft Corporation`.
+    pub publisher: Option<String>,
+    /// The command line the process was launched with.
+    ///
+    /// Only populated with the `command-line` feature; `None` otherwise or if
+    /// the process cannot be queried.
+    pub process_command_line: Option<String>,
     /// The 1-based index of this window in enumeration results.
     pub index: usize,
     /// The position and dimensions of the window.
     pub position: WindowPosition,
+    /// The client area and visible frame bounds of the window.
+    pub geometry: WindowGeometry,
+    /// The handle (HMONITOR) of the monitor the window is mostly on.
+    pub monitor_handle: isize,
+    /// The 1-based index of the monitor the window is mostly on, if known.
+    pub monitor_index: Option<usize>,
+    /// The full bounds of the monitor the window is mostly on, gathered
+    /// together with the position.
+    pub monitor_bounds: WindowPosition,
+    /// The window style flags (`WS_*`, from `GWL_STYLE`).
+    pub style: u32,
+    /// The extended window style flags (`WS_EX_*`, from `GWL_EXSTYLE`).
+    pub ex_style: u32,
+    /// Whether DWM has cloaked the window.
+    ///
+    /// Cloaked windows report as visible but are not actually shown, e.g.
+    /// suspended UWP apps or windows on another virtual desktop.
+    pub is_cloaked: bool,
+    /// The registration of the window class: class styles, atom, the
+    /// registering module, and the default icon, cursor, and background.
+    ///
+    /// Helps tell UI frameworks apart, e.g. WPF registers its classes with
+    /// no background brush. Only read with
+    /// [`EnumerationOptions::include_class_details`] or
+    /// [`WindowInfo::load_class_details`].
+    pub class_details: Option<ClassDetails>,
+    /// The window that owns this one (e.g. the main window of a dialog), if any.
+    pub owner_hwnd: Option<WindowHandle>,
+    /// The root of this window's parent and owner chain
+    /// (`GetAncestor(GA_ROOTOWNER)`); the window itself if it is unowned.
+    pub root_ancestor: WindowHandle,
+    /// Whether the window is visible (`IsWindowVisible`).
+    pub is_visible: bool,
+    /// Whether the window is minimized (`IsIconic`).
+    pub is_minimized: bool,
+    /// Whether the window is not responding ("hung"), as reported by
+    /// `IsHungAppWindow` or by a timed-out title request.
+    pub is_hung: bool,
+    /// For a ghost window shown in place of a hung window, the hung window
+    /// (`HungWindowFromGhostWindow`). See [`WindowInfo::is_ghost`].
+    pub ghost_of: Option<WindowHandle>,
+    /// What kind of window this is, e.g. an application window or a piece
+    /// of the desktop shell (see [`WindowInfo::classify`]).
+    pub kind: WindowKind,
+}
+
+/// Resource usage of a process at the time it was queried.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessMetrics {
+    /// The physical memory currently used by the process, in bytes.
+    pub working_set: u64,
+    /// The memory committed for the process alone, in bytes.
+    pub private_bytes: u64,
+    /// The CPU time spent by the process so far, in user and kernel mode.
+    pub cpu_time: Duration,
+    /// The number of open kernel object handles.
+    pub handle_count: u32,
+    /// The number of threads.
+    pub thread_count: u32,
+    /// When the process was started.
+    pub start_time: Option<SystemTime>,
+}
+
+/// The outcome of verifying the Authenticode signature of an executable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureStatus {
+    /// Signed, and the signature chains to a trusted root.
+    Valid,
+    /// Neither signed itself nor listed in a system catalog.
+    NotSigned,
+    /// Signed with a certificate that does not chain to a trusted root,
+    /// such as a self-signed one.
+    Untrusted,
+    /// Signed with a certificate that has expired and no timestamp.
+    Expired,
+    /// Signed with a certificate that is explicitly distrusted.
+    Distrusted,
+    /// The signature is malformed or does not match the file, e.g. because
+    /// the file was modified after signing.
+    Invalid,
+}
+
+/// The mandatory integrity level of a process, from lowest to highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IntegrityLevel {
+    /// Untrusted processes, e.g. anonymous sessions.
+    Untrusted,
+    /// Sandboxed processes such as browser renderers.
+    Low,
+    /// Regular, non-elevated processes.
+    Medium,
+    /// Non-elevated processes with UI access (`uiAccess`).
+    MediumPlus,
+    /// Elevated processes.
+    High,
+    /// Services and system processes.
+    System,
+    /// Protected processes.
+    Protected,
+}
+
+/// The instruction set a process runs as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessArch {
+    /// 32-bit x86, natively or under WOW64.
+    X86,
+    /// 64-bit x86 (AMD64), natively or emulated on ARM64.
+    X64,
+    /// 32-bit ARM.
+    Arm,
+    /// 64-bit ARM.
+    Arm64,
+}
+
+/// How [`WindowInfo::force_close`] made a window go away, from the most
+/// to the least graceful.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CloseMethod {
+    /// The window closed after being asked to (`WM_CLOSE`).
+    Requested,
+    /// The window closed after `EndTask`, as with "End task" in the taskbar.
+    EndTask,
+    /// The owning process was terminated (`TerminateProcess`).
+    Terminated,
+}
+
+/// Whether a window is minimized, maximized, or neither.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowState {
+    /// Neither minimized nor maximized.
+    #[default]
+    Normal,
+    /// Minimized to the taskbar.
+    Minimized,
+    /// Maximized to fill its monitor's work area.
+    Maximized,
+}
+
+/// The show state and restored bounds of a window, as saved by
+/// [`WindowInfo::get_placement`] and restored by
+/// [`WindowInfo::apply_placement`].
+///
+/// With the `serde` feature the placement can be persisted, e.g. to restore
+/// a window layout later.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowPlacement {
+    /// Whether the window is minimized, maximized, or neither.
+    pub state: WindowState,
+    /// The bounds of the window when it is neither minimized nor maximized,
+    /// in workspace coordinates (which exclude docked taskbars).
+    pub normal_position: WindowPosition,
+    /// Whether a minimized window is maximized when restored.
+    pub restore_to_maximized: bool,
+}
+
+/// The role of a window, derived from its class name and styles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WindowKind {
+    /// A regular application window.
+    AppWindow,
+    /// A taskbar (`Shell_TrayWnd` or `Shell_SecondaryTrayWnd`).
+    Taskbar,
+    /// The desktop (`Progman` or `WorkerW`).
+    Desktop,
+    /// A notification area window, such as the overflow flyout.
+    Tray,
+    /// An input method editor window.
+    ImeWindow,
+    /// A tooltip.
+    Tooltip,
+    /// A popup menu.
+    Menu,
+    /// A window of the system dialog class (`#32770`), such as message boxes
+    /// and common dialogs.
+    SystemDialog,
+    /// A window that could not be classified, e.g. an untitled tool window.
+    #[default]
+    Other,
+}
+
+/// The UWP app hosted inside an `ApplicationFrameWindow`.
+///
+/// UWP apps are drawn into a frame window owned by `ApplicationFrameHost.exe`;
+/// the app itself runs in a separate process that owns the hosted
+/// `Windows.UI.Core.CoreWindow`.
+#[derive(Debug, Clone, Default)]
+pub struct UwpAppInfo {
+    /// The hosted `Windows.UI.Core.CoreWindow`.
+    pub core_window: WindowHandle,
+    /// The process ID (PID) of the app.
+    pub pid: u32,
+    /// The name of the app's process executable.
+    pub process_name: String,
+    /// The full path to the app's process executable file.
+    pub process_file: PathBuf,
+    /// The Application User Model ID of the app.
+    pub aumid: Option<String>,
+    /// The full name of the app's package.
+    pub package_full_name: Option<String>,
+}
+
+/// The kind of a standard Win32 control, as recognized by
+/// [`ControlKind::from_class_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlKind {
+    /// A text box (`Edit` or a rich edit control).
+    Edit,
+    /// A label (`Static`).
+    Static,
+    /// A push button, check box, radio button, or group box (`Button`).
+    Button,
+    /// A list box (`ListBox`).
+    ListBox,
+    /// A drop-down list (`ComboBox`).
+    ComboBox,
+}
+
+/// The text read from a child control of a window by
+/// [`WindowInfo::control_texts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlText {
+    /// The handle of the control.
+    pub hwnd: WindowHandle,
+    /// The window class name of the control.
+    pub class_name: String,
+    /// The kind of control.
+    pub kind: ControlKind,
+    /// The dialog control identifier, as used in dialog templates.
+    pub control_id: i32,
+    /// Whether the control is visible.
+    pub is_visible: bool,
+    /// The text of the control: the contents of a text box, the caption
+    /// of a label or button, or the selected text of a combo box.
+    pub text: String,
+    /// The entries of a list box or combo box; empty for other controls
+    /// and for owner-drawn lists that store no strings.
+    pub items: Vec<String>,
+}
+
+/// The windows belonging to a single process.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessWindows {
+    /// The process ID (PID).
+    pub pid: u32,
+    /// The name of the process executable.
+    pub process_name: String,
+    /// The full path to the process executable file.
+    pub process_file: PathBuf,
+    /// The windows owned by the process.
+    pub windows: Vec<WindowInfo>,
+}
+
+/// The registration of a window class, as read with `GetClassLongPtrW`.
+///
+/// Handles and addresses are only meaningful in the process owning the
+/// window, but identify e.g. which module registered a class.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClassDetails {
+    /// The atom identifying the class.
+    pub atom: u16,
+    /// The class style flags (`CS_*`).
+    pub style: u32,
+    /// The module that registered the class (`HMODULE`).
+    pub module: usize,
+    /// The window procedure of the class.
+    pub window_proc: usize,
+    /// The number of extra bytes allocated per class.
+    pub class_extra_bytes: u32,
+    /// The number of extra bytes allocated per window.
+    pub window_extra_bytes: u32,
+    /// The class icon (`HICON`), or 0.
+    pub icon: usize,
+    /// The class cursor (`HCURSOR`), or 0.
+    pub cursor: usize,
+    /// The background brush (`HBRUSH`), or 0 if the window paints its
+    /// own background.
+    pub background: usize,
+}
+
+/// A property attached to a window with `SetPropW`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowProperty {
+    /// The property name, or `#` and the atom for properties named by an
+    /// integer atom.
+    pub name: String,
+    /// The property value, usually a handle or a pointer.
+    pub value: usize,
+}
+
+/// Everything known about a single window, as gathered by
+/// `WindowEnumerator::inspect`.
+///
+/// Printing an inspection (`{}`) gives a property dump in the style of
+/// Spy++'s window properties.
+#[derive(Debug, Clone, Default)]
+pub struct WindowInspection {
+    /// The window information, with the optional properties loaded.
+    pub info: WindowInfo,
+    /// The parent window of a child window.
+    pub parent: Option<WindowHandle>,
+    /// The control identifier of a child window (`GWLP_ID`); the menu
+    /// handle of a top-level window.
+    pub control_id: isize,
+    /// The current window procedure (`GWLP_WNDPROC`), which differs from
+    /// the class procedure if the window is subclassed.
+    pub window_proc: usize,
+    /// The module instance that created the window (`GWLP_HINSTANCE`).
+    pub instance: usize,
+    /// The application-defined value of the window (`GWLP_USERDATA`).
+    pub user_data: usize,
+    /// Whether the window is a native Unicode window (`IsWindowUnicode`).
+    pub is_unicode: bool,
+    /// Whether the window accepts input (no `WS_DISABLED`).
+    pub is_enabled: bool,
+    /// The registration of the window class.
+    pub class: ClassDetails,
+    /// The properties attached to the window.
+    pub properties: Vec<WindowProperty>,
+}
+
+/// How a node of a [`WindowTree`] relates to the node above it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TreeRelation {
+    /// A top-level window without a known owner.
+    #[default]
+    Root,
+    /// A child window, drawn inside its parent.
+    Child,
+    /// A top-level window owned by the node above, e.g. a dialog.
+    Owned,
+}
+
+/// A window and the windows below it in a [`WindowTree`].
+#[derive(Debug, Clone, Default)]
+pub struct WindowTreeNode {
+    /// The window handle.
+    pub hwnd: WindowHandle,
+    /// The window title.
+    pub title: String,
+    /// The window class name.
+    pub class_name: String,
+    /// The process ID (PID) of the owning process.
+    pub pid: u32,
+    /// Whether the window is visible.
+    pub is_visible: bool,
+    /// How this window relates to the node above it.
+    pub relation: TreeRelation,
+    /// The child windows and owned windows, children first.
+    pub children: Vec<WindowTreeNode>,
+}
+
+/// The parent, owner, and child relationships of windows, like the window
+/// view of Spy++.
+///
+/// Printing a tree (`{}`) renders it as indented text;
+/// [`to_dot`](WindowTree::to_dot) renders it for Graphviz.
+#[derive(Debug, Clone, Default)]
+pub struct WindowTree {
+    /// The windows at the top of the tree.
+    pub roots: Vec<WindowTreeNode>,
+}
+
+/// An immutable, cheaply clonable snapshot of enumerated windows.
+///
+/// Cloning a `WindowList` only bumps a reference count, and the list is
+/// `Send + Sync`, so a snapshot taken from a
+/// [`WindowEnumerator`](crate::WindowEnumerator) can be shared across
+/// worker threads without copying the windows or locking the enumerator.
+#[derive(Debug, Clone, Default)]
+pub struct WindowList {
+    pub(crate) windows: Arc<[WindowInfo]>,
+}
+
+/// A custom filter of a [`WindowQuery`].
+pub(crate) type WindowPredicate<'a> = Box<dyn Fn(&WindowInfo) -> bool + 'a>;
+
+/// A lazily evaluated query over a list of windows, built by chaining
+/// filters, sort keys, a selection, and limits.
+///
+/// Nothing is evaluated until the query is iterated or collected. The steps
+/// always apply in the same order regardless of how they were chained:
+/// filters, sorting, selection, then [`skip`](Self::skip) and
+/// [`take`](Self::take). Obtained from
+/// [`WindowEnumerator::windows`](crate::WindowEnumerator::windows) or
+/// [`WindowQuery::new`].
+pub struct WindowQuery<'a> {
+    pub(crate) windows: &'a [WindowInfo],
+    pub(crate) criteria: Vec<FilterCriteria>,
+    pub(crate) predicates: Vec<WindowPredicate<'a>>,
+    #[cfg(feature = "sorting")]
+    pub(crate) sort_keys: Vec<SortKey>,
+    #[cfg(feature = "selection")]
+    pub(crate) selection: Option<Selection>,
+    pub(crate) skip: usize,
+    pub(crate) take: Option<usize>,
+}
+
+/// Statistics of the per-PID process information cache of a
+/// [`WindowEnumerator`](crate::WindowEnumerator).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessCacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that had to query the process.
+    pub misses: u64,
+    /// Processes currently cached.
+    pub entries: usize,
+}
+
+/// The changes detected by [`WindowEnumerator::refresh`](crate::WindowEnumerator::refresh).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshSummary {
+    /// Windows that appeared since the previous enumeration.
+    pub added: Vec<WindowHandle>,
+    /// Windows that disappeared since the previous enumeration.
+    pub removed: Vec<WindowHandle>,
+}
+
+/// A parsed output template for [`WindowInfo::format`].
+///
+/// Templates contain `{field}` placeholders such as `"{index}\t{pid}\t{title}"`.
+/// Supported fields are `index`, `hwnd`, `pid`, `thread_id`, `title`,
+/// `class_name`, `process_name`, `process_file`, `command_line`, `aumid`,
+/// `x`, `y`, `width`, `height`, and `monitor`. Use `{{` and `}}` for literal
+/// braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatSpec {
+    pub(crate) segments: Vec<FormatSegment>,
+}
+
+/// A literal piece or placeholder of a [`FormatSpec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FormatSegment {
+    Literal(String),
+    Field(FormatField),
+}
+
+/// A window property that can be referenced in a [`FormatSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FormatField {
+    Index,
+    Hwnd,
+    Pid,
+    ThreadId,
+    Title,
+    ClassName,
+    ProcessName,
+    ProcessFile,
+    CommandLine,
+    Aumid,
+    X,
+    Y,
+    Width,
+    Height,
+    Monitor,
+}
+
+/// The layout used by [`WindowList::to_table`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableStyle {
+    /// Columns aligned with spaces and separated by `|`.
+    #[default]
+    Plain,
+    /// A GitHub-flavored Markdown table.
+    Markdown,
+    /// Tab-separated values with a header row.
+    Tsv,
+}
+
+/// A column of a [`TablePrinter`](crate::TablePrinter).
+///
+/// Parses from its lowercase name, e.g. `"pid"` or `"class"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableColumn {
+    /// The 1-based window index.
+    Index,
+    /// The window handle.
+    Hwnd,
+    /// The process ID.
+    Pid,
+    /// The window class name.
+    ClassName,
+    /// The process executable name.
+    ProcessName,
+    /// The `x,y` position of the top-left corner.
+    Position,
+    /// The `widthxheight` size.
+    Size,
+    /// The window title.
+    Title,
+}
+
+/// How a [`TablePrinter`](crate::TablePrinter) fits titles longer than
+/// its maximum title width.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TitleOverflow {
+    /// Cuts the title off, ending it with `…`.
+    #[default]
+    Truncate,
+    /// Continues the title on the following lines, breaking between words
+    /// where possible.
+    Wrap,
+}
+
+/// A virtual-key code for [`WindowInfo::send_keys`].
+///
+/// Common keys are available as constants; letters and digits use their
+/// uppercase ASCII code, see [`VirtualKey::from_char`].
+///
+/// Requires the `input` feature.
+#[cfg(feature = "input")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VirtualKey(pub u16);
+
+/// A captured image of a window.
+///
+/// Requires the `capture` feature.
+#[cfg(feature = "capture")]
+#[derive(Debug, Clone, Default)]
+pub struct WindowCapture {
+    /// The image width in pixels.
+    pub width: u32,
+    /// The image height in pixels.
+    pub height: u32,
+    /// The pixel data as tightly packed 8-bit RGBA, row by row from the top.
+    pub data: Vec<u8>,
+}
+
+/// A change to a top-level window reported by the watcher subsystem.
+#[derive(Debug, Clone)]
+pub enum WindowEvent {
+    /// A window was created.
+    Created {
+        /// The window handle (HWND).
+        hwnd: WindowHandle,
+    },
+    /// A window was destroyed.
+    Destroyed {
+        /// The window handle (HWND).
+        hwnd: WindowHandle,
+    },
+    /// A window's title changed.
+    TitleChanged {
+        /// The window handle (HWND).
+        hwnd: WindowHandle,
+        /// The new window title.
+        title: String,
+    },
+    /// A window was moved or resized.
+    Moved {
+        /// The window handle (HWND).
+        hwnd: WindowHandle,
+        /// The new position and dimensions of the window.
+        position: WindowPosition,
+    },
+    /// The user finished moving or resizing a window by dragging it.
+    ///
+    /// Unlike [`Moved`](Self::Moved), which fires continuously, this is
+    /// reported once per drag.
+    MoveSizeEnded {
+        /// The window handle (HWND).
+        hwnd: WindowHandle,
+        /// The position and dimensions before the drag.
+        old: WindowPosition,
+        /// The position and dimensions after the drag.
+        new: WindowPosition,
+    },
+    /// A window was minimized, maximized, or restored.
+    StateChanged {
+        /// The window handle (HWND).
+        hwnd: WindowHandle,
+        /// The state before the change.
+        old: WindowState,
+        /// The state after the change.
+        new: WindowState,
+    },
+    /// A window became the foreground window.
+    Foreground {
+        /// The window handle (HWND).
+        hwnd: WindowHandle,
+    },
+}
+
+/// How events are delivered to a subscription of
+/// [`WindowWatcher::subscribe_with`](crate::WindowWatcher::subscribe_with).
+///
+/// The default delivers every event and never drops any.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubscriptionOptions {
+    /// Collapses the `Moved` and `TitleChanged` events of each window into at
+    /// most one per interval, carrying the latest position or title.
+    ///
+    /// The first event is delivered right away; those following within the
+    /// interval replace each other until it elapses. Dragging a window
+    /// otherwise produces hundreds of `Moved` events per second.
+    pub coalesce: Option<std::time::Duration>,
+    /// The maximum number of undelivered events, or `None` for no limit.
+    pub capacity: Option<usize>,
+    /// What happens when an event arrives while `capacity` events are
+    /// waiting to be received.
+    pub overflow: OverflowPolicy,
+}
+
+/// How a full subscription queue handles another event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discards the oldest undelivered event, counted in
+    /// [`EventReceiver::dropped`](crate::EventReceiver::dropped).
+    #[default]
+    DropOldest,
+    /// Waits until the receiver makes room.
+    ///
+    /// This stalls the hook thread, delaying the events of the watcher and
+    /// all other subscriptions until then.
+    Block,
+}
+
+/// The time a window spent in the foreground, as tracked by
+/// [`FocusTracker`](crate::FocusTracker).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusRecord {
+    /// The window handle (HWND).
+    pub hwnd: WindowHandle,
+    /// The process ID (PID) that owns the window.
+    pub pid: u32,
+    /// The name of the process executable, empty if it could not be queried.
+    pub process_name: String,
+    /// The total time the window was the foreground window.
+    pub total_focused: Duration,
+}
+
+/// The time the windows of a process spent in the foreground, as tracked by
+/// [`FocusTracker`](crate::FocusTracker).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessFocusRecord {
+    /// The process ID (PID).
+    pub pid: u32,
+    /// The name of the process executable, empty if it could not be queried.
+    pub process_name: String,
+    /// The total time any window of the process was the foreground window.
+    pub total_focused: Duration,
+}
+
+/// Well-known window class names of Windows itself and popular applications.
+///
+/// Use [`KnownWindowClass::class_name`] to get the raw class name, or
+/// [`FilterCriteria::known_class`] to filter by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KnownWindowClass {
+    /// A classic console host window (`ConsoleWindowClass`).
+    ConsoleWindow,
+    /// A Windows Terminal window (`CASCADIA_HOSTING_WINDOW_CLASS`).
+    WindowsTerminal,
+    /// A File Explorer window (`CabinetWClass`).
+    ExplorerCabinet,
+    /// A Chromium-based browser or Electron app window (`Chrome_WidgetWin_1`).
+    ChromeWidget,
+    /// A Firefox window (`MozillaWindowClass`).
+    Firefox,
+    /// The frame hosting a UWP app (`ApplicationFrameWindow`).
+    ApplicationFrame,
+    /// The content window of a UWP app (`Windows.UI.Core.CoreWindow`).
+    CoreWindow,
+    /// The primary taskbar (`Shell_TrayWnd`).
+    ShellTray,
+    /// A taskbar on a secondary monitor (`Shell_SecondaryTrayWnd`).
+    SecondaryShellTray,
+    /// The desktop program manager (`Progman`).
+    Progman,
+    /// A desktop worker window, e.g. hosting the wallpaper (`WorkerW`).
+    WorkerW,
+    /// A standard dialog box (`#32770`).
+    Dialog,
+    /// A popup menu (`#32768`).
+    Menu,
+    /// A tooltip (`tooltips_class32`).
+    Tooltip,
+    /// A classic Notepad window (`Notepad`).
+    Notepad,
+    /// The default input method editor window of a thread (`IME`).
+    Ime,
+    /// The notification area overflow flyout (`NotifyIconOverflowWindow`).
+    NotifyIconOverflow,
+    /// A window drawn by the system in place of a hung window (`Ghost`).
+    Ghost,
+}
+
+/// How a [`StringFilter`] pattern is compared against a window property.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// The value contains the pattern.
+    #[default]
+    Contains,
+    /// The value equals the pattern.
+    Exact,
+    /// The whole value matches the pattern, where `*` matches any run of
+    /// characters and `?` matches a single character.
+    Glob,
+}
+
+/// A string pattern and the way it is matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringFilter {
+    /// The pattern to match.
+    pub pattern: String,
+    /// How the pattern is matched.
+    pub mode: MatchMode,
+    /// Whether letter case must match; case is ignored by default.
+    pub case_sensitive: bool,
 }
 
 /// Criteria for filtering windows during enumeration.
@@ -41,24 +1085,186 @@ pub struct WindowInfo {
 pub struct FilterCriteria {
     /// Filter by exact process ID match.
     pub pid: Option<u32>,
+    /// Filter by process ID being any of the specified IDs.
+    pub pids: Option<Vec<u32>>,
+    /// Exclude windows owned by any of the specified process IDs.
+    pub exclude_pids: Option<Vec<u32>>,
+    /// Filter by exact owning thread ID match.
+    pub thread_id: Option<u32>,
+    /// Filter by the session of the owning process.
+    ///
+    /// Windows whose session is unknown never match.
+    pub session_id: Option<u32>,
     /// Filter by title containing the specified string (case-insensitive).
     pub title_contains: Option<String>,
     /// Filter by class name containing the specified string (case-insensitive).
     pub class_name_contains: Option<String>,
     /// Filter by process name containing the specified string (case-insensitive).
+    ///
+    /// For UWP frame windows the hosted app's process name also matches.
     pub process_name_contains: Option<String>,
     /// Filter by process file path containing the specified string (case-insensitive).
+    ///
+    /// Matches against both the Win32 path and the NT device path.
     pub process_file_contains: Option<String>,
+    /// Filter by a well-known window class.
+    pub known_class: Option<KnownWindowClass>,
+    /// Filter by title matching the specified pattern, e.g. the glob
+    /// `"* - Notepad"`.
+    pub title_matches: Option<StringFilter>,
+    /// Filter by class name matching the specified pattern.
+    pub class_name_matches: Option<StringFilter>,
+    /// Filter by process name matching the specified pattern.
+    ///
+    /// For UWP frame windows the hosted app's process name also matches.
+    pub process_name_matches: Option<StringFilter>,
+    /// Filter by process file path matching the specified pattern.
+    ///
+    /// Matches against both the Win32 path and the NT device path.
+    pub process_file_matches: Option<StringFilter>,
+    /// Filter by process command line matching the specified pattern.
+    ///
+    /// Windows without a known command line never match this filter.
+    pub command_line_matches: Option<StringFilter>,
+    /// Exclude windows whose title contains the specified string (case-insensitive).
+    pub title_not_contains: Option<String>,
+    /// Exclude windows whose class name contains the specified string (case-insensitive).
+    pub class_name_not_contains: Option<String>,
+    /// Exclude windows whose process name contains the specified string (case-insensitive).
+    pub process_name_not_contains: Option<String>,
+    /// Filter by AUMID containing the specified string (case-insensitive).
+    ///
+    /// Windows without a known AUMID never match.
+    pub aumid_contains: Option<String>,
+    /// Filter by the account owning the process containing the specified
+    /// string (case-insensitive), e.g. `"alice"` or `"CONTOSO\\"`.
+    ///
+    /// Windows without a resolved [`WindowInfo::user_name`] never match.
+    pub user_contains: Option<String>,
+    /// Filter by the product name of the executable containing the
+    /// specified string (case-insensitive).
+    ///
+    /// Windows without a known [`WindowInfo::product_name`] never match.
+    pub product_name_contains: Option<String>,
+    /// Filter by any ancestor process executable name containing the
+    /// specified string (case-insensitive), e.g. the name of a launcher.
+    ///
+    /// Windows without a resolved [`WindowInfo::parent_chain`] never match.
+    pub spawned_by_contains: Option<String>,
+    /// Keep only windows whose executable has (`true`) or lacks (`false`)
+    /// a valid signature.
+    ///
+    /// Windows whose [`WindowInfo::signature_status`] is unknown never match.
+    pub signed: Option<bool>,
+    /// Filter by process command line containing the specified string (case-insensitive).
+    ///
+    /// Windows without a known command line never match this filter.
+    pub command_line_contains: Option<String>,
+    /// Filter by minimum window width in pixels (inclusive).
+    pub min_width: Option<i32>,
+    /// Filter by minimum window height in pixels (inclusive).
+    pub min_height: Option<i32>,
+    /// Filter by maximum window width in pixels (inclusive).
+    pub max_width: Option<i32>,
+    /// Filter by maximum window height in pixels (inclusive).
+    pub max_height: Option<i32>,
+    /// Filter by windows overlapping the specified screen rectangle.
+    pub intersects_rect: Option<WindowPosition>,
+    /// Filter by the 1-based index of the monitor the window is on.
+    pub monitor_index: Option<usize>,
+    /// Exclude tool windows (`WS_EX_TOOLWINDOW`), as Alt-Tab does.
+    pub exclude_tool_windows: bool,
+    /// Exclude windows cloaked by DWM.
+    pub exclude_cloaked: bool,
+    /// Exclude ghost windows shown in place of hung windows, so a hung
+    /// application is not counted twice.
+    pub exclude_ghost_windows: bool,
+    /// Keep only windows whose process is (`Some(true)`) or is not
+    /// (`Some(false)`) elevated.
+    ///
+    /// Windows whose elevation is unknown never match.
+    pub elevated: Option<bool>,
+    /// Keep only windows whose process integrity level is at most the
+    /// specified level, e.g. the level of the current process to skip
+    /// windows that UIPI would not let it interact with.
+    ///
+    /// Windows whose integrity level is unknown never match.
+    pub max_integrity_level: Option<IntegrityLevel>,
+    /// Keep only windows whose process runs as the specified architecture.
+    ///
+    /// Windows whose architecture is unknown never match.
+    pub process_arch: Option<ProcessArch>,
+    /// Exclude desktop shell and system windows such as the taskbar,
+    /// desktop, tooltips, and IME windows (see [`WindowKind::is_system`]).
+    pub exclude_system_windows: bool,
+    /// Keep only windows that are (`Some(true)`) or are not (`Some(false)`)
+    /// always-on-top.
+    ///
+    /// Relies on the extended style, so the windows must have been
+    /// enumerated with [`EnumerationOptions::include_styles`].
+    pub topmost: Option<bool>,
+    /// Keep only windows that are (`Some(true)`) or are not (`Some(false)`)
+    /// fullscreen (see [`WindowInfo::is_fullscreen`]).
+    ///
+    /// Needs the position and styles, so the windows must have been
+    /// enumerated with both [`EnumerationOptions::include_position`] and
+    /// [`EnumerationOptions::include_styles`].
+    pub fullscreen: Option<bool>,
+    /// Keep only windows that are responding (see [`WindowInfo::is_hung`]).
+    pub responding_only: bool,
+    /// Keep only windows that are not responding (see [`WindowInfo::is_hung`]).
+    pub hung_only: bool,
 }
 
 #[cfg(feature = "selection")]
 /// Selection criteria for choosing specific windows from enumeration results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Selection {
     /// Select all windows that match the filter criteria.
     All,
     /// Select windows by their 1-based indices.
     Indices(Vec<usize>),
+    /// Select windows by ranges that may be open-ended, stepped, or
+    /// counted from the end of the list.
+    Ranges(Vec<SelectionRange>),
+    /// Apply the inner selection to 1-based positions within the filtered
+    /// (and sorted) result list instead of to the original enumeration
+    /// indices.
+    ///
+    /// This is the recommended mode whenever results are sorted, since
+    /// `"1"` then means "the first result" rather than "the first window
+    /// that was enumerated".
+    ByPosition(Box<Selection>),
+    /// Select everything matched by `include` that is not matched by
+    /// `exclude`.
+    Except {
+        /// The selection to start from.
+        include: Box<Selection>,
+        /// The selection to remove from `include`.
+        exclude: Box<Selection>,
+    },
+}
+
+#[cfg(feature = "selection")]
+/// A 1-based index that is counted from either end of a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionIndex {
+    /// The n-th item from the start (`1` is the first).
+    FromStart(usize),
+    /// The n-th item from the end (`1` is the last).
+    FromEnd(usize),
+}
+
+#[cfg(feature = "selection")]
+/// An inclusive range of selected indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectionRange {
+    /// First index of the range.
+    pub start: SelectionIndex,
+    /// Last index of the range, or `None` to run to the end of the list.
+    pub end: Option<SelectionIndex>,
+    /// Distance between selected indices (at least 1).
+    pub step: usize,
 }
 
 #[cfg(feature = "sorting")]
@@ -73,8 +1279,68 @@ pub enum PositionSort {
     XY(i8, i8), // (x_order, y_order)
 }
 
+#[cfg(feature = "sorting")]
+/// The direction of a sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest values first.
+    Asc,
+    /// Largest values first.
+    Desc,
+}
+
+#[cfg(feature = "sorting")]
+/// A single key of a multi-key sort specification.
+///
+/// Sorting takes a slice of keys; earlier keys take precedence and later
+/// keys only break ties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Sort by process ID.
+    Pid(SortOrder),
+    /// Sort by window title (case-insensitive).
+    Title(SortOrder),
+    /// Sort by window title (case-insensitive), comparing embedded numbers
+    /// by value so that "Window 2" sorts before "Window 10".
+    TitleNatural(SortOrder),
+    /// Sort by window title using the linguistic order of the user's
+    /// locale (see [`utils::locale_cmp`](crate::utils::locale_cmp)).
+    TitleLocale(SortOrder),
+    /// Sort by X coordinate.
+    X(SortOrder),
+    /// Sort by Y coordinate.
+    Y(SortOrder),
+    /// Sort by window area (width × height).
+    Area(SortOrder),
+    /// Sort by window width.
+    Width(SortOrder),
+    /// Sort by window height.
+    Height(SortOrder),
+    /// Sort by the working set of the owning process (see
+    /// [`WindowInfo::process_metrics`]); windows without metrics come first
+    /// in ascending order.
+    Memory(SortOrder),
+    /// Sort by the start time of the owning process; windows without
+    /// metrics come first in ascending order.
+    StartTime(SortOrder),
+}
+
+#[cfg(feature = "sorting")]
+/// The sort keys taken by the sorting methods, in order of precedence.
+///
+/// Converts from a slice, array, or `Vec` of [`SortKey`]s, and from a
+/// deprecated [`SortCriteria`], so code written against the fixed-field
+/// criteria keeps compiling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKeys<'a>(pub(crate) std::borrow::Cow<'a, [SortKey]>);
+
 #[cfg(feature = "sorting")]
 /// Criteria for sorting window enumeration results.
+///
+/// The fixed field order always gives PID precedence over title, title over
+/// position, and so on. Use an ordered slice of [`SortKey`]s to control the
+/// precedence; [`SortCriteria::to_keys`] converts existing criteria.
+#[deprecated(note = "use an ordered `Vec<SortKey>` instead")]
 #[derive(Debug, Clone, Default)] // ← 添加 Default derive
 pub struct SortCriteria {
     /// Sort by process ID (1: ascending, -1: descending, 0: no sorting).
@@ -83,6 +1349,177 @@ pub struct SortCriteria {
     pub title: i8,
     /// Sort by window position (None: no sorting, Some: position-based sorting).
     pub position: Option<PositionSort>,
+    /// Sort by window area, width × height (1: ascending, -1: descending, 0: no sorting).
+    pub area: i8,
+    /// Sort by window width (1: ascending, -1: descending, 0: no sorting).
+    pub width: i8,
+    /// Sort by window height (1: ascending, -1: descending, 0: no sorting).
+    pub height: i8,
 }
 
 // 删除手动实现的 Default for SortCriteria
+
+/// Fuzzy criteria for ranking windows by how likely they are the one
+/// looked for, see [`ScoreCriteria::rank`].
+///
+/// Unlike [`FilterCriteria`], where every field must match, each criterion
+/// adds its weight to a window's score. If any of the text criteria are
+/// set, only windows matching at least one of them are candidates; the
+/// bonuses then order those candidates.
+#[derive(Debug, Clone, Default)]
+pub struct ScoreCriteria {
+    /// The expected window title (case-insensitive).
+    pub title: Option<String>,
+    /// The expected process name, e.g. `"code.exe"` (case-insensitive).
+    pub process_name: Option<String>,
+    /// The expected window class name (case-insensitive).
+    pub class_name: Option<String>,
+    /// How much each criterion contributes to the score.
+    pub weights: ScoreWeights,
+}
+
+/// The weight of each [`ScoreCriteria`] criterion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreWeights {
+    /// Added if the title equals [`ScoreCriteria::title`].
+    pub title_exact: f32,
+    /// Added if the title only contains [`ScoreCriteria::title`].
+    pub title_contains: f32,
+    /// Added if the process name equals [`ScoreCriteria::process_name`].
+    pub process_exact: f32,
+    /// Added if the process name only contains [`ScoreCriteria::process_name`].
+    pub process_contains: f32,
+    /// Added if the class name equals [`ScoreCriteria::class_name`].
+    pub class_exact: f32,
+    /// Added if the class name only contains [`ScoreCriteria::class_name`].
+    pub class_contains: f32,
+    /// Added for the foreground window.
+    pub foreground: f32,
+    /// Scaled by how recently the window was active: the full weight for
+    /// the top of the Z-order, approaching zero for the bottom.
+    pub recency: f32,
+    /// Added for regular application windows (see [`WindowKind::AppWindow`]).
+    pub app_window: f32,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            title_exact: 10.0,
+            title_contains: 5.0,
+            process_exact: 8.0,
+            process_contains: 4.0,
+            class_exact: 6.0,
+            class_contains: 3.0,
+            foreground: 3.0,
+            recency: 2.0,
+            app_window: 2.0,
+        }
+    }
+}
+
+/// A declarative arrangement of windows, applied by a [`LayoutEngine`].
+///
+/// Windows are placed in the order given, e.g. the order of a sorted and
+/// filtered window list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Layout {
+    /// Equally sized cells, filled row by row. With `columns: None` the
+    /// grid is as square as possible.
+    Grid {
+        /// The number of columns.
+        columns: Option<usize>,
+    },
+    /// Full-height columns side by side.
+    Columns,
+    /// Full-width rows stacked on top of each other.
+    Rows,
+    /// Overlapping windows, each shifted right and down by `offset` pixels.
+    ///
+    /// A window that would extend past the area starts over from its
+    /// top-left corner.
+    Cascade {
+        /// The shift between consecutive windows.
+        offset: i32,
+    },
+    /// Explicit bounds per window; windows beyond the list are left alone.
+    Explicit(Vec<WindowPosition>),
+}
+
+/// Computes and applies [`Layout`]s within an area of the screen, such as
+/// the work area of a monitor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayoutEngine {
+    /// The area the windows are arranged in, in screen coordinates.
+    pub area: WindowPosition,
+    /// The space left between neighbouring windows, in pixels.
+    pub gap: i32,
+}
+
+/// A composable filter expression combining [`FilterCriteria`] with boolean logic.
+///
+/// Fields within a single [`FilterCriteria`] are always AND-ed; use this
+/// type to express alternatives and negations.
+#[derive(Debug, Clone)]
+pub enum Filter {
+    /// Matches if all sub-filters match (an empty list always matches).
+    And(Vec<Filter>),
+    /// Matches if any sub-filter matches (an empty list never matches).
+    Or(Vec<Filter>),
+    /// Matches if the sub-filter does not match.
+    Not(Box<Filter>),
+    /// Matches according to a set of filter criteria.
+    Leaf(Box<FilterCriteria>),
+}
+
+impl From<FilterCriteria> for Filter {
+    fn from(criteria: FilterCriteria) -> Self {
+        Filter::Leaf(Box::new(criteria))
+    }
+}
+
+#[cfg(all(feature = "sorting", feature = "selection"))]
+/// A named set of window matching rules, such as user-editable window
+/// profiles loaded from a configuration file.
+///
+/// With the `serde` feature the set can be deserialized from any serde
+/// format, e.g. TOML:
+///
+/// ```toml
+/// [[rules]]
+/// name = "editor"
+/// query = "process=code.exe exclude:tool sort:title select:1"
+///
+/// [[rules]]
+/// name = "browsers"
+/// query = "process~chrome"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowRuleSet {
+    /// The rules, evaluated in order.
+    pub rules: Vec<WindowRule>,
+}
+
+#[cfg(all(feature = "sorting", feature = "selection"))]
+/// A named rule of a [`WindowRuleSet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowRule {
+    /// The identifier reported with the rule's matches.
+    pub name: String,
+    /// The filter, sort, and selection to apply, in the syntax of
+    /// [`parse_query`](crate::parse_query).
+    pub query: String,
+}
+
+#[cfg(all(feature = "sorting", feature = "selection"))]
+/// The windows matched by one rule of a [`WindowRuleSet`].
+#[derive(Debug, Clone)]
+pub struct RuleMatches {
+    /// The name of the rule.
+    pub name: String,
+    /// The matching windows, filtered, sorted, and selected by the rule.
+    pub windows: Vec<WindowInfo>,
+}
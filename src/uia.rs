@@ -0,0 +1,135 @@
+use windows::Win32::Foundation::HWND;
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+};
+use windows::Win32::UI::Accessibility::{CUIAutomation8, IUIAutomation, IUIAutomation2};
+
+use windows::core::ComInterface;
+
+use crate::types::WindowInfo;
+
+/// How long a single UI Automation request may take before it is
+/// abandoned, so an unresponsive provider cannot stall an enumeration.
+const UIA_TIMEOUT_MS: u32 = 2000;
+
+/// The names of the UI Automation control types, indexed from
+/// `UIA_ButtonControlTypeId` (50000).
+const CONTROL_TYPE_NAMES: [&str; 41] = [
+    "Button",
+    "Calendar",
+    "CheckBox",
+    "ComboBox",
+    "Edit",
+    "Hyperlink",
+    "Image",
+    "ListItem",
+    "List",
+    "Menu",
+    "MenuBar",
+    "MenuItem",
+    "ProgressBar",
+    "RadioButton",
+    "ScrollBar",
+    "Slider",
+    "Spinner",
+    "StatusBar",
+    "Tab",
+    "TabItem",
+    "Text",
+    "ToolBar",
+    "ToolTip",
+    "Tree",
+    "TreeItem",
+    "Custom",
+    "Group",
+    "Thumb",
+    "DataGrid",
+    "DataItem",
+    "Document",
+    "SplitButton",
+    "Window",
+    "Pane",
+    "Header",
+    "HeaderItem",
+    "Table",
+    "TitleBar",
+    "Separator",
+    "SemanticZoom",
+    "AppBar",
+];
+
+/// A UI Automation client for reading the elements of top-level windows.
+///
+/// Initializes COM on the calling thread for its lifetime unless the
+/// thread already uses a different apartment model.
+pub(crate) struct UiaClient {
+    automation: Option<IUIAutomation>,
+    uninitialize: bool,
+}
+
+impl UiaClient {
+    /// Creates the automation object, or returns `None` if UI Automation
+    /// is unavailable.
+    pub(crate) fn new() -> Option<Self> {
+        unsafe {
+            // Fails with RPC_E_CHANGED_MODE on threads that already chose
+            // another apartment, where COM is usable all the same
+            let uninitialize = CoInitializeEx(None, COINIT_MULTITHREADED).is_ok();
+            // Created first so a failure below still uninitializes COM
+            let mut client = Self {
+                automation: None,
+                uninitialize,
+            };
+
+            let automation: IUIAutomation =
+                CoCreateInstance(&CUIAutomation8, None, CLSCTX_INPROC_SERVER).ok()?;
+            if let Ok(automation) = automation.cast::<IUIAutomation2>() {
+                let _ = automation.SetConnectionTimeout(UIA_TIMEOUT_MS);
+                let _ = automation.SetTransactionTimeout(UIA_TIMEOUT_MS);
+            }
+
+            client.automation = Some(automation);
+            Some(client)
+        }
+    }
+
+    /// Reads the name, control type, and automation ID of the element of
+    /// a window into its information.
+    ///
+    /// Properties that cannot be read, or are empty, are left `None`.
+    pub(crate) fn load_into(&self, window: &mut WindowInfo) {
+        let Some(automation) = &self.automation else {
+            return;
+        };
+        let Ok(element) = (unsafe { automation.ElementFromHandle(HWND::from(window.hwnd)) }) else {
+            return;
+        };
+
+        unsafe {
+            window.uia_name = element
+                .CurrentName()
+                .ok()
+                .map(|name| name.to_string())
+                .filter(|name| !name.is_empty());
+            window.uia_control_type = element.CurrentControlType().ok().and_then(|id| {
+                let index = usize::try_from(id.0 - 50000).ok()?;
+                CONTROL_TYPE_NAMES.get(index).map(|name| name.to_string())
+            });
+            window.uia_automation_id = element
+                .CurrentAutomationId()
+                .ok()
+                .map(|id| id.to_string())
+                .filter(|id| !id.is_empty());
+        }
+    }
+}
+
+impl Drop for UiaClient {
+    fn drop(&mut self) {
+        // Release the automation object while COM is still initialized
+        self.automation = None;
+        if self.uninitialize {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
@@ -1,56 +1,179 @@
 use crate::errors::{Result, WindowError};
-use crate::types::WindowInfo;
+use crate::types::{Filter, SignatureStatus, WindowInfo};
 
 #[cfg(feature = "selection")]
-use crate::types::Selection;
+use crate::types::{Selection, SelectionIndex, SelectionRange};
 
 #[cfg(feature = "sorting")]
-use crate::types::PositionSort; // ← 只保留 PositionSort，删除 SortCriteria
+use crate::types::{PositionSort, SortKey, SortOrder};
+
+#[cfg(all(feature = "sorting", feature = "selection"))]
+use crate::types::{FilterCriteria, ProcessArch, StringFilter};
 
 /// Parses a selection string into a [`Selection`] enum.
 ///
+/// Besides `all`, a selection is a comma-separated list of:
+///
+/// * single indices: `3`, or `-1` for the last item, `-2` for the one before
+/// * the keywords `first` and `last`
+/// * inclusive ranges: `1-3`, `-3--1`, or `2-last`
+/// * open-ended ranges: `3-` runs to the end of the list
+/// * step ranges: `1-9:2` selects every second index from 1 to 9
+/// * exclusions: any of the above prefixed with `!`, e.g. `all,!3` or
+///   `1-10,!5-6`; a selection of only exclusions starts from `all`
+///
+/// Selections using only plain indices and closed ranges are expanded into
+/// [`Selection::Indices`]; anything else is kept as [`Selection::Ranges`]
+/// and resolved against the list length by [`Selection::matches_in`].
+///
 /// # Examples
 /// ```
-/// use window_enumerator::parse_selection;
+/// use window_enumerator::{parse_selection, Selection};
 ///
 /// let selection = parse_selection("1,2,3").unwrap();
 /// let all_selection = parse_selection("all").unwrap();
 /// let range_selection = parse_selection("1-3").unwrap();
+/// assert_eq!(parse_selection("1-9:4").unwrap(), Selection::Indices(vec![1, 5, 9]));
+///
+/// let last_two = parse_selection("-2-last").unwrap();
+/// assert!(last_two.matches_in(9, 10));
+/// assert!(last_two.matches_in(10, 10));
+/// assert!(!last_two.matches_in(8, 10));
+///
+/// let from_three = parse_selection("3-").unwrap();
+/// assert!(from_three.matches_in(7, 7));
+/// assert!(!from_three.matches_in(2, 7));
+///
+/// // Reversed ranges and index 0 are rejected
+/// assert!(parse_selection("5-3").is_err());
+/// assert!(parse_selection("0").is_err());
+///
+/// // Huge ranges are kept as ranges rather than expanded
+/// let huge = parse_selection("1-4294967295").unwrap();
+/// assert!(matches!(huge, Selection::Ranges(_)));
+/// assert!(huge.matches(123_456));
+///
+/// let except = parse_selection("1-10,!5-6").unwrap();
+/// assert!(except.matches(4));
+/// assert!(!except.matches(5));
+/// assert!(!parse_selection("!last").unwrap().matches_in(10, 10));
 /// ```
 ///
 /// # Errors
-/// Returns [`WindowError::InvalidSelectionFormat`] if the string cannot be parsed.
+/// Returns [`WindowError::InvalidSelectionToken`] naming the first invalid
+/// item and its byte offset. Its reason is [`WindowError::InvalidIndex`] for
+/// malformed indices and index `0`, or [`WindowError::InvalidRange`] for
+/// malformed or reversed ranges and steps.
+///
+/// ```
+/// use window_enumerator::{parse_selection, WindowError};
+///
+/// let error = parse_selection("1,2,x,5-3").unwrap_err();
+/// assert_eq!(
+///     error,
+///     WindowError::InvalidSelectionToken {
+///         token: "x".to_string(),
+///         position: 4,
+///         reason: Box::new(WindowError::InvalidIndex),
+///     }
+/// );
+/// ```
 #[cfg(feature = "selection")]
 #[allow(dead_code)] // ← 添加这个属性，因为函数可能被库用户使用
 pub fn parse_selection(selection_str: &str) -> Result<Selection> {
-    let selection_str = selection_str.trim().to_lowercase();
-
-    if selection_str == "all" {
+    if selection_str.trim().eq_ignore_ascii_case("all") {
         return Ok(Selection::All);
     }
 
-    let mut indices = Vec::new();
-    let parts: Vec<&str> = selection_str.split(',').collect();
-
-    for part in parts {
-        let part = part.trim();
-        if part.contains('-') {
-            // Handle ranges like "1-3"
-            let range_parts: Vec<&str> = part.split('-').collect();
-            if range_parts.len() == 2 {
-                let start = parse_index(range_parts[0].trim())?;
-                let end = parse_index(range_parts[1].trim())?;
-
-                for i in start..=end {
-                    indices.push(i);
-                }
-            } else {
-                return Err(WindowError::InvalidRange);
-            }
+    let mut include_all = false;
+    let mut include = Vec::new();
+    let mut exclude = Vec::new();
+
+    for (position, token) in split_tokens(selection_str, ',') {
+        let part = token.to_lowercase();
+        let result = if let Some(excluded) = part.strip_prefix('!') {
+            parse_selection_range(excluded.trim()).map(|range| exclude.push(range))
+        } else if part == "all" {
+            include_all = true;
+            Ok(())
         } else {
-            // Handle single indices
-            let index = parse_index(part)?;
-            indices.push(index);
+            parse_selection_range(&part).map(|range| include.push(range))
+        };
+
+        result.map_err(|reason| WindowError::InvalidSelectionToken {
+            token: token.to_string(),
+            position,
+            reason: Box::new(reason),
+        })?;
+    }
+
+    // A selection made only of exclusions starts from everything
+    let include = if include_all || include.is_empty() {
+        Selection::All
+    } else {
+        ranges_to_selection(include)
+    };
+
+    if exclude.is_empty() {
+        return Ok(include);
+    }
+
+    Ok(Selection::Except {
+        include: Box::new(include),
+        exclude: Box::new(ranges_to_selection(exclude)),
+    })
+}
+
+/// Returns the highest index of a list of windows, which index-based
+/// selections resolve `last` and open ranges against. Indices have gaps
+/// after a refresh, so the list length can fall short of it.
+#[cfg(feature = "selection")]
+pub(crate) fn max_index(windows: &[WindowInfo]) -> usize {
+    windows.iter().map(|window| window.index).max().unwrap_or(0)
+}
+
+/// Splits a string at `separator` into trimmed tokens, each paired with its
+/// byte offset in the input.
+#[cfg(any(feature = "selection", feature = "sorting"))]
+fn split_tokens(s: &str, separator: char) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+    s.split(separator).map(move |raw| {
+        let position = offset + (raw.len() - raw.trim_start().len());
+        offset += raw.len() + separator.len_utf8();
+        (position, raw.trim())
+    })
+}
+
+/// The largest number of indices a selection is expanded into.
+///
+/// Larger selections such as `1-4294967295` are kept as ranges instead of
+/// allocating every index.
+#[cfg(feature = "selection")]
+const MAX_EXPANDED_INDICES: usize = 65_536;
+
+/// Converts parsed ranges into a selection, expanding them into plain
+/// indices when they do not depend on the list length.
+#[cfg(feature = "selection")]
+fn ranges_to_selection(ranges: Vec<SelectionRange>) -> Selection {
+    // Plain closed ranges can be expanded without knowing the list length
+    let mut expanded_len = 0usize;
+    let expandable = ranges.iter().all(|range| match (range.start, range.end) {
+        (SelectionIndex::FromStart(start), Some(SelectionIndex::FromStart(end))) => {
+            expanded_len = expanded_len.saturating_add((end - start) / range.step + 1);
+            expanded_len <= MAX_EXPANDED_INDICES
+        }
+        _ => false,
+    });
+    if !expandable {
+        return Selection::Ranges(ranges);
+    }
+
+    let mut indices = Vec::new();
+    for range in ranges {
+        if let (SelectionIndex::FromStart(start), Some(SelectionIndex::FromStart(end))) =
+            (range.start, range.end)
+        {
+            indices.extend((start..=end).step_by(range.step));
         }
     }
 
@@ -58,7 +181,102 @@ pub fn parse_selection(selection_str: &str) -> Result<Selection> {
     indices.sort();
     indices.dedup();
 
-    Ok(Selection::Indices(indices))
+    Selection::Indices(indices)
+}
+
+/// Parses a single comma-separated selection item, e.g. "3", "-1", "2-",
+/// or "1-9:2".
+#[cfg(feature = "selection")]
+fn parse_selection_range(part: &str) -> Result<SelectionRange> {
+    let (body, step) = match part.split_once(':') {
+        Some((body, step)) => {
+            let step: usize = step.trim().parse().map_err(|_| WindowError::InvalidRange)?;
+            if step == 0 {
+                return Err(WindowError::InvalidRange);
+            }
+            (body.trim(), Some(step))
+        }
+        None => (part, None),
+    };
+
+    let (start, rest) = parse_selection_index(body)?;
+    let rest = rest.trim_start();
+
+    if rest.is_empty() {
+        // A single index cannot carry a step
+        if step.is_some() {
+            return Err(WindowError::InvalidRange);
+        }
+        return Ok(SelectionRange {
+            start,
+            end: Some(start),
+            step: 1,
+        });
+    }
+
+    let rest = rest
+        .strip_prefix('-')
+        .ok_or(WindowError::InvalidRange)?
+        .trim();
+    let end = if rest.is_empty() {
+        None
+    } else {
+        let (end, trailing) = parse_selection_index(rest)?;
+        if !trailing.trim().is_empty() {
+            return Err(WindowError::InvalidRange);
+        }
+        Some(end)
+    };
+
+    // Reject reversed ranges such as "5-3" or "-1--3"
+    let reversed = match (start, end) {
+        (SelectionIndex::FromStart(start), Some(SelectionIndex::FromStart(end))) => start > end,
+        (SelectionIndex::FromEnd(start), Some(SelectionIndex::FromEnd(end))) => start < end,
+        _ => false,
+    };
+    if reversed {
+        return Err(WindowError::InvalidRange);
+    }
+
+    Ok(SelectionRange {
+        start,
+        end,
+        step: step.unwrap_or(1),
+    })
+}
+
+/// Parses a leading selection index and returns it with the unparsed rest.
+#[cfg(feature = "selection")]
+fn parse_selection_index(s: &str) -> Result<(SelectionIndex, &str)> {
+    let s = s.trim_start();
+
+    if let Some(rest) = s.strip_prefix("first") {
+        return Ok((SelectionIndex::FromStart(1), rest));
+    }
+    if let Some(rest) = s.strip_prefix("last") {
+        return Ok((SelectionIndex::FromEnd(1), rest));
+    }
+
+    let (from_end, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s),
+    };
+    let len = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    let index = parse_index(&digits[..len])?;
+    let rest = &digits[len..];
+
+    // Indices are 1-based in both directions
+    if index == 0 {
+        return Err(WindowError::InvalidIndex);
+    }
+
+    if from_end {
+        Ok((SelectionIndex::FromEnd(index), rest))
+    } else {
+        Ok((SelectionIndex::FromStart(index), rest))
+    }
 }
 
 /// Parses a position sort string into a [`PositionSort`] enum.
@@ -70,45 +288,345 @@ pub fn parse_selection(selection_str: &str) -> Result<Selection> {
 /// let x_sort = parse_position_sort("x1").unwrap();
 /// let y_sort = parse_position_sort("y-1").unwrap();
 /// let xy_sort = parse_position_sort("x1|y1").unwrap();
+///
+/// let error = parse_position_sort("x1|y2").unwrap_err();
+/// assert!(error.to_string().contains("'y2' at position 3"));
 /// ```
 ///
 /// # Errors
-/// Returns [`WindowError::InvalidPositionSortFormat`] if the string cannot be parsed.
+/// Returns [`WindowError::InvalidPositionSortToken`] naming the first
+/// invalid part and its byte offset.
 #[cfg(feature = "sorting")]
 #[allow(dead_code)] // ← 添加这个属性，因为函数可能被库用户使用
 pub fn parse_position_sort(sort_str: &str) -> Result<Option<PositionSort>> {
+    if sort_str.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let parts: Vec<(usize, &str)> = split_tokens(sort_str, '|').collect();
+    let token_error = |(position, token): (usize, &str), reason: WindowError| {
+        WindowError::InvalidPositionSortToken {
+            token: token.to_string(),
+            position,
+            reason: Box::new(reason),
+        }
+    };
+    let parse_part = |part: (usize, &str), prefix: char| {
+        parse_single_position_order(&part.1.to_lowercase(), prefix)
+            .map_err(|reason| token_error(part, reason))
+    };
+
+    match parts[..] {
+        // Handle "x1|y1" format
+        [x_part, y_part] => Ok(Some(PositionSort::XY(
+            parse_part(x_part, 'x')?,
+            parse_part(y_part, 'y')?,
+        ))),
+        // Handle single coordinate sorts
+        [part] => match part.1.chars().next().map(|c| c.to_ascii_lowercase()) {
+            Some('x') => Ok(Some(PositionSort::X(parse_part(part, 'x')?))),
+            Some('y') => Ok(Some(PositionSort::Y(parse_part(part, 'y')?))),
+            _ => Err(token_error(part, WindowError::InvalidPositionSortFormat)),
+        },
+        _ => Err(token_error(
+            parts[2],
+            WindowError::InvalidPositionSortFormat,
+        )),
+    }
+}
+
+/// Parses a full sort specification into an ordered list of [`SortKey`]s.
+///
+/// The string is a comma-separated list of `key:order` pairs, where earlier
+/// keys take precedence. Keys are:
+///
+/// * `pid`
+/// * `title`, `title_natural` (or `natural`), and `title_locale` (or
+///   `locale`)
+/// * `x` and `y`
+/// * `area`, `width`, and `height`
+/// * `memory` and `start_time` (or `started`)
+///
+/// Orders are `1`/`asc` or `-1`/`desc`. The order may be omitted and
+/// defaults to ascending.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::{parse_sort, SortKey, SortOrder};
+///
+/// let keys = parse_sort("pid:-1,title:1,x").unwrap();
+/// assert_eq!(
+///     keys,
+///     vec![
+///         SortKey::Pid(SortOrder::Desc),
+///         SortKey::Title(SortOrder::Asc),
+///         SortKey::X(SortOrder::Asc),
+///     ]
+/// );
+/// assert!(parse_sort("").unwrap().is_empty());
+/// assert!(parse_sort("color:1").is_err());
+/// ```
+///
+/// # Errors
+/// Returns [`WindowError::InvalidSortFormat`] for unknown keys, or
+/// [`WindowError::InvalidSortOrder`] for unknown orders.
+#[cfg(feature = "sorting")]
+pub fn parse_sort(sort_str: &str) -> Result<Vec<SortKey>> {
     let sort_str = sort_str.trim().to_lowercase();
 
     if sort_str.is_empty() {
-        return Ok(None);
+        return Ok(Vec::new());
     }
 
-    if sort_str.contains('|') {
-        // Handle "x1|y1" format
-        let parts: Vec<&str> = sort_str.split('|').collect();
-        if parts.len() != 2 {
-            return Err(WindowError::InvalidPositionSortFormat);
+    sort_str
+        .split(',')
+        .map(|part| {
+            let (key, order) = match part.split_once(':') {
+                Some((key, order)) => (key.trim(), parse_sort_order(order.trim())?),
+                None => (part.trim(), SortOrder::Asc),
+            };
+            match key {
+                "pid" => Ok(SortKey::Pid(order)),
+                "title" => Ok(SortKey::Title(order)),
+                "title_natural" | "natural" => Ok(SortKey::TitleNatural(order)),
+                "title_locale" | "locale" => Ok(SortKey::TitleLocale(order)),
+                "x" => Ok(SortKey::X(order)),
+                "y" => Ok(SortKey::Y(order)),
+                "area" => Ok(SortKey::Area(order)),
+                "width" => Ok(SortKey::Width(order)),
+                "height" => Ok(SortKey::Height(order)),
+                "memory" => Ok(SortKey::Memory(order)),
+                "start_time" | "started" => Ok(SortKey::StartTime(order)),
+                _ => Err(WindowError::InvalidSortFormat),
+            }
+        })
+        .collect()
+}
+
+/// Compares two strings in natural order, case-insensitively.
+///
+/// Runs of ASCII digits are compared by numeric value, so "Window 2"
+/// sorts before "Window 10". Numerically equal runs with different
+/// leading zeros are ordered by length.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use window_enumerator::utils::natural_cmp;
+///
+/// assert_eq!(natural_cmp("Window 2", "Window 10"), Ordering::Less);
+/// assert_eq!(natural_cmp("doc10.txt", "DOC9.txt"), Ordering::Greater);
+/// assert_eq!(natural_cmp("a", "A"), Ordering::Equal);
+/// ```
+#[cfg(feature = "sorting")]
+pub fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let a = fold_case(a);
+    let b = fold_case(b);
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let a_run = take_digits(&mut a_chars);
+                let b_run = take_digits(&mut b_chars);
+                let a_value = a_run.trim_start_matches('0');
+                let b_value = b_run.trim_start_matches('0');
+                let ordering = a_value
+                    .len()
+                    .cmp(&b_value.len())
+                    .then_with(|| a_value.cmp(b_value))
+                    .then_with(|| a_run.len().cmp(&b_run.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
         }
+    }
+}
 
-        let x_part = parts[0].trim();
-        let y_part = parts[1].trim();
+/// Folds the case of a string for case-insensitive comparison.
+///
+/// Unlike `to_lowercase`, this also folds characters whose lowercase form
+/// differs between otherwise equal words: `ß` becomes `ss`, final `ς` becomes
+/// `σ`, ligatures such as `ﬁ` are expanded, and the Turkish dotted `İ` and
+/// dotless `ı` fold to a plain `i` so Turkish titles match regardless of how
+/// the letter was typed.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::utils::fold_case;
+///
+/// assert_eq!(fold_case("STRASSE"), fold_case("Straße"));
+/// assert_eq!(fold_case("ΟΔΥΣΣΕΥΣ"), fold_case("Οδυσσευς"));
+/// assert!(fold_case("İSTANBUL Haritası").contains(&fold_case("istanbul")));
+/// ```
+pub fn fold_case(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            // Full foldings that expand into several characters
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            'ﬀ' => folded.push_str("ff"),
+            'ﬁ' => folded.push_str("fi"),
+            'ﬂ' => folded.push_str("fl"),
+            'ﬃ' => folded.push_str("ffi"),
+            'ﬄ' => folded.push_str("ffl"),
+            'ﬅ' | 'ﬆ' => folded.push_str("st"),
+            // Variant letter forms that fold to their base letter
+            'ς' => folded.push('σ'),
+            'ſ' => folded.push('s'),
+            'ϐ' => folded.push('β'),
+            'ϑ' => folded.push('θ'),
+            'ϕ' => folded.push('φ'),
+            'ϖ' => folded.push('π'),
+            'ϰ' => folded.push('κ'),
+            'ϱ' => folded.push('ρ'),
+            'ϵ' => folded.push('ε'),
+            '\u{345}' | '\u{1fbe}' => folded.push('ι'),
+            // Dotted and dotless I
+            'İ' | 'ı' => folded.push('i'),
+            c => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
 
-        let x_order = parse_single_position_order(x_part, 'x')?;
-        let y_order = parse_single_position_order(y_part, 'y')?;
+/// Checks whether `value` contains `pattern`, ignoring case.
+fn contains_ignore_case(value: &str, pattern: &str) -> bool {
+    fold_case(value).contains(&fold_case(pattern))
+}
 
-        Ok(Some(PositionSort::XY(x_order, y_order)))
-    } else {
-        // Handle single coordinate sorts
-        if sort_str.starts_with('x') {
-            let order = parse_single_position_order(&sort_str, 'x')?;
-            Ok(Some(PositionSort::X(order)))
-        } else if sort_str.starts_with('y') {
-            let order = parse_single_position_order(&sort_str, 'y')?;
-            Ok(Some(PositionSort::Y(order)))
-        } else {
-            Err(WindowError::InvalidPositionSortFormat)
+/// Compares two strings using the linguistic sort order of the user's locale.
+///
+/// Case is ignored and embedded numbers are compared by value. This uses
+/// `CompareStringEx`, so e.g. Turkish or Swedish users get their language's
+/// ordering. Without the `windows` feature, or if the comparison fails, it
+/// falls back to [`natural_cmp`].
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use window_enumerator::utils::locale_cmp;
+///
+/// assert_eq!(locale_cmp("apple", "Banana"), Ordering::Less);
+/// assert_eq!(locale_cmp("Window 2", "window 10"), Ordering::Less);
+/// ```
+#[cfg(feature = "sorting")]
+pub fn locale_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    #[cfg(feature = "windows")]
+    {
+        use std::cmp::Ordering;
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::LPARAM;
+        use windows::Win32::Globalization::{
+            CompareStringEx, CSTR_EQUAL, CSTR_GREATER_THAN, CSTR_LESS_THAN, LINGUISTIC_IGNORECASE,
+            SORT_DIGITSASNUMBERS,
+        };
+
+        let a_wide: Vec<u16> = a.encode_utf16().collect();
+        let b_wide: Vec<u16> = b.encode_utf16().collect();
+        // A null locale name selects the user's default locale
+        let result = unsafe {
+            CompareStringEx(
+                PCWSTR::null(),
+                LINGUISTIC_IGNORECASE | SORT_DIGITSASNUMBERS,
+                &a_wide,
+                &b_wide,
+                None,
+                None,
+                LPARAM(0),
+            )
+        };
+        match result {
+            CSTR_LESS_THAN => return Ordering::Less,
+            CSTR_EQUAL => return Ordering::Equal,
+            CSTR_GREATER_THAN => return Ordering::Greater,
+            _ => {}
         }
     }
+
+    natural_cmp(a, b)
+}
+
+/// Checks whether `text` matches the glob `pattern` as a whole.
+///
+/// `*` matches any run of characters (including none) and `?` matches
+/// exactly one character. Matching is case-sensitive; see
+/// [`StringFilter`](crate::StringFilter) for case-insensitive matching.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::utils::glob_match;
+///
+/// assert!(glob_match("*- Notepad", "notes.txt - Notepad"));
+/// assert!(glob_match("Window ?", "Window 1"));
+/// assert!(!glob_match("Window ?", "Window 10"));
+/// assert!(!glob_match("Notepad", "Notepad++"));
+/// ```
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it currently absorbs up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` absorb one more character and retry
+                Some((star, absorbed)) => {
+                    p = star + 1;
+                    t = absorbed + 1;
+                    backtrack = Some((star, absorbed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Consumes a run of ASCII digits from the iterator.
+#[cfg(feature = "sorting")]
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
+    }
+    digits
+}
+
+/// Parses a sort order (e.g., "1", "-1", "asc", "desc").
+#[cfg(feature = "sorting")]
+fn parse_sort_order(order_str: &str) -> Result<SortOrder> {
+    match order_str {
+        "1" | "asc" => Ok(SortOrder::Asc),
+        "-1" | "desc" => Ok(SortOrder::Desc),
+        _ => Err(WindowError::InvalidSortOrder),
+    }
 }
 
 /// Parses a single position sort order (e.g., "x1" -> 1).
@@ -127,6 +645,197 @@ fn parse_single_position_order(part: &str, expected_prefix: char) -> Result<i8>
     }
 }
 
+/// Parses a query string that combines filtering, sorting, and selection.
+///
+/// A query is a whitespace-separated list of terms. Values containing spaces
+/// can be wrapped in double quotes.
+///
+/// | Term | Effect |
+/// |------|--------|
+/// | `title~v`, `class~v`, `process~v`, `file~v`, `cmd~v`, `aumid~v`, `user~v`, `product~v` | field contains `v` |
+/// | `spawned_by~v` | an ancestor process name contains `v` |
+/// | `title!~v`, `class!~v`, `process!~v` | field does not contain `v` |
+/// | `title=v`, `class=v`, `process=v` | field equals `v`, or matches it as a glob if `v` contains `*` or `?` |
+/// | `pid=1234`, `pid=1,2` | PID is one of the listed values |
+/// | `pid!=1234` | PID is not one of the listed values |
+/// | `thread=5678`, `session=1`, `monitor=1` | exact thread ID, session, or monitor index |
+/// | `width>=800`, `width<=1920`, `height>=600`, `height<=1080` | size bounds |
+/// | `exclude:tool`, `exclude:cloaked`, `exclude:ghost` | drop tool, cloaked, or ghost windows |
+/// | `exclude:system` | drop shell and system windows (taskbar, desktop, tooltips, ...) |
+/// | `exclude:hung`, `exclude:responding` | drop hung or responding windows |
+/// | `exclude:topmost`, `topmost=true` | drop or keep only always-on-top windows |
+/// | `fullscreen=true`, `fullscreen=false` | keep only fullscreen or other windows |
+/// | `elevated=true`, `elevated=false` | keep only elevated or non-elevated processes |
+/// | `arch=x86`, `arch=x64`, `arch=arm64` | keep only processes of that architecture |
+/// | `signed=true`, `signed=false` | keep only validly signed or other executables |
+/// | `sort:<spec>` | sort keys as accepted by [`parse_sort`] |
+/// | `select:<spec>` | selection as accepted by [`parse_selection`] |
+///
+/// The selection applies to positions within the filtered and sorted
+/// results (see [`Selection::ByPosition`]) and defaults to all windows.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::{parse_query, SortKey, SortOrder, StringFilter};
+///
+/// let (criteria, sort_keys, selection) =
+///     parse_query(r#"title~"Visual Studio" pid=1234 sort:title:-1 select:1-3"#).unwrap();
+/// assert_eq!(criteria.title_contains.as_deref(), Some("Visual Studio"));
+/// assert_eq!(criteria.pid, Some(1234));
+/// assert_eq!(sort_keys, vec![SortKey::Title(SortOrder::Desc)]);
+/// assert!(selection.matches_in(3, 10));
+/// assert!(!selection.matches_in(4, 10));
+///
+/// assert!(parse_query("color=red").is_err());
+///
+/// let (criteria, _, _) = parse_query(r#"title="* - Notepad" class=Notepad"#).unwrap();
+/// assert_eq!(criteria.title_matches, Some(StringFilter::glob("* - Notepad")));
+/// assert_eq!(criteria.class_name_matches, Some(StringFilter::exact("Notepad")));
+/// ```
+///
+/// # Errors
+/// Returns [`WindowError::InvalidQueryFormat`] for unknown or malformed
+/// terms, or the error of [`parse_sort`] / [`parse_selection`] for invalid
+/// `sort:` and `select:` values.
+#[cfg(all(feature = "sorting", feature = "selection"))]
+pub fn parse_query(query: &str) -> Result<(FilterCriteria, Vec<SortKey>, Selection)> {
+    let mut criteria = FilterCriteria::default();
+    let mut sort_keys = Vec::new();
+    let mut selection = Selection::All;
+
+    for term in tokenize_query(query)? {
+        let invalid = || WindowError::InvalidQueryFormat(term.clone());
+
+        if let Some(spec) = term.strip_prefix("sort:") {
+            sort_keys.extend(parse_sort(spec)?);
+            continue;
+        }
+        if let Some(spec) = term.strip_prefix("select:") {
+            selection = parse_selection(spec)?;
+            continue;
+        }
+        if let Some(what) = term.strip_prefix("exclude:") {
+            match what {
+                "tool" | "tool-windows" => criteria.exclude_tool_windows = true,
+                "cloaked" => criteria.exclude_cloaked = true,
+                "ghost" => criteria.exclude_ghost_windows = true,
+                "system" => criteria.exclude_system_windows = true,
+                "hung" => criteria.responding_only = true,
+                "topmost" => criteria.topmost = Some(false),
+                "responding" => criteria.hung_only = true,
+                _ => return Err(invalid()),
+            }
+            continue;
+        }
+
+        // Longer operators first so "!~" is not mistaken for "~"
+        let (field, op, value) = ["!~", "!=", ">=", "<=", "~", "="]
+            .iter()
+            .filter_map(|op| {
+                term.find(op)
+                    .map(|at| (&term[..at], *op, &term[at + op.len()..]))
+            })
+            .min_by_key(|(field, _, _)| field.len())
+            .ok_or_else(invalid)?;
+
+        let field = field.to_lowercase();
+        let value = value.to_string();
+        let number = || value.parse::<i32>().map_err(|_| invalid());
+        let ids = || {
+            value
+                .split(',')
+                .map(|id| id.trim().parse::<u32>().map_err(|_| invalid()))
+                .collect::<Result<Vec<u32>>>()
+        };
+
+        match (field.as_str(), op) {
+            ("title", "~") => criteria.title_contains = Some(value),
+            ("title", "!~") => criteria.title_not_contains = Some(value),
+            ("class", "~") => criteria.class_name_contains = Some(value),
+            ("class", "!~") => criteria.class_name_not_contains = Some(value),
+            ("process", "~") => criteria.process_name_contains = Some(value),
+            ("process", "!~") => criteria.process_name_not_contains = Some(value),
+            ("file", "~") => criteria.process_file_contains = Some(value),
+            ("cmd", "~") => criteria.command_line_contains = Some(value),
+            ("aumid", "~") => criteria.aumid_contains = Some(value),
+            ("user", "~") => criteria.user_contains = Some(value),
+            ("product", "~") => criteria.product_name_contains = Some(value),
+            ("spawned_by", "~") => criteria.spawned_by_contains = Some(value),
+            ("title", "=") => criteria.title_matches = Some(pattern_filter(value)),
+            ("class", "=") => criteria.class_name_matches = Some(pattern_filter(value)),
+            ("process", "=") => criteria.process_name_matches = Some(pattern_filter(value)),
+            ("pid", "=") => match ids()?.as_slice() {
+                [pid] => criteria.pid = Some(*pid),
+                pids => criteria.pids = Some(pids.to_vec()),
+            },
+            ("pid", "!=") => criteria
+                .exclude_pids
+                .get_or_insert_with(Vec::new)
+                .extend(ids()?),
+            ("thread", "=") => criteria.thread_id = Some(value.parse().map_err(|_| invalid())?),
+            ("session", "=") => criteria.session_id = Some(value.parse().map_err(|_| invalid())?),
+            ("monitor", "=") => {
+                criteria.monitor_index = Some(value.parse().map_err(|_| invalid())?)
+            }
+            ("topmost", "=") => criteria.topmost = Some(value.parse().map_err(|_| invalid())?),
+            ("fullscreen", "=") => {
+                criteria.fullscreen = Some(value.parse().map_err(|_| invalid())?)
+            }
+            ("elevated", "=") => criteria.elevated = Some(value.parse().map_err(|_| invalid())?),
+            ("arch", "=") => {
+                criteria.process_arch = Some(ProcessArch::from_name(&value).ok_or_else(invalid)?)
+            }
+            ("signed", "=") => criteria.signed = Some(value.parse().map_err(|_| invalid())?),
+            ("width", ">=") => criteria.min_width = Some(number()?),
+            ("width", "<=") => criteria.max_width = Some(number()?),
+            ("height", ">=") => criteria.min_height = Some(number()?),
+            ("height", "<=") => criteria.max_height = Some(number()?),
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok((criteria, sort_keys, selection.by_position()))
+}
+
+/// Builds a glob filter if `value` contains wildcards, or an exact one otherwise.
+#[cfg(all(feature = "sorting", feature = "selection"))]
+fn pattern_filter(value: String) -> StringFilter {
+    if value.contains(['*', '?']) {
+        StringFilter::glob(value)
+    } else {
+        StringFilter::exact(value)
+    }
+}
+
+/// Splits a query into whitespace-separated terms, honoring double quotes.
+#[cfg(all(feature = "sorting", feature = "selection"))]
+fn tokenize_query(query: &str) -> Result<Vec<String>> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    terms.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(WindowError::InvalidQueryFormat(current));
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+
+    Ok(terms)
+}
+
 /// Parses a string into a usize index.
 #[allow(dead_code)] // ← 添加这个属性，因为函数可能被库用户使用
 fn parse_index(s: &str) -> Result<usize> {
@@ -135,6 +844,22 @@ fn parse_index(s: &str) -> Result<usize> {
 
 /// Checks if a window matches the given filter criteria.
 ///
+/// # Examples
+/// ```
+/// use window_enumerator::{utils::matches_criteria, FilterCriteria, WindowInfo};
+///
+/// let window = WindowInfo {
+///     title: "index.html - DevTools".to_string(),
+///     process_name: "chrome.exe".to_string(),
+///     ..Default::default()
+/// };
+/// let criteria = FilterCriteria::builder()
+///     .process_name_contains("chrome")
+///     .title_not_contains("devtools")
+///     .build();
+/// assert!(!matches_criteria(&window, &criteria));
+/// ```
+///
 /// # Arguments
 ///
 /// * `window` - The window to check
@@ -151,37 +876,56 @@ pub fn matches_criteria(window: &WindowInfo, criteria: &crate::types::FilterCrit
         }
     }
 
+    // PID set filters
+    if let Some(ref pids) = criteria.pids {
+        if !pids.contains(&window.pid) {
+            return false;
+        }
+    }
+    if let Some(ref exclude_pids) = criteria.exclude_pids {
+        if exclude_pids.contains(&window.pid) {
+            return false;
+        }
+    }
+
+    // Thread filter (exact match)
+    if let Some(thread_id) = criteria.thread_id {
+        if window.thread_id != thread_id {
+            return false;
+        }
+    }
+
+    if let Some(session_id) = criteria.session_id {
+        if window.session_id != Some(session_id) {
+            return false;
+        }
+    }
+
     // Title filter (contains, case-insensitive)
     if let Some(ref title_filter) = criteria.title_contains {
-        if !title_filter.is_empty()
-            && !window
-                .title
-                .to_lowercase()
-                .contains(&title_filter.to_lowercase())
-        {
+        if !title_filter.is_empty() && !contains_ignore_case(&window.title, title_filter) {
             return false;
         }
     }
 
     // Class name filter (contains, case-insensitive)
     if let Some(ref class_filter) = criteria.class_name_contains {
-        if !class_filter.is_empty()
-            && !window
-                .class_name
-                .to_lowercase()
-                .contains(&class_filter.to_lowercase())
-        {
+        if !class_filter.is_empty() && !contains_ignore_case(&window.class_name, class_filter) {
             return false;
         }
     }
 
-    // Process name filter (contains, case-insensitive)
+    // Process name filter (contains, case-insensitive), also matching the
+    // app behind a UWP frame window
     if let Some(ref process_filter) = criteria.process_name_contains {
+        let process_filter = fold_case(process_filter);
+        let matches_name = |name: &str| fold_case(name).contains(&process_filter);
         if !process_filter.is_empty()
+            && !matches_name(&window.process_name)
             && !window
-                .process_name
-                .to_lowercase()
-                .contains(&process_filter.to_lowercase())
+                .uwp_app
+                .as_ref()
+                .is_some_and(|app| matches_name(&app.process_name))
         {
             return false;
         }
@@ -190,12 +934,251 @@ pub fn matches_criteria(window: &WindowInfo, criteria: &crate::types::FilterCrit
     // Process file filter (contains, case-insensitive)
     if let Some(ref file_filter) = criteria.process_file_contains {
         if !file_filter.is_empty() {
-            let file_str = window.process_file.to_string_lossy().to_lowercase();
-            if !file_str.contains(&file_filter.to_lowercase()) {
+            // Match either the Win32 path or the NT device path
+            let file_filter = fold_case(file_filter);
+            let matches_path =
+                |path: &std::path::Path| fold_case(&path.to_string_lossy()).contains(&file_filter);
+            if !matches_path(&window.process_file) && !matches_path(&window.process_device_path) {
                 return false;
             }
         }
     }
 
+    // Elevation and integrity level filters
+    if let Some(elevated) = criteria.elevated {
+        if window.is_elevated != Some(elevated) {
+            return false;
+        }
+    }
+    if let Some(max_level) = criteria.max_integrity_level {
+        if window.integrity_level.is_none_or(|level| level > max_level) {
+            return false;
+        }
+    }
+    if let Some(arch) = criteria.process_arch {
+        if window.process_arch != Some(arch) {
+            return false;
+        }
+    }
+
+    // Signature filter
+    if let Some(signed) = criteria.signed {
+        match window.signature_status {
+            Some(status) if (status == SignatureStatus::Valid) == signed => {}
+            _ => return false,
+        }
+    }
+
+    // Shell and system window filter
+    if criteria.exclude_system_windows && window.kind.is_system() {
+        return false;
+    }
+
+    // Well-known class filter
+    if let Some(known_class) = criteria.known_class {
+        if !known_class.matches(&window.class_name) {
+            return false;
+        }
+    }
+
+    // Pattern filters (contains, exact, or glob)
+    if let Some(ref filter) = criteria.title_matches {
+        if !filter.matches(&window.title) {
+            return false;
+        }
+    }
+    if let Some(ref filter) = criteria.class_name_matches {
+        if !filter.matches(&window.class_name) {
+            return false;
+        }
+    }
+    if let Some(ref filter) = criteria.process_name_matches {
+        if !filter.matches(&window.process_name)
+            && !window
+                .uwp_app
+                .as_ref()
+                .is_some_and(|app| filter.matches(&app.process_name))
+        {
+            return false;
+        }
+    }
+    if let Some(ref filter) = criteria.process_file_matches {
+        let matches_path = |path: &std::path::Path| filter.matches(&path.to_string_lossy());
+        if !matches_path(&window.process_file) && !matches_path(&window.process_device_path) {
+            return false;
+        }
+    }
+    if let Some(ref filter) = criteria.command_line_matches {
+        if !window
+            .process_command_line
+            .as_deref()
+            .is_some_and(|command_line| filter.matches(command_line))
+        {
+            return false;
+        }
+    }
+
+    // Exclusion filters (not contains, case-insensitive)
+    let exclusions = [
+        (&criteria.title_not_contains, &window.title),
+        (&criteria.class_name_not_contains, &window.class_name),
+        (&criteria.process_name_not_contains, &window.process_name),
+    ];
+    for (filter, value) in exclusions {
+        if let Some(ref filter) = filter {
+            if !filter.is_empty() && contains_ignore_case(value, filter) {
+                return false;
+            }
+        }
+    }
+
+    // AUMID filter (contains, case-insensitive)
+    if let Some(ref aumid_filter) = criteria.aumid_contains {
+        if !aumid_filter.is_empty() {
+            match window.aumid {
+                Some(ref aumid) if contains_ignore_case(aumid, aumid_filter) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    // Owning account filter (contains, case-insensitive)
+    if let Some(ref user_filter) = criteria.user_contains {
+        if !user_filter.is_empty() {
+            match window.user_name {
+                Some(ref user_name) if contains_ignore_case(user_name, user_filter) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    // Product name filter (contains, case-insensitive)
+    if let Some(ref product_filter) = criteria.product_name_contains {
+        if !product_filter.is_empty() {
+            match window.product_name {
+                Some(ref product_name) if contains_ignore_case(product_name, product_filter) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    // Ancestor filter (contains, case-insensitive)
+    if let Some(ref spawned_by_filter) = criteria.spawned_by_contains {
+        if !spawned_by_filter.is_empty() {
+            match window.parent_chain {
+                Some(ref chain)
+                    if chain
+                        .iter()
+                        .any(|name| contains_ignore_case(name, spawned_by_filter)) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    // Command line filter (contains, case-insensitive)
+    if let Some(ref command_line_filter) = criteria.command_line_contains {
+        if !command_line_filter.is_empty() {
+            match window.process_command_line {
+                Some(ref command_line)
+                    if contains_ignore_case(command_line, command_line_filter) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    // Size filters (inclusive bounds)
+    let position = &window.position;
+    if criteria.min_width.is_some_and(|min| position.width < min)
+        || criteria.min_height.is_some_and(|min| position.height < min)
+        || criteria.max_width.is_some_and(|max| position.width > max)
+        || criteria.max_height.is_some_and(|max| position.height > max)
+    {
+        return false;
+    }
+
+    // Region filter (window must overlap the rectangle)
+    if let Some(ref rect) = criteria.intersects_rect {
+        if !position.intersects(rect) {
+            return false;
+        }
+    }
+
+    // Monitor filter (exact match)
+    if criteria.monitor_index.is_some() && window.monitor_index != criteria.monitor_index {
+        return false;
+    }
+
+    // Style filters
+    if criteria.exclude_tool_windows && window.is_tool_window() {
+        return false;
+    }
+
+    // Cloaking filter
+    if criteria.exclude_cloaked && window.is_cloaked {
+        return false;
+    }
+
+    // Ghost window filter
+    if criteria.exclude_ghost_windows && window.is_ghost() {
+        return false;
+    }
+
+    // Always-on-top filter
+    if let Some(topmost) = criteria.topmost {
+        if window.is_topmost() != topmost {
+            return false;
+        }
+    }
+
+    // Fullscreen filter
+    if let Some(fullscreen) = criteria.fullscreen {
+        if window.is_fullscreen() != fullscreen {
+            return false;
+        }
+    }
+
+    // Responsiveness filters
+    if criteria.responding_only && window.is_hung {
+        return false;
+    }
+    if criteria.hung_only && !window.is_hung {
+        return false;
+    }
+
     true
 }
+
+/// Checks if a window matches the given filter expression.
+///
+/// # Examples
+/// ```
+/// use window_enumerator::{utils::matches_filter, Filter, FilterCriteria, WindowInfo};
+///
+/// let window = WindowInfo {
+///     process_name: "firefox.exe".to_string(),
+///     ..Default::default()
+/// };
+/// let browsers = Filter::Or(vec![
+///     FilterCriteria::builder().process_name_contains("chrome").build().into(),
+///     FilterCriteria::builder().process_name_contains("firefox").build().into(),
+/// ]);
+/// assert!(matches_filter(&window, &browsers));
+/// assert!(!matches_filter(&window, &Filter::Not(Box::new(browsers))));
+/// ```
+///
+/// # Arguments
+///
+/// * `window` - The window to check
+/// * `filter` - The filter expression to evaluate
+///
+/// # Returns
+///
+/// `true` if the window satisfies the expression, `false` otherwise.
+pub fn matches_filter(window: &WindowInfo, filter: &Filter) -> bool {
+    match filter {
+        Filter::And(filters) => filters.iter().all(|filter| matches_filter(window, filter)),
+        Filter::Or(filters) => filters.iter().any(|filter| matches_filter(window, filter)),
+        Filter::Not(filter) => !matches_filter(window, filter),
+        Filter::Leaf(criteria) => matches_criteria(window, criteria),
+    }
+}
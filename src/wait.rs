@@ -0,0 +1,239 @@
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::HWND;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::{Result, WindowError};
+use crate::types::{FilterCriteria, WindowEvent, WindowHandle, WindowInfo};
+use crate::utils;
+use crate::watcher::WindowWatcher;
+
+/// How often the waits without an explicit interval check their condition.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Returns the time left until `deadline`, or `Duration::MAX` without one,
+/// i.e. when the timeout was too large to represent and the wait is
+/// indefinite.
+fn time_left(deadline: Option<Instant>) -> Duration {
+    deadline.map_or(Duration::MAX, |deadline| {
+        deadline.saturating_duration_since(Instant::now())
+    })
+}
+
+/// Waiting for windows to appear or disappear.
+impl WindowEnumerator {
+    /// Blocks until a window matching `criteria` exists, re-enumerating
+    /// every `poll_interval`.
+    ///
+    /// Enumerations use the options of the previous run, see
+    /// [`enumerate_all_windows_with`](Self::enumerate_all_windows_with).
+    /// The window list reflects the last enumeration afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::TimedOut`] if no window matched within
+    /// `timeout`, and [`WindowError::ApiCallFailed`] if an enumeration fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// std::process::Command::new("notepad.exe").spawn().unwrap();
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// let criteria = FilterCriteria::builder().process_name_contains("notepad").build();
+    /// let window = enumerator
+    ///     .wait_for_window(&criteria, Duration::from_secs(10), Duration::from_millis(100))
+    ///     .unwrap();
+    /// println!("Notepad opened: {}", window.hwnd);
+    /// ```
+    pub fn wait_for_window(
+        &mut self,
+        criteria: &FilterCriteria,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<WindowInfo> {
+        let deadline = Instant::now().checked_add(timeout);
+        loop {
+            if let Some(window) = self.find_matching_window(criteria)? {
+                return Ok(window);
+            }
+
+            let remaining = time_left(deadline);
+            if remaining.is_zero() {
+                return Err(WindowError::TimedOut);
+            }
+            std::thread::sleep(poll_interval.min(remaining));
+        }
+    }
+
+    /// Blocks until a window matching `criteria` exists, reacting to window
+    /// events instead of polling.
+    ///
+    /// Windows already open are checked once; afterwards only windows that
+    /// are created, renamed, or moved are inspected, so a match is found
+    /// as soon as it appears without repeated full enumerations.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::TimedOut`] if no window matched within
+    /// `timeout`, [`WindowError::ApiCallFailed`] if the enumeration fails,
+    /// and [`WindowError::Other`] if the event hooks cannot be installed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// let criteria = FilterCriteria::builder().title_contains("Setup complete").build();
+    /// let window = enumerator
+    ///     .wait_for_window_event(&criteria, Duration::from_secs(60))
+    ///     .unwrap();
+    /// ```
+    pub fn wait_for_window_event(
+        &mut self,
+        criteria: &FilterCriteria,
+        timeout: Duration,
+    ) -> Result<WindowInfo> {
+        let deadline = Instant::now().checked_add(timeout);
+
+        // Install the hooks first so a window appearing during the initial
+        // enumeration still produces an event
+        let watcher = WindowWatcher::start()?;
+        if let Some(window) = self.find_matching_window(criteria)? {
+            return Ok(window);
+        }
+
+        loop {
+            let hwnd = match watcher.recv_timeout(time_left(deadline)) {
+                Some(
+                    WindowEvent::Created { hwnd }
+                    | WindowEvent::TitleChanged { hwnd, .. }
+                    | WindowEvent::Moved { hwnd, .. }
+                    | WindowEvent::Foreground { hwnd },
+                ) => hwnd,
+                Some(_) => continue,
+                None => return Err(WindowError::TimedOut),
+            };
+
+            // Windows destroyed in the meantime simply don't match
+            if let Ok(window) = self.get_single_window_info(hwnd.into()) {
+                if (window.is_visible || self.options.include_hidden)
+                    && utils::matches_criteria(&window, criteria)
+                {
+                    return Ok(window);
+                }
+            }
+        }
+    }
+
+    /// Blocks until the given window is closed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the window was closed within `timeout`, `false` otherwise.
+    /// A handle that was never valid counts as closed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// enumerator.enumerate_all_windows().unwrap();
+    /// let criteria = FilterCriteria::builder().title_contains("Installing").build();
+    /// if let Some(window) = enumerator.filter_windows(&criteria).first() {
+    ///     if !WindowEnumerator::wait_for_close(window.hwnd, Duration::from_secs(300)) {
+    ///         eprintln!("installer is still running");
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_for_close(hwnd: WindowHandle, timeout: Duration) -> bool {
+        let (thread_id, _) = unsafe { Self::get_thread_process_id(HWND::from(hwnd)) };
+        Self::wait_for_thread_window_close(hwnd, thread_id, timeout)
+    }
+
+    /// Blocks until `hwnd` no longer names a window of the thread
+    /// `thread_id`, i.e. until it is closed or its handle was recycled.
+    pub(crate) fn wait_for_thread_window_close(
+        hwnd: WindowHandle,
+        thread_id: u32,
+        timeout: Duration,
+    ) -> bool {
+        let hwnd = HWND::from(hwnd);
+        let deadline = Instant::now().checked_add(timeout);
+
+        // A recycled handle belongs to another thread, so the window is gone
+        loop {
+            let (current_thread_id, _) = unsafe { Self::get_thread_process_id(hwnd) };
+            if current_thread_id == 0 || current_thread_id != thread_id {
+                return true;
+            }
+
+            let remaining = time_left(deadline);
+            if remaining.is_zero() {
+                return false;
+            }
+            std::thread::sleep(WAIT_POLL_INTERVAL.min(remaining));
+        }
+    }
+
+    /// Blocks until no window matches `criteria`, re-enumerating
+    /// periodically with the options of the previous run.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no window matched within `timeout`, `false` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::ApiCallFailed`] if an enumeration fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::{FilterCriteria, WindowEnumerator};
+    ///
+    /// let mut enumerator = WindowEnumerator::new();
+    /// let criteria = FilterCriteria::builder().class_name_contains("MsiDialog").build();
+    /// let finished = enumerator
+    ///     .wait_until_no_match(&criteria, Duration::from_secs(600))
+    ///     .unwrap();
+    /// ```
+    pub fn wait_until_no_match(
+        &mut self,
+        criteria: &FilterCriteria,
+        timeout: Duration,
+    ) -> Result<bool> {
+        let deadline = Instant::now().checked_add(timeout);
+        loop {
+            if self.find_matching_window(criteria)?.is_none() {
+                return Ok(true);
+            }
+
+            let remaining = time_left(deadline);
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+            std::thread::sleep(WAIT_POLL_INTERVAL.min(remaining));
+        }
+    }
+
+    /// Re-enumerates with the previous options and returns the first window
+    /// matching `criteria`.
+    fn find_matching_window(&mut self, criteria: &FilterCriteria) -> Result<Option<WindowInfo>> {
+        let options = self.options.clone();
+        self.enumerate_all_windows_with(&options)?;
+        Ok(self
+            .get_windows()
+            .iter()
+            .find(|window| utils::matches_criteria(window, criteria))
+            .cloned())
+    }
+}
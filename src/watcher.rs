@@ -0,0 +1,946 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::mem::Discriminant;
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+use windows::Win32::Foundation::*;
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Accessibility::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::enumerator::WindowEnumerator;
+use crate::errors::{Result, WindowError};
+use crate::types::{
+    EnumerationOptions, FilterCriteria, OverflowPolicy, SubscriptionOptions, WindowEvent,
+    WindowHandle, WindowPosition, WindowState,
+};
+use crate::utils;
+
+/// A callback receiving events on the hook thread.
+type EventSink = Box<dyn FnMut(WindowEvent)>;
+
+thread_local! {
+    /// The event sink of the hook thread running on this thread, if any.
+    static EVENT_SINK: RefCell<Option<EventSink>> = RefCell::new(None);
+
+    /// Positions of the windows the user is currently dragging, keyed by HWND.
+    static DRAG_STARTS: RefCell<HashMap<isize, WindowPosition>> = RefCell::new(HashMap::new());
+
+    /// The last observed state of each window, keyed by HWND.
+    static WINDOW_STATES: RefCell<HashMap<isize, WindowState>> = RefCell::new(HashMap::new());
+
+    /// The top-level windows known to exist, keyed by HWND.
+    ///
+    /// A destroyed window no longer has a parent to check, so destruction
+    /// is only reported for windows recorded here.
+    static TOP_LEVEL_WINDOWS: RefCell<HashSet<isize>> = RefCell::new(HashSet::new());
+}
+
+/// A dedicated thread owning WinEvent hooks and pumping their message loop.
+///
+/// The thread is stopped and joined when this value is dropped.
+pub(crate) struct HookThread {
+    thread_id: u32,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl HookThread {
+    /// Spawns the hook thread, delivering every event to `make_sink()`.
+    ///
+    /// The sink is constructed on the hook thread itself, so it does not
+    /// need to be `Send` once created.
+    pub(crate) fn spawn<F>(make_sink: F) -> Result<Self>
+    where
+        F: FnOnce() -> EventSink + Send + 'static,
+    {
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<u32>>();
+
+        let handle = std::thread::spawn(move || unsafe {
+            // Force creation of the message queue before reporting readiness,
+            // so that PostThreadMessageW(WM_QUIT) cannot be lost.
+            let mut msg = MSG::default();
+            let _ = PeekMessageW(&mut msg, HWND::default(), WM_USER, WM_USER, PM_NOREMOVE);
+
+            let hooks = [
+                (EVENT_OBJECT_CREATE, EVENT_OBJECT_DESTROY),
+                (EVENT_OBJECT_LOCATIONCHANGE, EVENT_OBJECT_NAMECHANGE),
+                (EVENT_SYSTEM_FOREGROUND, EVENT_SYSTEM_FOREGROUND),
+                (EVENT_SYSTEM_MOVESIZESTART, EVENT_SYSTEM_MOVESIZEEND),
+                (EVENT_SYSTEM_MINIMIZESTART, EVENT_SYSTEM_MINIMIZEEND),
+            ]
+            .map(|(min, max)| {
+                SetWinEventHook(
+                    min,
+                    max,
+                    HMODULE::default(),
+                    Some(win_event_proc),
+                    0,
+                    0,
+                    // Windows of the host process are reported as well, so
+                    // e.g. focus moving to them is not missed
+                    WINEVENT_OUTOFCONTEXT,
+                )
+            });
+
+            if hooks.iter().any(|hook| hook.is_invalid()) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %windows::core::Error::from_win32(), "SetWinEventHook failed");
+                for hook in hooks.iter().filter(|hook| !hook.is_invalid()) {
+                    let _ = UnhookWinEvent(*hook);
+                }
+                let _ = ready_tx.send(Err(WindowError::Other(
+                    "Failed to install window event hooks".to_string(),
+                )));
+                return;
+            }
+
+            // Windows created from now on are recorded by their events
+            let _ = EnumWindows(Some(record_top_level_window), LPARAM(0));
+
+            EVENT_SINK.with(|sink| *sink.borrow_mut() = Some(make_sink()));
+            let _ = ready_tx.send(Ok(GetCurrentThreadId()));
+
+            // Pump messages until WM_QUIT so the hook callbacks get delivered
+            while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+
+            for hook in hooks {
+                let _ = UnhookWinEvent(hook);
+            }
+            EVENT_SINK.with(|sink| *sink.borrow_mut() = None);
+            DRAG_STARTS.with(|starts| starts.borrow_mut().clear());
+            WINDOW_STATES.with(|states| states.borrow_mut().clear());
+            TOP_LEVEL_WINDOWS.with(|windows| windows.borrow_mut().clear());
+        });
+
+        match ready_rx.recv() {
+            Ok(Ok(thread_id)) => Ok(Self {
+                thread_id,
+                handle: Some(handle),
+            }),
+            Ok(Err(error)) => {
+                let _ = handle.join();
+                Err(error)
+            }
+            Err(_) => {
+                let _ = handle.join();
+                Err(WindowError::Other(
+                    "Window event hook thread exited unexpectedly".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl Drop for HookThread {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// `EnumWindows` callback recording the existing top-level windows.
+unsafe extern "system" fn record_top_level_window(hwnd: HWND, _: LPARAM) -> BOOL {
+    TOP_LEVEL_WINDOWS.with(|windows| windows.borrow_mut().insert(hwnd.0));
+    TRUE
+}
+
+/// WinEvent hook callback translating raw events into [`WindowEvent`]s.
+unsafe extern "system" fn win_event_proc(
+    _hook: HWINEVENTHOOK,
+    event: u32,
+    hwnd: HWND,
+    id_object: i32,
+    id_child: i32,
+    _event_thread: u32,
+    _event_time: u32,
+) {
+    // Only report events about windows themselves, not their child objects
+    if hwnd.0 == 0 || id_object != OBJID_WINDOW.0 || id_child != CHILDID_SELF as i32 {
+        return;
+    }
+
+    let window_event = match event {
+        EVENT_OBJECT_DESTROY => {
+            DRAG_STARTS.with(|starts| starts.borrow_mut().remove(&hwnd.0));
+            WINDOW_STATES.with(|states| states.borrow_mut().remove(&hwnd.0));
+            if !TOP_LEVEL_WINDOWS.with(|windows| windows.borrow_mut().remove(&hwnd.0)) {
+                return;
+            }
+            WindowEvent::Destroyed { hwnd: hwnd.into() }
+        }
+        _ if GetAncestor(hwnd, GA_PARENT) != GetDesktopWindow() => return,
+        EVENT_OBJECT_CREATE => {
+            TOP_LEVEL_WINDOWS.with(|windows| windows.borrow_mut().insert(hwnd.0));
+            WindowEvent::Created { hwnd: hwnd.into() }
+        }
+        EVENT_OBJECT_NAMECHANGE => WindowEvent::TitleChanged {
+            hwnd: hwnd.into(),
+            title: WindowEnumerator::get_window_text(hwnd),
+        },
+        EVENT_OBJECT_LOCATIONCHANGE => {
+            // Maximizing has no event of its own, but always moves the window
+            if let Some(event) = state_change(hwnd, None) {
+                emit(event);
+            }
+            WindowEvent::Moved {
+                hwnd: hwnd.into(),
+                position: WindowEnumerator::get_window_position(hwnd),
+            }
+        }
+        EVENT_SYSTEM_MINIMIZESTART => {
+            // The window may already be iconic, so ask where it will restore to
+            let mut placement = WINDOWPLACEMENT {
+                length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+                ..Default::default()
+            };
+            let restores_maximized = GetWindowPlacement(hwnd, &mut placement).is_ok()
+                && placement.flags.0 & WPF_RESTORETOMAXIMIZED.0 != 0;
+            let before = if restores_maximized {
+                WindowState::Maximized
+            } else {
+                WindowState::Normal
+            };
+            match state_change(hwnd, Some(before)) {
+                Some(event) => event,
+                None => return,
+            }
+        }
+        EVENT_SYSTEM_MINIMIZEEND => match state_change(hwnd, Some(WindowState::Minimized)) {
+            Some(event) => event,
+            None => return,
+        },
+        EVENT_SYSTEM_FOREGROUND => WindowEvent::Foreground { hwnd: hwnd.into() },
+        EVENT_SYSTEM_MOVESIZESTART => {
+            let position = WindowEnumerator::get_window_position(hwnd);
+            DRAG_STARTS.with(|starts| starts.borrow_mut().insert(hwnd.0, position));
+            return;
+        }
+        EVENT_SYSTEM_MOVESIZEEND => {
+            let new = WindowEnumerator::get_window_position(hwnd);
+            let old = DRAG_STARTS
+                .with(|starts| starts.borrow_mut().remove(&hwnd.0))
+                .unwrap_or(new);
+            WindowEvent::MoveSizeEnded {
+                hwnd: hwnd.into(),
+                old,
+                new,
+            }
+        }
+        _ => return,
+    };
+
+    emit(window_event);
+}
+
+/// Delivers an event to the sink of the hook thread.
+fn emit(event: WindowEvent) {
+    EVENT_SINK.with(|sink| {
+        if let Some(sink) = sink.borrow_mut().as_mut() {
+            sink(event);
+        }
+    });
+}
+
+/// Records the current state of a window and reports it if it changed.
+///
+/// `fallback` is assumed to be the previous state of windows not seen
+/// before; without it, their first observation is only recorded.
+unsafe fn state_change(hwnd: HWND, fallback: Option<WindowState>) -> Option<WindowEvent> {
+    let new = if IsIconic(hwnd).as_bool() {
+        WindowState::Minimized
+    } else if IsZoomed(hwnd).as_bool() {
+        WindowState::Maximized
+    } else {
+        WindowState::Normal
+    };
+
+    let old = WINDOW_STATES.with(|states| states.borrow_mut().insert(hwnd.0, new));
+    match old.or(fallback) {
+        Some(old) if old != new => Some(WindowEvent::StateChanged {
+            hwnd: hwnd.into(),
+            old,
+            new,
+        }),
+        _ => None,
+    }
+}
+
+/// Watches top-level windows for creation, destruction, title, position,
+/// foreground, and minimize/maximize state changes, as well as the end of
+/// user drags.
+///
+/// Events are collected by WinEvent hooks running on a dedicated thread and
+/// can be received through the blocking or polling methods below. The hooks
+/// are removed when the watcher is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use window_enumerator::{WindowEvent, WindowWatcher};
+///
+/// let watcher = WindowWatcher::start().unwrap();
+/// while let Some(event) = watcher.recv() {
+///     if let WindowEvent::TitleChanged { hwnd, title } = event {
+///         println!("{}: {}", hwnd, title);
+///     }
+/// }
+/// ```
+pub struct WindowWatcher {
+    receiver: mpsc::Receiver<WindowEvent>,
+    title_history: Option<Arc<Mutex<TitleHistory>>>,
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
+    _thread: HookThread,
+}
+
+/// A receiver registered with [`WindowWatcher::subscribe_with`].
+struct Subscription {
+    criteria: FilterCriteria,
+    queue: Arc<EventQueue>,
+    /// Windows that matched the last time an event about them arrived, so
+    /// their destruction can still be reported.
+    matched: HashSet<WindowHandle>,
+}
+
+/// Delivers events to the subscriptions whose criteria match the window.
+///
+/// Lives on the hook thread, which owns the enumerator used to look up
+/// the windows.
+struct SubscriptionRouter {
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
+    enumerator: WindowEnumerator,
+    /// The owning process of each window looked up so far, since it can no
+    /// longer be queried once the window is destroyed.
+    pids: HashMap<WindowHandle, u32>,
+}
+
+impl SubscriptionRouter {
+    fn dispatch(&mut self, event: &WindowEvent) {
+        let queues = self.route(event);
+        if queues.is_empty() {
+            return;
+        }
+
+        // Pushing may block, so the subscriptions must not stay locked
+        let mut closed = false;
+        for queue in &queues {
+            closed |= !queue.push(event.clone());
+        }
+        if closed {
+            lock(&self.subscriptions).retain(|subscription| !subscription.queue.is_closed());
+        }
+    }
+
+    /// Returns the queues of the subscriptions the event is delivered to.
+    fn route(&mut self, event: &WindowEvent) -> Vec<Arc<EventQueue>> {
+        let mut subscriptions = lock(&self.subscriptions);
+        if subscriptions.is_empty() {
+            return Vec::new();
+        }
+        let hwnd = event.hwnd();
+
+        // A destroyed window can no longer be inspected, so it is reported
+        // to whoever saw it match last
+        if let WindowEvent::Destroyed { .. } = event {
+            // Processes close their windows before exiting, after which
+            // their PIDs may be reused
+            if let Some(pid) = self.pids.remove(&hwnd) {
+                if !self.pids.values().any(|&other| other == pid) {
+                    self.enumerator.evict_process(pid);
+                }
+            }
+            return subscriptions
+                .iter_mut()
+                .filter_map(|subscription| {
+                    subscription
+                        .matched
+                        .remove(&hwnd)
+                        .then(|| Arc::clone(&subscription.queue))
+                })
+                .collect();
+        }
+
+        self.enumerator.options = required_options(&subscriptions);
+        let Ok(mut window) = self.enumerator.get_single_window_info(hwnd.into()) else {
+            return Vec::new();
+        };
+        self.pids.insert(hwnd, window.pid);
+        if subscriptions
+            .iter()
+            .any(|subscription| subscription.criteria.spawned_by_contains.is_some())
+        {
+            window.load_parent_chain();
+        }
+
+        let mut queues = Vec::new();
+        for subscription in subscriptions.iter_mut() {
+            if utils::matches_criteria(&window, &subscription.criteria) {
+                subscription.matched.insert(hwnd);
+                queues.push(Arc::clone(&subscription.queue));
+            } else {
+                subscription.matched.remove(&hwnd);
+            }
+        }
+        queues
+    }
+}
+
+/// The pending events of a subscription, shared between the hook thread
+/// and the [`EventReceiver`].
+struct EventQueue {
+    options: SubscriptionOptions,
+    state: Mutex<QueueState>,
+    /// Signalled when an event is pushed or the queue is closed.
+    pushed: Condvar,
+    /// Signalled when an event is received or the queue is closed.
+    popped: Condvar,
+}
+
+#[derive(Default)]
+struct QueueState {
+    events: VecDeque<QueuedEvent>,
+    /// When the last coalesced event of each window and kind is due.
+    last_due: HashMap<(WindowHandle, Discriminant<WindowEvent>), Instant>,
+    dropped: u64,
+    /// The watcher was dropped, so no more events will arrive.
+    sender_closed: bool,
+    /// The receiver was dropped, so events are no longer wanted.
+    receiver_closed: bool,
+}
+
+/// An event waiting to be received.
+struct QueuedEvent {
+    event: WindowEvent,
+    /// When the event may be received, later than its arrival while it is
+    /// still collecting coalesced events.
+    due: Instant,
+}
+
+impl EventQueue {
+    fn new(options: SubscriptionOptions) -> Self {
+        Self {
+            options,
+            state: Mutex::new(QueueState::default()),
+            pushed: Condvar::new(),
+            popped: Condvar::new(),
+        }
+    }
+
+    /// Adds an event, coalescing it and applying the overflow policy.
+    ///
+    /// Returns `false` if the receiver has been dropped.
+    fn push(&self, event: WindowEvent) -> bool {
+        let mut state = lock(&self.state);
+        if state.receiver_closed {
+            return false;
+        }
+        let now = Instant::now();
+        let hwnd = event.hwnd();
+
+        let due = match self.options.coalesce {
+            Some(interval)
+                if matches!(
+                    event,
+                    WindowEvent::Moved { .. } | WindowEvent::TitleChanged { .. }
+                ) =>
+            {
+                let key = (hwnd, std::mem::discriminant(&event));
+                // Only the latest event of the window can absorb it, or
+                // its events would get out of order
+                let pending = state
+                    .events
+                    .iter_mut()
+                    .rev()
+                    .find(|queued| queued.event.hwnd() == hwnd)
+                    .filter(|queued| std::mem::discriminant(&queued.event) == key.1);
+                if let Some(pending) = pending {
+                    pending.event = event;
+                    return true;
+                }
+                // Intervals too large to represent don't hold events back
+                let due = state
+                    .last_due
+                    .get(&key)
+                    .and_then(|last| last.checked_add(interval))
+                    .map_or(now, |due| due.max(now));
+                state.last_due.insert(key, due);
+                due
+            }
+            Some(_) => {
+                // Release held back events of the window so its events
+                // stay in order
+                for queued in state
+                    .events
+                    .iter_mut()
+                    .filter(|queued| queued.event.hwnd() == hwnd)
+                {
+                    queued.due = queued.due.min(now);
+                }
+                // Handles are recycled, so forget closed windows
+                if let WindowEvent::Destroyed { .. } = event {
+                    state.last_due.retain(|(key_hwnd, _), _| *key_hwnd != hwnd);
+                }
+                now
+            }
+            None => now,
+        };
+
+        if let Some(capacity) = self.options.capacity {
+            match self.options.overflow {
+                OverflowPolicy::DropOldest => {
+                    while state.events.len() >= capacity.max(1) {
+                        state.events.pop_front();
+                        state.dropped += 1;
+                    }
+                }
+                OverflowPolicy::Block => {
+                    while state.events.len() >= capacity.max(1) {
+                        if state.receiver_closed {
+                            return false;
+                        }
+                        if state.sender_closed {
+                            return true;
+                        }
+                        state = self
+                            .popped
+                            .wait(state)
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    }
+                }
+            }
+        }
+
+        state.events.push_back(QueuedEvent { event, due });
+        self.pushed.notify_one();
+        true
+    }
+
+    /// Takes the first event that is due, waiting until `deadline` if there
+    /// is none.
+    ///
+    /// Returns `None` once the deadline passes, or the watcher was dropped
+    /// and all events have been received.
+    fn pop(&self, deadline: Option<Instant>) -> Option<WindowEvent> {
+        let mut state = lock(&self.state);
+        loop {
+            let now = Instant::now();
+            if let Some(index) = state.events.iter().position(|queued| queued.due <= now) {
+                let event = state.events.remove(index).map(|queued| queued.event);
+                self.popped.notify_one();
+                return event;
+            }
+            if state.sender_closed && state.events.is_empty() {
+                return None;
+            }
+
+            // Wake up for the next held back event or the deadline
+            let wake = state.events.iter().map(|queued| queued.due).min();
+            let wake = match (wake, deadline) {
+                (Some(wake), Some(deadline)) => Some(wake.min(deadline)),
+                (wake, deadline) => wake.or(deadline),
+            };
+            state = match wake {
+                Some(wake) if wake <= now => return None,
+                Some(wake) => {
+                    self.pushed
+                        .wait_timeout(state, wake - now)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .0
+                }
+                None => self
+                    .pushed
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            };
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        lock(&self.state).receiver_closed
+    }
+
+    /// Marks one end of the queue as gone and wakes up the other end.
+    fn close(&self, receiver: bool) {
+        let mut state = lock(&self.state);
+        if receiver {
+            state.receiver_closed = true;
+        } else {
+            state.sender_closed = true;
+        }
+        self.pushed.notify_all();
+        self.popped.notify_all();
+    }
+}
+
+/// Receives the events of a [`WindowWatcher::subscribe`] subscription.
+///
+/// Dropping the receiver ends the subscription. Once the watcher is
+/// dropped, the remaining events can still be received, after which the
+/// methods return `None`.
+pub struct EventReceiver {
+    queue: Arc<EventQueue>,
+}
+
+impl EventReceiver {
+    /// Blocks until the next event is available.
+    ///
+    /// Returns `None` if the watcher has been dropped.
+    pub fn recv(&self) -> Option<WindowEvent> {
+        self.queue.pop(None)
+    }
+
+    /// Blocks until the next event is available or the timeout elapses.
+    ///
+    /// A timeout too large to represent waits like [`recv`](Self::recv).
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<WindowEvent> {
+        self.queue.pop(Instant::now().checked_add(timeout))
+    }
+
+    /// Returns the next event if one is immediately available.
+    pub fn try_recv(&self) -> Option<WindowEvent> {
+        self.queue.pop(Some(Instant::now()))
+    }
+
+    /// Returns an iterator blocking for each event until the watcher is
+    /// dropped.
+    pub fn iter(&self) -> impl Iterator<Item = WindowEvent> + '_ {
+        std::iter::from_fn(|| self.recv())
+    }
+
+    /// Returns how many events were discarded under
+    /// [`OverflowPolicy::DropOldest`] because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        lock(&self.queue.state).dropped
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        self.queue.close(true);
+    }
+}
+
+/// Returns the options gathering the properties the criteria of all
+/// subscriptions need, leaving out costly ones nobody filters on.
+fn required_options(subscriptions: &[Subscription]) -> EnumerationOptions {
+    let any = |check: fn(&FilterCriteria) -> bool| {
+        subscriptions
+            .iter()
+            .any(|subscription| check(&subscription.criteria))
+    };
+    EnumerationOptions {
+        include_user_name: any(|criteria| criteria.user_contains.is_some()),
+        include_version_info: any(|criteria| criteria.product_name_contains.is_some()),
+        #[cfg(feature = "signing")]
+        verify_signatures: any(|criteria| criteria.signed.is_some()),
+        ..Default::default()
+    }
+}
+
+/// The most recent titles of each watched window.
+struct TitleHistory {
+    capacity: usize,
+    entries: HashMap<WindowHandle, VecDeque<(SystemTime, String)>>,
+}
+
+impl TitleHistory {
+    fn record(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::TitleChanged { hwnd, title } => {
+                let titles = self.entries.entry(*hwnd).or_default();
+                // Windows often repeat a title change, which is no transition
+                if titles.back().is_some_and(|(_, last)| last == title) {
+                    return;
+                }
+                if titles.len() == self.capacity {
+                    titles.pop_front();
+                }
+                titles.push_back((SystemTime::now(), title.clone()));
+            }
+            // Handles are recycled, so forget closed windows
+            WindowEvent::Destroyed { hwnd } => {
+                self.entries.remove(hwnd);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl WindowWatcher {
+    /// Starts watching window events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::Other`] if the event hooks cannot be installed.
+    pub fn start() -> Result<Self> {
+        Self::start_with(None, true)
+    }
+
+    /// Starts watching window events that are only delivered to
+    /// [subscriptions](Self::subscribe).
+    ///
+    /// The watcher's own queue stays empty, so [`recv`](Self::recv) returns
+    /// `None` right away, and unread events cannot pile up in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::Other`] if the event hooks cannot be installed.
+    pub fn start_for_subscribers() -> Result<Self> {
+        Self::start_with(None, false)
+    }
+
+    /// Starts watching window events, also remembering the last `capacity`
+    /// titles of each window.
+    ///
+    /// The history is available through [`title_history`](Self::title_history)
+    /// independently of whether the events are received.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::Other`] if the event hooks cannot be installed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{WindowEvent, WindowWatcher};
+    ///
+    /// let watcher = WindowWatcher::start_with_title_history(16).unwrap();
+    /// while let Some(event) = watcher.recv() {
+    ///     if let WindowEvent::TitleChanged { hwnd, .. } = event {
+    ///         for (time, title) in watcher.title_history(hwnd) {
+    ///             println!("{:?}: {}", time, title);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn start_with_title_history(capacity: usize) -> Result<Self> {
+        Self::start_with(
+            Some(Arc::new(Mutex::new(TitleHistory {
+                capacity: capacity.max(1),
+                entries: HashMap::new(),
+            }))),
+            true,
+        )
+    }
+
+    fn start_with(title_history: Option<Arc<Mutex<TitleHistory>>>, queue: bool) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let sender = queue.then_some(sender);
+        let history = title_history.clone();
+        let subscriptions = Arc::new(Mutex::new(Vec::new()));
+        let shared = Arc::clone(&subscriptions);
+        let thread = HookThread::spawn(move || {
+            let mut router = SubscriptionRouter {
+                subscriptions: shared,
+                enumerator: WindowEnumerator::new(),
+                pids: HashMap::new(),
+            };
+            Box::new(move |event| {
+                if let Some(ref history) = history {
+                    lock(history).record(&event);
+                }
+                router.dispatch(&event);
+                if let Some(ref sender) = sender {
+                    let _ = sender.send(event);
+                }
+            })
+        })?;
+
+        Ok(Self {
+            receiver,
+            title_history,
+            subscriptions,
+            _thread: thread,
+        })
+    }
+
+    /// Registers a receiver for the events about windows matching
+    /// `criteria`.
+    ///
+    /// The window of each event is looked up and matched on the hook thread,
+    /// so only relevant events reach the receiver. Properties that are
+    /// costly to query, such as the user name or the parent process chain,
+    /// are only gathered while a subscription filters on them. A
+    /// [`Destroyed`](WindowEvent::Destroyed) event is delivered if the
+    /// previous event about the window matched, since a closed window can no
+    /// longer be inspected.
+    ///
+    /// Subscribers receive events in addition to the watcher's own queue.
+    /// Dropping the receiver ends the subscription.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use window_enumerator::{FilterCriteria, WindowEvent, WindowWatcher};
+    ///
+    /// let watcher = WindowWatcher::start_for_subscribers().unwrap();
+    /// let criteria = FilterCriteria::builder().process_name_contains("notepad").build();
+    /// let events = watcher.subscribe(criteria);
+    /// for event in events.iter() {
+    ///     if let WindowEvent::TitleChanged { title, .. } = event {
+    ///         println!("Notepad: {}", title);
+    ///     }
+    /// }
+    /// ```
+    pub fn subscribe(&self, criteria: FilterCriteria) -> EventReceiver {
+        self.subscribe_with(criteria, SubscriptionOptions::default())
+    }
+
+    /// Registers a receiver for the events about windows matching
+    /// `criteria`, coalescing and bounding its queue as configured.
+    ///
+    /// See [`subscribe`](Self::subscribe) for how events are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use window_enumerator::{
+    ///     FilterCriteria, OverflowPolicy, SubscriptionOptions, WindowEvent, WindowWatcher,
+    /// };
+    ///
+    /// let watcher = WindowWatcher::start_for_subscribers().unwrap();
+    /// let options = SubscriptionOptions {
+    ///     coalesce: Some(Duration::from_millis(100)),
+    ///     capacity: Some(256),
+    ///     overflow: OverflowPolicy::DropOldest,
+    /// };
+    /// let events = watcher.subscribe_with(FilterCriteria::default(), options);
+    /// for event in events.iter() {
+    ///     if let WindowEvent::Moved { hwnd, position } = event {
+    ///         println!("{} moved to {},{}", hwnd, position.x, position.y);
+    ///     }
+    /// }
+    /// ```
+    pub fn subscribe_with(
+        &self,
+        criteria: FilterCriteria,
+        options: SubscriptionOptions,
+    ) -> EventReceiver {
+        let queue = Arc::new(EventQueue::new(options));
+        lock(&self.subscriptions).push(Subscription {
+            criteria,
+            queue: Arc::clone(&queue),
+            matched: HashSet::new(),
+        });
+        EventReceiver { queue }
+    }
+
+    /// Returns the recorded titles of a window, oldest first, with the time
+    /// each title was first seen.
+    ///
+    /// Empty unless the watcher was started with
+    /// [`start_with_title_history`](Self::start_with_title_history). The
+    /// history of a window is dropped once it is destroyed.
+    pub fn title_history(&self, hwnd: WindowHandle) -> Vec<(SystemTime, String)> {
+        self.title_history
+            .as_ref()
+            .and_then(|history| {
+                lock(history)
+                    .entries
+                    .get(&hwnd)
+                    .map(|titles| titles.iter().cloned().collect())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Blocks until the next event is available.
+    ///
+    /// Returns `None` if the hook thread has stopped.
+    pub fn recv(&self) -> Option<WindowEvent> {
+        self.receiver.recv().ok()
+    }
+
+    /// Blocks until the next event is available or the timeout elapses.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<WindowEvent> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+
+    /// Returns the next event if one is immediately available.
+    pub fn try_recv(&self) -> Option<WindowEvent> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl Drop for WindowWatcher {
+    fn drop(&mut self) {
+        // Also releases a hook thread blocked on a full subscription queue
+        for subscription in lock(&self.subscriptions).iter() {
+            subscription.queue.close(false);
+        }
+    }
+}
+
+/// Locks a mutex shared with the hook thread, ignoring poisoning.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// An asynchronous [`Stream`](futures_core::Stream) of window events.
+///
+/// Requires the `tokio` feature. The WinEvent hooks run on a dedicated
+/// thread that pumps its own message loop, so the stream can be polled
+/// from any async runtime task.
+///
+/// # Examples
+///
+/// ```ignore
+/// use futures::StreamExt;
+/// use window_enumerator::WindowEventStream;
+///
+/// # async fn run() {
+/// let mut stream = WindowEventStream::new().unwrap();
+/// while let Some(event) = stream.next().await {
+///     println!("{:?}", event);
+/// }
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub struct WindowEventStream {
+    receiver: tokio::sync::mpsc::UnboundedReceiver<WindowEvent>,
+    _thread: HookThread,
+}
+
+#[cfg(feature = "tokio")]
+impl WindowEventStream {
+    /// Starts watching window events as a stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WindowError::Other`] if the event hooks cannot be installed.
+    pub fn new() -> Result<Self> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let thread = HookThread::spawn(move || {
+            Box::new(move |event| {
+                let _ = sender.send(event);
+            })
+        })?;
+
+        Ok(Self {
+            receiver,
+            _thread: thread,
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl futures_core::Stream for WindowEventStream {
+    type Item = WindowEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}